@@ -1,6 +1,6 @@
 use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
-use std::collections::HashSet;
-use trie_hard_rs::Trie;
+use std::collections::{BTreeMap, HashSet};
+use trie_hard_rs::{ArenaTrie, AsciiTrie, ByteTrie, RadixTrie, Trie, TstTrie};
 
 // Helper function to generate test data
 fn generate_words(count: usize, prefix: &str) -> Vec<String> {
@@ -55,6 +55,35 @@ fn generate_english_like_words(count: usize) -> Vec<String> {
     words
 }
 
+// Distinct words of two to four common English syllables, picked with a fixed
+// linear congruential generator so every run sees the same words. Unlike
+// `generate_english_like_words`, the Trie branches the way a dictionary's does.
+fn generate_syllable_words(count: usize) -> Vec<String> {
+    let syllables = [
+        "a", "al", "an", "ar", "be", "ca", "con", "de", "di", "en", "er", "ex", "in", "ing", "is",
+        "la", "le", "ly", "ma", "ment", "mo", "ne", "ni", "o", "ous", "per", "pro", "ra", "re",
+        "ri", "ro", "sion", "ta", "te", "ter", "ti", "tion", "to", "tu", "un", "ver", "vi",
+    ];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |bound: usize| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize % bound
+    };
+
+    let mut seen = HashSet::new();
+    while seen.len() < count {
+        let word: String = (0..2 + next(3))
+            .map(|_| syllables[next(syllables.len())])
+            .collect();
+        seen.insert(word);
+    }
+    let mut words: Vec<String> = seen.into_iter().collect();
+    words.sort_unstable();
+    words
+}
+
 // Benchmark single insertions
 fn bench_insert_single(c: &mut Criterion) {
     let mut group = c.benchmark_group("insert_single");
@@ -66,7 +95,7 @@ fn bench_insert_single(c: &mut Criterion) {
             b.iter(|| {
                 let mut trie = Trie::new();
                 for word in &words {
-                    trie.insert(black_box(word), black_box(&1));
+                    trie.insert(black_box(word), black_box(1));
                 }
                 black_box(trie)
             })
@@ -106,7 +135,7 @@ fn bench_lookup(c: &mut Criterion) {
 
         // Pre-populate the trie
         for word in &words {
-            trie.insert(word, &1);
+            trie.insert(word, 1);
         }
 
         // Benchmark successful lookups
@@ -144,7 +173,7 @@ fn bench_prefix_search(c: &mut Criterion) {
 
         // Pre-populate the trie
         for word in &words {
-            trie.insert(word, &1);
+            trie.insert(word, 1);
         }
 
         let prefixes = ["app", "test", "user", "web", "nonexistent"];
@@ -171,7 +200,7 @@ fn bench_autocomplete(c: &mut Criterion) {
     let mut trie = Trie::new();
 
     for word in &words {
-        trie.insert(word, &1);
+        trie.insert(word, 1);
     }
 
     let prefixes = ["app", "test", "user", "data", "nonexistent"];
@@ -203,7 +232,7 @@ fn bench_autocomplete_scaling(c: &mut Criterion) {
         let mut trie = Trie::new();
 
         for word in &words {
-            trie.insert(word, &1);
+            trie.insert(word, 1);
         }
 
         group.bench_with_input(BenchmarkId::new("english_like", size), size, |b, _| {
@@ -234,7 +263,7 @@ fn bench_delete(c: &mut Criterion) {
                     // Setup: create a fresh trie for each iteration
                     let mut trie = Trie::new();
                     for word in &words {
-                        trie.insert(word, &1);
+                        trie.insert(word, 1);
                     }
                     trie
                 },
@@ -265,7 +294,7 @@ fn bench_memory_comparison(c: &mut Criterion) {
         b.iter(|| {
             let mut trie = Trie::new();
             for word in &words {
-                trie.insert(black_box(word), black_box(&1));
+                trie.insert(black_box(word), black_box(1));
             }
             black_box(trie)
         })
@@ -295,7 +324,7 @@ fn bench_worst_case(c: &mut Criterion) {
 
     let mut trie = Trie::new();
     for word in &words {
-        trie.insert(word, &1);
+        trie.insert(word, 1);
     }
 
     group.bench_function("long_common_prefix_autocomplete", |b| {
@@ -314,7 +343,7 @@ fn bench_worst_case(c: &mut Criterion) {
         b.iter(|| {
             let mut trie = Trie::new();
             for word in &very_long_words {
-                trie.insert(black_box(word), black_box(&1));
+                trie.insert(black_box(word), black_box(1));
             }
             black_box(trie)
         })
@@ -333,7 +362,7 @@ fn bench_real_world_simulation(c: &mut Criterion) {
 
     // Pre-populate with initial data
     for word in &words[..4000] {
-        trie.insert(word, &1);
+        trie.insert(word, 1);
     }
 
     group.bench_function("mixed_operations", |b| {
@@ -354,7 +383,7 @@ fn bench_real_world_simulation(c: &mut Criterion) {
             // Simulate adding a few new words
             for i in 0..5 {
                 let new_word = format!("dynamic_word_{}", i);
-                trie.insert(black_box(&new_word), black_box(&i));
+                trie.insert(black_box(&new_word), black_box(i));
             }
 
             // Simulate some lookups
@@ -396,7 +425,7 @@ fn bench_unicode(c: &mut Criterion) {
         b.iter(|| {
             let mut trie = Trie::new();
             for word in &extended_unicode {
-                trie.insert(black_box(word), black_box(&1));
+                trie.insert(black_box(word), black_box(1));
             }
             black_box(trie)
         })
@@ -404,7 +433,7 @@ fn bench_unicode(c: &mut Criterion) {
 
     let mut unicode_trie = Trie::new();
     for word in &extended_unicode {
-        unicode_trie.insert(word, &1);
+        unicode_trie.insert(word, 1);
     }
 
     group.bench_function("unicode_autocomplete", |b| {
@@ -419,6 +448,585 @@ fn bench_unicode(c: &mut Criterion) {
     group.finish();
 }
 
+// Compare the binary format against serde_json on the same entries
+fn bench_serialization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialization");
+
+    let words = generate_words(10000, "word");
+    let mut trie = Trie::new();
+    for (i, word) in words.iter().enumerate() {
+        trie.insert(word, i as u32);
+    }
+    let map = trie.clone().into_btreemap();
+
+    let bytes = trie.to_bytes();
+    let json = serde_json::to_vec(&map).unwrap();
+    println!(
+        "serialized size: to_bytes = {} bytes, serde_json = {} bytes",
+        bytes.len(),
+        json.len()
+    );
+
+    group.bench_function("to_bytes", |b| b.iter(|| black_box(trie.to_bytes())));
+    group.bench_function("serde_json_encode", |b| {
+        b.iter(|| black_box(serde_json::to_vec(&map).unwrap()))
+    });
+    group.bench_function("from_bytes", |b| {
+        b.iter(|| black_box(Trie::<u32>::from_bytes(black_box(&bytes)).unwrap()))
+    });
+    group.bench_function("serde_json_decode", |b| {
+        b.iter(|| {
+            let map: BTreeMap<String, u32> = serde_json::from_slice(black_box(&json)).unwrap();
+            black_box(Trie::from(map))
+        })
+    });
+
+    group.finish();
+}
+
+// Compare the fixed-array AsciiTrie against the HashMap-based Trie
+fn bench_ascii_trie(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ascii_trie");
+    let words = generate_words(10000, "word");
+
+    group.bench_function("trie_insert", |b| {
+        b.iter(|| {
+            let mut trie = Trie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+    group.bench_function("ascii_trie_insert", |b| {
+        b.iter(|| {
+            let mut trie = AsciiTrie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+
+    let mut trie = Trie::new();
+    let mut ascii_trie = AsciiTrie::new();
+    for word in &words {
+        trie.insert(word, 1);
+        ascii_trie.insert(word, 1);
+    }
+    group.bench_function("trie_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(trie.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("ascii_trie_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(ascii_trie.get(black_box(word)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+// Compare ByteTrie against Trie on ASCII keys, where Trie still decodes chars
+fn bench_byte_trie(c: &mut Criterion) {
+    let mut group = c.benchmark_group("byte_trie");
+    let words = generate_syllable_words(10000);
+
+    group.bench_function("trie_insert", |b| {
+        b.iter(|| {
+            let mut trie = Trie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+    group.bench_function("byte_trie_insert", |b| {
+        b.iter(|| {
+            let mut trie = ByteTrie::new();
+            for word in &words {
+                trie.insert(black_box(word.as_bytes()), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+
+    let mut trie = Trie::new();
+    let mut byte_trie = ByteTrie::new();
+    for word in &words {
+        trie.insert(word, 1);
+        byte_trie.insert(word.as_bytes(), 1);
+    }
+    group.bench_function("trie_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(trie.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("byte_trie_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(byte_trie.get(black_box(word.as_bytes())));
+            }
+        })
+    });
+    group.bench_function("trie_auto_complete", |b| {
+        b.iter(|| black_box(trie.auto_complete(black_box("con"), 100)))
+    });
+    group.bench_function("byte_trie_auto_complete", |b| {
+        b.iter(|| black_box(byte_trie.auto_complete(black_box(b"con"), 100)))
+    });
+
+    group.finish();
+}
+
+// Compare RadixTrie against Trie on keys with long unshared suffixes
+fn bench_radix_trie(c: &mut Criterion) {
+    let mut group = c.benchmark_group("radix_trie");
+    let words: Vec<String> = (0..10000)
+        .map(|i| format!("{:04}_{}", i, "unshared_suffix_".repeat(1 + i % 4)))
+        .collect();
+
+    // Trie needs one node per distinct prefix; RadixTrie one per branch or key
+    let trie_nodes = 1 + words
+        .iter()
+        .flat_map(|w| w.char_indices().map(move |(i, c)| &w[..i + c.len_utf8()]))
+        .collect::<HashSet<_>>()
+        .len();
+    let mut radix = RadixTrie::new();
+    for word in &words {
+        radix.insert(word, 1);
+    }
+    println!(
+        "node count: Trie = {}, RadixTrie = {}",
+        trie_nodes,
+        radix.node_count()
+    );
+
+    group.bench_function("trie_insert", |b| {
+        b.iter(|| {
+            let mut trie = Trie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+    group.bench_function("radix_trie_insert", |b| {
+        b.iter(|| {
+            let mut trie = RadixTrie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+    group.bench_function("radix_trie_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(radix.get(black_box(word)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+// Compare the index-linked ArenaTrie against the pointer-linked Trie
+fn bench_arena_trie(c: &mut Criterion) {
+    let mut group = c.benchmark_group("arena_trie");
+    let words = generate_english_like_words(10000);
+
+    group.bench_function("trie_build_and_drop", |b| {
+        b.iter(|| {
+            let mut trie = Trie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+    group.bench_function("arena_trie_build_and_drop", |b| {
+        b.iter(|| {
+            let mut trie = ArenaTrie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+
+    let mut trie = Trie::new();
+    let mut arena_trie = ArenaTrie::new();
+    for word in &words {
+        trie.insert(word, 1);
+        arena_trie.insert(word, 1);
+    }
+    group.bench_function("trie_traverse", |b| {
+        b.iter(|| black_box(trie.auto_complete(black_box(""), usize::MAX)))
+    });
+    group.bench_function("arena_trie_traverse", |b| {
+        b.iter(|| black_box(arena_trie.auto_complete(black_box(""), usize::MAX)))
+    });
+
+    group.finish();
+}
+
+// Compare building from a sorted dictionary with from_sorted_input and add_word_list
+fn bench_from_sorted_input(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_sorted_input");
+    group.sample_size(20);
+    let mut words = generate_words(100_000, "word");
+    words.sort_unstable();
+
+    group.bench_function("add_word_list", |b| {
+        b.iter(|| {
+            let mut trie = Trie::new();
+            trie.add_word_list(black_box(&words), |word| word.len());
+            black_box(trie)
+        })
+    });
+    group.bench_function("from_sorted_input", |b| {
+        b.iter(|| {
+            black_box(Trie::from_sorted_input(
+                black_box(&words).iter().map(|word| (word, word.len())),
+            ))
+        })
+    });
+
+    group.finish();
+}
+
+// Routing-table style lookups: longest stored prefix of each request path
+fn bench_longest_match(c: &mut Criterion) {
+    let mut group = c.benchmark_group("longest_match");
+    let mut trie = Trie::new();
+    for (i, route) in generate_realistic_words(10_000).iter().enumerate() {
+        trie.insert(&format!("/{}", route.replace('_', "/")), i);
+    }
+    let queries: Vec<String> = generate_realistic_words(1_000)
+        .iter()
+        .map(|route| format!("/{}/42/details", route.replace('_', "/")))
+        .collect();
+
+    group.bench_function("get_longest_match", |b| {
+        b.iter(|| {
+            for query in &queries {
+                black_box(trie.get_longest_match(black_box(query)));
+            }
+        })
+    });
+    group.bench_function("longest_prefix_match", |b| {
+        b.iter(|| {
+            for query in &queries {
+                black_box(trie.longest_prefix_match(black_box(query)));
+            }
+        })
+    });
+    group.bench_function("all_prefixes_of_last", |b| {
+        b.iter(|| {
+            for query in &queries {
+                black_box(trie.all_prefixes_of(black_box(query)).pop());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+// Compare the ternary search tree against the HashMap-per-node Trie and the
+// sorted-Vec-per-node ArenaTrie
+fn bench_tst_trie(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tst_trie");
+    let words = generate_realistic_words(10000);
+
+    group.bench_function("trie_insert", |b| {
+        b.iter(|| {
+            let mut trie = Trie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+    group.bench_function("arena_trie_insert", |b| {
+        b.iter(|| {
+            let mut trie = ArenaTrie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+    group.bench_function("tst_trie_insert", |b| {
+        b.iter(|| {
+            let mut trie = TstTrie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+
+    let mut trie = Trie::new();
+    let mut arena_trie = ArenaTrie::new();
+    let mut tst_trie = TstTrie::new();
+    for word in &words {
+        trie.insert(word, 1);
+        arena_trie.insert(word, 1);
+        tst_trie.insert(word, 1);
+    }
+    group.bench_function("trie_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(trie.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("arena_trie_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(arena_trie.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("tst_trie_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(tst_trie.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("trie_traverse", |b| {
+        b.iter(|| black_box(trie.auto_complete(black_box(""), usize::MAX)))
+    });
+    group.bench_function("tst_trie_traverse", |b| {
+        b.iter(|| black_box(tst_trie.auto_complete(black_box(""), usize::MAX)))
+    });
+
+    group.finish();
+}
+
+// Build, look up and walk a 10,000-word Trie. Run once with and once without
+// `--features smallvec-children` to compare the node children maps.
+fn bench_children_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("children_map");
+    let words = generate_syllable_words(10000);
+
+    group.bench_function("insert", |b| {
+        b.iter(|| {
+            let mut trie = Trie::new();
+            for word in &words {
+                trie.insert(black_box(word), black_box(1));
+            }
+            black_box(trie)
+        })
+    });
+
+    let mut trie = Trie::new();
+    for word in &words {
+        trie.insert(word, 1);
+    }
+    group.bench_function("get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(trie.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("traverse", |b| {
+        b.iter(|| black_box(trie.auto_complete(black_box(""), usize::MAX)))
+    });
+
+    group.finish();
+}
+
+// Lookups with the default SipHash children maps against AHash
+fn bench_hasher(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hasher");
+    let words = generate_syllable_words(10000);
+
+    let mut sip = Trie::new();
+    let mut ahash = Trie::with_hasher(ahash::RandomState::new());
+    for word in &words {
+        sip.insert(word, 1);
+        ahash.insert(word, 1);
+    }
+
+    group.bench_function("get_sip", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(sip.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("get_ahash", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(ahash.get(black_box(word)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+// Build an inverted index over generated documents, then query it
+fn bench_inverted_index(c: &mut Criterion) {
+    let mut group = c.benchmark_group("inverted_index");
+    let words = generate_syllable_words(5000);
+    let texts: Vec<String> = (0..1000)
+        .map(|doc| {
+            (0..50)
+                .map(|i| words[(doc * 31 + i * 97) % words.len()].as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    let ids: Vec<String> = (0..texts.len()).map(|doc| format!("doc{doc}")).collect();
+    let docs: Vec<(&str, &str)> = ids
+        .iter()
+        .zip(&texts)
+        .map(|(id, text)| (id.as_str(), text.as_str()))
+        .collect();
+
+    group.bench_function("build", |b| {
+        b.iter(|| black_box(Trie::build_inverted_index(black_box(&docs))))
+    });
+
+    let index = Trie::build_inverted_index(&docs);
+    group.bench_function("lookup", |b| {
+        b.iter(|| {
+            for word in &words[..1000] {
+                black_box(index.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("prefix_lookup", |b| {
+        b.iter(|| {
+            let documents: usize = index
+                .prefix_iter(black_box("con"))
+                .map(|(_, postings)| postings.len())
+                .sum();
+            black_box(documents)
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_batch_get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_get");
+    let urls: Vec<String> = (0..1000)
+        .map(|i| {
+            format!(
+                "https://api.example.com/v2/organizations/{}/projects/{}/issues",
+                i % 10,
+                i
+            )
+        })
+        .collect();
+    let trie: Trie<usize> = urls
+        .iter()
+        .enumerate()
+        .map(|(i, url)| (url.as_str(), i))
+        .collect();
+    let keys: Vec<&str> = urls.iter().rev().map(String::as_str).collect();
+
+    group.bench_function("get_loop", |b| {
+        b.iter(|| {
+            let values: Vec<Option<usize>> = keys
+                .iter()
+                .map(|key| trie.get(black_box(key)).copied())
+                .collect();
+            black_box(values)
+        })
+    });
+    group.bench_function("batch_get", |b| {
+        b.iter(|| black_box(trie.batch_get(black_box(&keys))))
+    });
+
+    group.finish();
+}
+
+fn bench_frozen_trie(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frozen_trie");
+    let words = generate_syllable_words(10000);
+    let trie: Trie<usize> = words
+        .iter()
+        .enumerate()
+        .map(|(i, w)| (w.as_str(), i))
+        .collect();
+    let frozen = trie.clone().freeze();
+
+    group.bench_function("trie_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(trie.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("frozen_get", |b| {
+        b.iter(|| {
+            for word in &words {
+                black_box(frozen.get(black_box(word)));
+            }
+        })
+    });
+    group.bench_function("trie_autocomplete", |b| {
+        b.iter(|| black_box(trie.auto_complete(black_box("con"), 100)))
+    });
+    group.bench_function("frozen_autocomplete", |b| {
+        b.iter(|| black_box(frozen.auto_complete(black_box("con"), 100)))
+    });
+    group.bench_function("freeze", |b| {
+        b.iter(|| black_box(black_box(trie.clone()).freeze()))
+    });
+
+    group.finish();
+}
+
+fn bench_aho_corasick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("aho_corasick");
+    let words = generate_syllable_words(5000);
+    let text: String = (0..20_000)
+        .map(|i| words[(i * 7919) % words.len()].as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let patterns: Vec<&String> = words.iter().step_by(50).collect();
+    let trie: Trie<usize> = patterns
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.as_str(), i))
+        .collect();
+
+    group.bench_function("build", |b| {
+        b.iter(|| black_box(black_box(&trie).compute_aho_corasick()))
+    });
+
+    let automaton = trie.compute_aho_corasick();
+    group.bench_function("search", |b| {
+        b.iter(|| black_box(automaton.search(black_box(&text)).len()))
+    });
+    group.bench_function("naive_find_per_pattern", |b| {
+        b.iter(|| {
+            let matches: usize = patterns
+                .iter()
+                .map(|pattern| black_box(&text).matches(pattern.as_str()).count())
+                .sum();
+            black_box(matches)
+        })
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_insert_single,
@@ -431,7 +1039,119 @@ criterion_group!(
     bench_memory_comparison,
     bench_worst_case,
     bench_real_world_simulation,
-    bench_unicode
+    bench_unicode,
+    bench_serialization,
+    bench_ascii_trie,
+    bench_byte_trie,
+    bench_radix_trie,
+    bench_arena_trie,
+    bench_from_sorted_input,
+    bench_longest_match,
+    bench_tst_trie,
+    bench_children_map,
+    bench_hasher,
+    bench_inverted_index,
+    bench_batch_get,
+    bench_frozen_trie,
+    bench_aho_corasick
 );
 
+// Parallel construction of a large Trie, against the sequential baseline
+#[cfg(feature = "rayon")]
+fn bench_par_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("par_build");
+    group.sample_size(10);
+
+    let items: Vec<(String, u32)> = generate_english_like_words(1_000_000)
+        .into_iter()
+        .enumerate()
+        .map(|(i, word)| (format!("{word}{i}"), i as u32))
+        .collect();
+
+    group.bench_function("sequential_1m", |b| {
+        b.iter(|| {
+            let mut trie = Trie::new();
+            for (word, value) in &items {
+                trie.insert(word, *value);
+            }
+            black_box(trie)
+        })
+    });
+    group.bench_function("par_build_1m", |b| {
+        b.iter(|| black_box(Trie::par_build(black_box(&items))))
+    });
+
+    group.finish();
+}
+
+// Concurrent autocomplete through SyncTrie's RwLock, against the same Trie
+// behind a Mutex, where readers have to take turns
+#[cfg(feature = "sync")]
+fn bench_sync_trie_reads(c: &mut Criterion) {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use trie_hard_rs::SyncTrie;
+
+    const THREADS: usize = 8;
+    const QUERIES_PER_THREAD: usize = 200;
+
+    let mut group = c.benchmark_group("concurrent_reads");
+    let words = generate_realistic_words(10000);
+    let trie = SyncTrie::new();
+    for word in &words {
+        trie.insert(word, 1);
+    }
+    let mutex_trie = Arc::new(Mutex::new(trie.snapshot()));
+
+    group.bench_function("sync_trie_rwlock", |b| {
+        b.iter(|| {
+            let readers: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    let trie = trie.clone();
+                    thread::spawn(move || {
+                        for _ in 0..QUERIES_PER_THREAD {
+                            black_box(trie.auto_complete(black_box("app"), 10));
+                        }
+                    })
+                })
+                .collect();
+            for reader in readers {
+                reader.join().unwrap();
+            }
+        })
+    });
+    group.bench_function("mutex", |b| {
+        b.iter(|| {
+            let readers: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    let trie = Arc::clone(&mutex_trie);
+                    thread::spawn(move || {
+                        for _ in 0..QUERIES_PER_THREAD {
+                            let trie = trie.lock().unwrap();
+                            black_box(trie.auto_complete(black_box("app"), 10));
+                        }
+                    })
+                })
+                .collect();
+            for reader in readers {
+                reader.join().unwrap();
+            }
+        })
+    });
+
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+criterion_group!(parallel_benches, bench_par_build);
+#[cfg(feature = "sync")]
+criterion_group!(sync_benches, bench_sync_trie_reads);
+
+#[cfg(all(feature = "rayon", feature = "sync"))]
+criterion_main!(benches, parallel_benches, sync_benches);
+#[cfg(all(feature = "rayon", not(feature = "sync")))]
+criterion_main!(benches, parallel_benches);
+#[cfg(all(feature = "sync", not(feature = "rayon")))]
+criterion_main!(benches, sync_benches);
+#[cfg(not(any(feature = "rayon", feature = "sync")))]
 criterion_main!(benches);
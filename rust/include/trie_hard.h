@@ -0,0 +1,103 @@
+#ifndef TRIE_HARD_H
+#define TRIE_HARD_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#if defined(TRIE_HARD_FFI)
+// An opaque handle to a `Trie<i64>`. Create one with `trie_new` and release
+// it with `trie_free`.
+typedef struct TrieHandle TrieHandle;
+#endif
+
+#if defined(TRIE_HARD_FFI)
+// Creates a new, empty trie. The caller owns the handle and must release it
+// with `trie_free`.
+TrieHandle *trie_new(void);
+#endif
+
+#if defined(TRIE_HARD_FFI)
+// Releases a trie created by `trie_new`. Passing null does nothing.
+//
+// # Safety
+//
+// `trie` must be null or a handle from `trie_new` that has not been freed.
+void trie_free(TrieHandle *trie);
+#endif
+
+#if defined(TRIE_HARD_FFI)
+// Stores `value` under `key`, replacing any previous value.
+//
+// # Safety
+//
+// `trie` must be null or a live handle from `trie_new`, and `key` must be
+// null or a NUL-terminated string.
+void trie_insert(TrieHandle *trie, const char *key, int64_t value);
+#endif
+
+#if defined(TRIE_HARD_FFI)
+// Looks up `key`. If it is stored, writes its value to `out` (when `out` is
+// not null) and returns true; otherwise leaves `out` alone and returns false.
+//
+// # Safety
+//
+// `trie` must be null or a live handle from `trie_new`, `key` must be null
+// or a NUL-terminated string, and `out` must be null or valid for writes.
+bool trie_get(const TrieHandle *trie, const char *key, int64_t *out);
+#endif
+
+#if defined(TRIE_HARD_FFI)
+// Removes `key`. Returns true if it was stored.
+//
+// # Safety
+//
+// `trie` must be null or a live handle from `trie_new`, and `key` must be
+// null or a NUL-terminated string.
+bool trie_delete(TrieHandle *trie, const char *key);
+#endif
+
+#if defined(TRIE_HARD_FFI)
+// Returns true if any stored key starts with `prefix`.
+//
+// # Safety
+//
+// `trie` must be null or a live handle from `trie_new`, and `prefix` must be
+// null or a NUL-terminated string.
+bool trie_prefix_search(const TrieHandle *trie, const char *prefix);
+#endif
+
+#if defined(TRIE_HARD_FFI)
+// Writes up to `max` keys starting with `prefix` to `out`, in lexicographic
+// order, and their count to `out_len`.
+//
+// Each key is a new NUL-terminated string owned by the caller, to be released
+// with `trie_string_free`. Keys containing a NUL character cannot be passed to
+// C and are skipped.
+//
+// # Safety
+//
+// `trie` must be null or a live handle from `trie_new`, `prefix` must be null
+// or a NUL-terminated string, `out` must be null or valid for writing `max`
+// pointers, and `out_len` must be null or valid for writes.
+void trie_auto_complete(const TrieHandle *trie,
+                        const char *prefix,
+                        size_t max,
+                        char **out,
+                        size_t *out_len);
+#endif
+
+#if defined(TRIE_HARD_FFI)
+// Releases a string returned by `trie_auto_complete`. Passing null does
+// nothing.
+//
+// # Safety
+//
+// `s` must be null or a string from `trie_auto_complete` that has not been
+// freed.
+void trie_string_free(char *s);
+#endif
+
+#endif  /* TRIE_HARD_H */
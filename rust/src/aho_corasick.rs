@@ -0,0 +1,212 @@
+use crate::trie::Trie;
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// Index of a state in the automaton.
+type StateId = u32;
+
+const ROOT: StateId = 0;
+
+/// An Aho-Corasick automaton over the keys of a `Trie`, built by
+/// `Trie::compute_aho_corasick`, that finds every occurrence of every key in a
+/// text in a single pass.
+///
+/// Each state is a node of the Trie, with two extra links: its failure link
+/// points at the state for the longest proper suffix of its key that is also a
+/// prefix of some key, and its output link at the state for the longest such
+/// suffix that is a whole key. On a mismatch the search falls back along
+/// failure links instead of restarting, and on reaching a state it reports
+/// every key ending there by following output links, so a search takes
+/// O(text length + number of matches).
+#[derive(Clone, Debug)]
+pub struct AhoCorasickAutomaton<TValue> {
+    states: Vec<AcState<TValue>>,
+    pattern_count: usize,
+}
+
+#[derive(Clone, Debug)]
+struct AcState<TValue> {
+    // Sorted by character, like `ArenaTrie`'s children.
+    children: Vec<(char, StateId)>,
+    fail: StateId,
+    output: Option<StateId>,
+    // Length in bytes of the key leading to this state.
+    depth: usize,
+    value: Option<TValue>,
+}
+
+impl<TValue> AcState<TValue> {
+    fn new(depth: usize, value: Option<TValue>) -> Self {
+        AcState {
+            children: Vec::new(),
+            fail: ROOT,
+            output: None,
+            depth,
+            value,
+        }
+    }
+
+    fn get_child(&self, c: char) -> Option<StateId> {
+        self.children
+            .binary_search_by_key(&c, |&(child_c, _)| child_c)
+            .ok()
+            .map(|i| self.children[i].1)
+    }
+}
+
+/// One occurrence of a key in the text passed to `AhoCorasickAutomaton::search`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AcMatch<'t, TValue> {
+    /// The matched part of the text, which equals the key.
+    pub matched: &'t str,
+    /// Byte offset of the match in the text.
+    pub start: usize,
+    /// The value stored under the key.
+    pub value: &'t TValue,
+}
+
+impl<TValue> AcMatch<'_, TValue> {
+    /// Byte offset in the text just past the match.
+    pub fn end(&self) -> usize {
+        self.start + self.matched.len()
+    }
+}
+
+// Derived impls would needlessly require `TValue: Clone` and `TValue: Copy`.
+impl<TValue> Clone for AcMatch<'_, TValue> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<TValue> Copy for AcMatch<'_, TValue> {}
+
+impl<TValue: Clone, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Builds an Aho-Corasick automaton that searches texts for all the keys of
+    /// the Trie at once, with each key's value cloned into it. The empty key,
+    /// if stored, is not searched for.
+    ///
+    /// The Trie's nodes are copied into the automaton in breadth-first order,
+    /// which is also the order the links are computed in: a state's failure
+    /// link is always shallower than the state, so it is complete by the time
+    /// it is followed.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "he" => 1, "she" => 2, "hers" => 3 };
+    /// let automaton = trie.compute_aho_corasick();
+    /// let found: Vec<_> = automaton
+    ///     .search("ushers")
+    ///     .iter()
+    ///     .map(|m| (m.matched, m.start))
+    ///     .collect();
+    /// assert_eq!(found, vec![("she", 1), ("he", 2), ("hers", 2)]);
+    /// ```
+    pub fn compute_aho_corasick(&self) -> AhoCorasickAutomaton<TValue> {
+        let mut states = vec![AcState::new(0, None)];
+        let mut queue = VecDeque::from([(self.root(), ROOT)]);
+        while let Some((node, id)) = queue.pop_front() {
+            for (&c, child) in node.children_iter_sorted() {
+                let child_id = states.len() as StateId;
+                let depth = states[id as usize].depth + c.len_utf8();
+                states.push(AcState::new(depth, child.get_value().cloned()));
+                states[id as usize].children.push((c, child_id));
+
+                if id != ROOT {
+                    let mut fallback = states[id as usize].fail;
+                    let fail = loop {
+                        if let Some(next) = states[fallback as usize].get_child(c) {
+                            break next;
+                        }
+                        if fallback == ROOT {
+                            break ROOT;
+                        }
+                        fallback = states[fallback as usize].fail;
+                    };
+                    let fail_state = &states[fail as usize];
+                    let output = if fail_state.value.is_some() {
+                        Some(fail)
+                    } else {
+                        fail_state.output
+                    };
+                    let state = &mut states[child_id as usize];
+                    state.fail = fail;
+                    state.output = output;
+                }
+                queue.push_back((child, child_id));
+            }
+        }
+
+        AhoCorasickAutomaton {
+            states,
+            pattern_count: self.len() - usize::from(self.root().get_value().is_some()),
+        }
+    }
+}
+
+impl<TValue> AhoCorasickAutomaton<TValue> {
+    /// Returns the number of keys the automaton searches for.
+    pub fn len(&self) -> usize {
+        self.pattern_count
+    }
+
+    /// Returns true if the automaton has no keys to search for.
+    pub fn is_empty(&self) -> bool {
+        self.pattern_count == 0
+    }
+
+    /// Finds every occurrence of every key in `text`, overlapping ones
+    /// included. Matches are ordered by where they end, and matches ending at
+    /// the same place from longest to shortest.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "error" => "E", "warn" => "W" };
+    /// let automaton = trie.compute_aho_corasick();
+    /// let levels: Vec<&str> = automaton
+    ///     .search("warn: disk full; error: write failed")
+    ///     .iter()
+    ///     .map(|m| *m.value)
+    ///     .collect();
+    /// assert_eq!(levels, vec!["W", "E"]);
+    /// ```
+    pub fn search<'t>(&'t self, text: &'t str) -> Vec<AcMatch<'t, TValue>> {
+        let mut matches = Vec::new();
+        let mut current = ROOT;
+        for (offset, c) in text.char_indices() {
+            current = loop {
+                if let Some(next) = self.state(current).get_child(c) {
+                    break next;
+                }
+                if current == ROOT {
+                    break ROOT;
+                }
+                current = self.state(current).fail;
+            };
+
+            // The current state's key may not be a whole key itself, but every
+            // state along its output links is.
+            let end = offset + c.len_utf8();
+            let mut found = Some(current);
+            while let Some(id) = found {
+                let state = self.state(id);
+                if let Some(value) = &state.value {
+                    let start = end - state.depth;
+                    matches.push(AcMatch {
+                        matched: &text[start..end],
+                        start,
+                        value,
+                    });
+                }
+                found = state.output;
+            }
+        }
+        matches
+    }
+
+    fn state(&self, id: StateId) -> &AcState<TValue> {
+        &self.states[id as usize]
+    }
+}
@@ -0,0 +1,260 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Index of a node in the arena. `u32` keeps child links half the size of a
+/// pointer on 64-bit targets.
+type NodeId = u32;
+
+const ROOT: NodeId = 0;
+
+/// A Trie whose nodes all live in one contiguous arena (a `Vec`) and refer to
+/// their children by index rather than by pointer.
+///
+/// Compared to `Trie`, the nodes themselves share one growing allocation
+/// instead of each owning a children map, so building one makes a single
+/// small allocation per node with children (its list of child links) plus the
+/// arena's occasional regrowth. Traversals walk memory that is packed
+/// together, and leaf nodes allocate nothing. Slots freed by `delete` are
+/// reused by later inserts.
+///
+/// The API mirrors `Trie`.
+#[derive(Clone)]
+pub struct ArenaTrie<TValue> {
+    nodes: Vec<ArenaNode<TValue>>,
+    // Slots of deleted nodes, handed out again before the arena grows.
+    free: Vec<NodeId>,
+    word_count: usize,
+}
+
+#[derive(Clone)]
+struct ArenaNode<TValue> {
+    // Sorted by character, so lookups can binary search and walks come out in
+    // lexicographic order. Most nodes have very few children, which makes a
+    // small sorted list both smaller and faster than a HashMap.
+    children: Vec<(char, NodeId)>,
+    value: Option<TValue>,
+}
+
+impl<TValue> ArenaNode<TValue> {
+    const fn new() -> Self {
+        ArenaNode {
+            children: Vec::new(),
+            value: None,
+        }
+    }
+
+    fn get_child(&self, c: char) -> Option<NodeId> {
+        self.children
+            .binary_search_by_key(&c, |&(child_c, _)| child_c)
+            .ok()
+            .map(|i| self.children[i].1)
+    }
+}
+
+impl<TValue> ArenaTrie<TValue> {
+    /// Initializes a new, empty ArenaTrie.
+    pub fn new() -> Self {
+        Self::with_capacity(1)
+    }
+
+    /// Initializes a new, empty ArenaTrie with room for `nodes` nodes (including
+    /// the root) before the arena has to grow.
+    pub fn with_capacity(nodes: usize) -> Self {
+        let mut arena = Vec::with_capacity(nodes.max(1));
+        arena.push(ArenaNode::new());
+        ArenaTrie {
+            nodes: arena,
+            free: Vec::new(),
+            word_count: 0,
+        }
+    }
+
+    /// Returns the number of words stored in the ArenaTrie. This is O(1).
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns true if the ArenaTrie contains no words.
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
+    /// Inserts a key-value pair, returning the previous value if the key was
+    /// already stored.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = ArenaTrie::new();
+    /// assert_eq!(trie.insert("apple", 1), None);
+    /// assert_eq!(trie.insert("apple", 2), Some(1));
+    /// ```
+    pub fn insert(&mut self, key: &str, value: TValue) -> Option<TValue> {
+        let mut current = ROOT;
+        for c in key.chars() {
+            current = self.add_child(current, c);
+        }
+        let previous = self.node_mut(current).value.replace(value);
+        if previous.is_none() {
+            self.word_count += 1;
+        }
+        previous
+    }
+
+    /// Gets the value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&TValue> {
+        let id = self.find_node(key)?;
+        self.node(id).value.as_ref()
+    }
+
+    /// Gets a mutable reference to the value stored under `key`.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut TValue> {
+        let id = self.find_node(key)?;
+        self.node_mut(id).value.as_mut()
+    }
+
+    /// Deletes a key and its value, freeing any nodes that only existed for it.
+    /// Returns true if the key was found. As with `Trie`, the empty key cannot
+    /// be removed this way.
+    pub fn delete(&mut self, key: &str) -> bool {
+        if key.is_empty() {
+            return false;
+        }
+
+        let mut path = Vec::with_capacity(key.len());
+        let mut current = ROOT;
+        for c in key.chars() {
+            let Some(child) = self.node(current).get_child(c) else {
+                return false;
+            };
+            path.push((current, c));
+            current = child;
+        }
+        if self.node_mut(current).value.take().is_none() {
+            return false;
+        }
+        self.word_count -= 1;
+
+        // Free nodes from the bottom up while they are left empty.
+        while let Some((parent, c)) = path.pop() {
+            let node = self.node(current);
+            if node.value.is_some() || !node.children.is_empty() {
+                break;
+            }
+            let siblings = &mut self.node_mut(parent).children;
+            let index = siblings
+                .binary_search_by_key(&c, |&(child_c, _)| child_c)
+                .expect("path child exists");
+            siblings.remove(index);
+            self.free.push(current);
+            current = parent;
+        }
+        true
+    }
+
+    /// Returns true if any stored word starts with `prefix`.
+    pub fn prefix_search(&self, prefix: &str) -> bool {
+        self.find_node(prefix).is_some()
+    }
+
+    /// Returns up to `max_results` words that start with `prefix`, in
+    /// lexicographic order.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = ArenaTrie::new();
+    /// trie.insert("apricot", 1);
+    /// trie.insert("apple", 2);
+    /// trie.insert("app", 3);
+    /// assert_eq!(trie.auto_complete("ap", 2), vec!["app", "apple"]);
+    /// ```
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        let mut results = Vec::new();
+        let Some(start) = self.find_node(prefix) else {
+            return results;
+        };
+
+        // Explicit-stack pre-order walk. Children are pushed in reverse so the
+        // smallest character is popped first.
+        let mut stack = vec![(start, prefix.to_string())];
+        while let Some((id, key)) = stack.pop() {
+            if results.len() >= max_results {
+                break;
+            }
+            let node = self.node(id);
+            for &(c, child) in node.children.iter().rev() {
+                let mut child_key = key.clone();
+                child_key.push(c);
+                stack.push((child, child_key));
+            }
+            if node.value.is_some() {
+                results.push(key);
+            }
+        }
+        results
+    }
+
+    /// Adds multiple words, using `value_generator` to compute each word's value.
+    pub fn add_word_list<T, F>(&mut self, items: &[T], value_generator: F)
+    where
+        T: AsRef<str>,
+        F: Fn(&T) -> TValue,
+    {
+        for item in items {
+            self.insert(item.as_ref(), value_generator(item));
+        }
+    }
+
+    /// Returns the number of nodes in use, including the root. Freed slots that
+    /// are waiting to be reused are not counted.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    fn node(&self, id: NodeId) -> &ArenaNode<TValue> {
+        &self.nodes[id as usize]
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> &mut ArenaNode<TValue> {
+        &mut self.nodes[id as usize]
+    }
+
+    fn find_node(&self, prefix: &str) -> Option<NodeId> {
+        let mut current = ROOT;
+        for c in prefix.chars() {
+            current = self.node(current).get_child(c)?;
+        }
+        Some(current)
+    }
+
+    /// Returns the child of `parent` along `c`, allocating it if needed.
+    fn add_child(&mut self, parent: NodeId, c: char) -> NodeId {
+        let index = match self
+            .node(parent)
+            .children
+            .binary_search_by_key(&c, |&(child_c, _)| child_c)
+        {
+            Ok(index) => return self.node(parent).children[index].1,
+            Err(index) => index,
+        };
+
+        let id = match self.free.pop() {
+            Some(id) => id,
+            None => {
+                let id = NodeId::try_from(self.nodes.len())
+                    .expect("ArenaTrie cannot hold more than u32::MAX nodes");
+                self.nodes.push(ArenaNode::new());
+                id
+            }
+        };
+        self.node_mut(parent).children.insert(index, (c, id));
+        id
+    }
+}
+
+/// Allows creating a new ArenaTrie with `ArenaTrie::default()`.
+impl<TValue> Default for ArenaTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,237 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Number of ASCII code points, and so the number of child slots per node.
+const ASCII_SIZE: usize = 128;
+
+/// A Trie specialized for ASCII keys. Each node holds its children in a fixed
+/// 128-slot array indexed by byte, so following an edge is a single array index
+/// with no hashing, which makes `get` several times faster than on `Trie`. The
+/// trade-off is memory: every node reserves a slot for every ASCII character
+/// whether it is used or not (about 1 KiB per node on 64-bit targets), so
+/// building a large AsciiTrie is slower than building the equivalent `Trie`.
+///
+/// The API mirrors `Trie`. Every method that takes a key panics if the key
+/// contains a non-ASCII character.
+#[derive(Clone)]
+pub struct AsciiTrie<TValue> {
+    root: AsciiNode<TValue>,
+    word_count: usize,
+}
+
+#[derive(Clone)]
+struct AsciiNode<TValue> {
+    children: [Option<Box<AsciiNode<TValue>>>; ASCII_SIZE],
+    // Number of occupied slots in `children`, so emptiness checks don't scan.
+    child_count: u8,
+    value: Option<TValue>,
+}
+
+impl<TValue> AsciiNode<TValue> {
+    const fn new() -> Self {
+        AsciiNode {
+            children: [const { None }; ASCII_SIZE],
+            child_count: 0,
+            value: None,
+        }
+    }
+
+    fn get_child(&self, byte: u8) -> Option<&AsciiNode<TValue>> {
+        self.children[byte as usize].as_deref()
+    }
+
+    fn add_child(&mut self, byte: u8) -> &mut AsciiNode<TValue> {
+        let slot = &mut self.children[byte as usize];
+        if slot.is_none() {
+            self.child_count += 1;
+        }
+        slot.get_or_insert_with(|| Box::new(AsciiNode::new()))
+    }
+}
+
+impl<TValue> AsciiTrie<TValue> {
+    /// Initializes a new, empty AsciiTrie. Usable in `const` and `static` items.
+    pub const fn new() -> Self {
+        AsciiTrie {
+            root: AsciiNode::new(),
+            word_count: 0,
+        }
+    }
+
+    /// Returns the number of words stored in the AsciiTrie. This is O(1).
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns true if the AsciiTrie contains no words.
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
+    /// Inserts a key-value pair, returning the previous value if the key was
+    /// already stored.
+    ///
+    /// Panics if `key` is not ASCII.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = AsciiTrie::new();
+    /// assert_eq!(trie.insert("apple", 1), None);
+    /// assert_eq!(trie.insert("apple", 2), Some(1));
+    /// ```
+    pub fn insert(&mut self, key: &str, value: TValue) -> Option<TValue> {
+        assert_ascii(key);
+        let mut current_node = &mut self.root;
+        for byte in key.bytes() {
+            current_node = current_node.add_child(byte);
+        }
+        let previous = current_node.value.replace(value);
+        if previous.is_none() {
+            self.word_count += 1;
+        }
+        previous
+    }
+
+    /// Gets the value stored under `key`.
+    ///
+    /// Panics if `key` is not ASCII.
+    pub fn get(&self, key: &str) -> Option<&TValue> {
+        assert_ascii(key);
+        self.find_node(key)?.value.as_ref()
+    }
+
+    /// Deletes a key and its value, pruning any nodes that only existed for it.
+    /// Returns true if the key was found. As with `Trie`, the empty key cannot be
+    /// removed this way.
+    ///
+    /// Panics if `key` is not ASCII.
+    pub fn delete(&mut self, key: &str) -> bool {
+        assert_ascii(key);
+        if key.is_empty() {
+            return false;
+        }
+        let mut removed = false;
+        Self::delete_recursively(&mut self.root, key.as_bytes(), &mut removed);
+        if removed {
+            self.word_count -= 1;
+        }
+        removed
+    }
+
+    /// Recursive helper for `delete`. Returns true if the caller should drop
+    /// `current_node` because it no longer holds a value or any children.
+    fn delete_recursively(
+        current_node: &mut AsciiNode<TValue>,
+        key: &[u8],
+        removed: &mut bool,
+    ) -> bool {
+        let Some((&byte, rest)) = key.split_first() else {
+            *removed = current_node.value.take().is_some();
+            return *removed && current_node.child_count == 0;
+        };
+
+        let slot = &mut current_node.children[byte as usize];
+        let Some(child) = slot.as_deref_mut() else {
+            return false;
+        };
+        if Self::delete_recursively(child, rest, removed) {
+            *slot = None;
+            current_node.child_count -= 1;
+            return current_node.value.is_none() && current_node.child_count == 0;
+        }
+        false
+    }
+
+    /// Returns true if any stored word starts with `prefix`.
+    ///
+    /// Panics if `prefix` is not ASCII.
+    pub fn prefix_search(&self, prefix: &str) -> bool {
+        assert_ascii(prefix);
+        self.find_node(prefix).is_some()
+    }
+
+    /// Returns up to `max_results` words that start with `prefix`, in
+    /// lexicographic order.
+    ///
+    /// Panics if `prefix` is not ASCII.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = AsciiTrie::new();
+    /// trie.insert("apricot", 1);
+    /// trie.insert("apple", 2);
+    /// trie.insert("app", 3);
+    /// assert_eq!(trie.auto_complete("ap", 2), vec!["app", "apple"]);
+    /// ```
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        assert_ascii(prefix);
+        let mut results = Vec::new();
+        if max_results == 0 {
+            return results;
+        }
+        if let Some(node) = self.find_node(prefix) {
+            let mut key = prefix.to_string();
+            Self::collect_words_recursive(node, &mut key, &mut results, max_results);
+        }
+        results
+    }
+
+    fn collect_words_recursive(
+        node: &AsciiNode<TValue>,
+        key: &mut String,
+        results: &mut Vec<String>,
+        max_results: usize,
+    ) {
+        if node.value.is_some() {
+            results.push(key.clone());
+        }
+        // Slots are ordered by byte value, so walking them in order already
+        // yields keys lexicographically.
+        for (byte, child) in node.children.iter().enumerate() {
+            if results.len() >= max_results {
+                return;
+            }
+            if let Some(child) = child {
+                key.push(byte as u8 as char);
+                Self::collect_words_recursive(child, key, results, max_results);
+                key.pop();
+            }
+        }
+    }
+
+    /// Adds multiple words, using `value_generator` to compute each word's value.
+    ///
+    /// Panics if any word is not ASCII.
+    pub fn add_word_list<T, F>(&mut self, items: &[T], value_generator: F)
+    where
+        T: AsRef<str>,
+        F: Fn(&T) -> TValue,
+    {
+        for item in items {
+            self.insert(item.as_ref(), value_generator(item));
+        }
+    }
+
+    fn find_node(&self, prefix: &str) -> Option<&AsciiNode<TValue>> {
+        let mut current_node = &self.root;
+        for byte in prefix.bytes() {
+            current_node = current_node.get_child(byte)?;
+        }
+        Some(current_node)
+    }
+}
+
+/// Allows creating a new AsciiTrie with `AsciiTrie::default()`.
+impl<TValue> Default for AsciiTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn assert_ascii(key: &str) {
+    assert!(
+        key.is_ascii(),
+        "AsciiTrie only supports ASCII keys, got {key:?}"
+    );
+}
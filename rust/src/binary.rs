@@ -0,0 +1,503 @@
+//! A compact binary encoding for Tries, produced by `Trie::to_bytes` and read
+//! back by `Trie::from_bytes`.
+//!
+//! The layout is a 4-byte header (`b"TRH"` plus a version byte) followed by the
+//! nodes in depth-first pre-order, children sorted by character:
+//!
+//! - the root: a flags byte, then its value if `IS_TERMINAL` is set;
+//! - every other node: its edge character as a varint code point, a flags byte,
+//!   then its value if `IS_TERMINAL` is set.
+//!
+//! `HAS_CHILDREN` means the node's first child follows immediately, and
+//! `HAS_SIBLINGS` means another child of the same parent follows once this
+//! node's subtree is done. Shared prefixes are written once, and no key strings
+//! or lengths appear anywhere, which is what keeps the output small.
+
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::error::Error;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+const MAGIC: &[u8; 3] = b"TRH";
+const VERSION: u8 = 1;
+
+const IS_TERMINAL: u8 = 0b001;
+const HAS_CHILDREN: u8 = 0b010;
+const HAS_SIBLINGS: u8 = 0b100;
+
+/// Why a byte slice could not be decoded by `Trie::from_bytes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeserializeError {
+    /// The data does not start with the `TRH` header.
+    BadMagic,
+    /// The data was written by an unknown version of the format.
+    UnsupportedVersion(u8),
+    /// The data ended in the middle of a record or value.
+    UnexpectedEof,
+    /// A varint ran past the width of the integer it encodes.
+    VarintOverflow,
+    /// A flags byte had bits set that the format does not define, or marked a
+    /// node other than the root as having neither a value nor children.
+    InvalidFlags(u8),
+    /// An edge or value held a number that is not a Unicode scalar value.
+    InvalidChar(u32),
+    /// A string value was not valid UTF-8.
+    InvalidUtf8,
+    /// A node listed the same child character twice.
+    DuplicateChild(char),
+    /// Bytes were left over after the last node.
+    TrailingBytes(usize),
+    /// A value's own encoding was malformed.
+    InvalidValue(String),
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeserializeError::BadMagic => write!(f, "missing trie header"),
+            DeserializeError::UnsupportedVersion(v) => write!(f, "unsupported format version {v}"),
+            DeserializeError::UnexpectedEof => write!(f, "unexpected end of data"),
+            DeserializeError::VarintOverflow => write!(f, "varint is too long"),
+            DeserializeError::InvalidFlags(flags) => write!(f, "invalid node flags {flags:#04x}"),
+            DeserializeError::InvalidChar(c) => write!(f, "invalid character code point {c:#x}"),
+            DeserializeError::InvalidUtf8 => write!(f, "string value is not valid UTF-8"),
+            DeserializeError::DuplicateChild(c) => write!(f, "duplicate child '{c}'"),
+            DeserializeError::TrailingBytes(n) => write!(f, "{n} trailing bytes after trie data"),
+            DeserializeError::InvalidValue(msg) => write!(f, "invalid value: {msg}"),
+        }
+    }
+}
+
+impl Error for DeserializeError {}
+
+/// A value type that can be written by `Trie::to_bytes` and read back by
+/// `Trie::from_bytes`.
+///
+/// `read_bytes` consumes exactly the bytes `write_bytes` produced from the
+/// front of `input`, advancing the slice past them.
+pub trait ByteSerialize: Sized {
+    /// Appends the encoding of `self` to `out`.
+    fn write_bytes(&self, out: &mut Vec<u8>);
+
+    /// Decodes a value from the front of `input`.
+    fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError>;
+
+    /// The fewest bytes any encoded value takes. Decoding a `Vec` uses it to
+    /// reject lengths the remaining input cannot hold, so it must never be
+    /// more than the real minimum. The default of 0 is always safe, but caps
+    /// a decoded `Vec` at 2^20 items, since the input length cannot bound it.
+    const MIN_ENCODED_LEN: usize = 0;
+}
+
+/// The most items `Vec::read_bytes` accepts for a type whose values may encode
+/// to no bytes at all, where the input length puts no bound on the count.
+const MAX_ZERO_SIZED_ITEMS: usize = 1 << 20;
+
+impl ByteSerialize for () {
+    fn write_bytes(&self, _out: &mut Vec<u8>) {}
+
+    fn read_bytes(_input: &mut &[u8]) -> Result<Self, DeserializeError> {
+        Ok(())
+    }
+}
+
+impl ByteSerialize for bool {
+    const MIN_ENCODED_LEN: usize = 1;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self));
+    }
+
+    fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError> {
+        match read_byte(input)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(DeserializeError::InvalidValue(format!("{b} is not a bool"))),
+        }
+    }
+}
+
+// Unsigned integers are LEB128 varints, so small counts stay one byte.
+macro_rules! impl_byte_serialize_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl ByteSerialize for $t {
+                const MIN_ENCODED_LEN: usize = 1;
+
+                fn write_bytes(&self, out: &mut Vec<u8>) {
+                    write_varint(out, *self as u64);
+                }
+
+                fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError> {
+                    let value = read_varint(input)?;
+                    <$t>::try_from(value).map_err(|_| DeserializeError::VarintOverflow)
+                }
+            }
+        )*
+    };
+}
+
+// Signed integers are zigzag-encoded first so small negative numbers stay short.
+macro_rules! impl_byte_serialize_signed {
+    ($($t:ty),*) => {
+        $(
+            impl ByteSerialize for $t {
+                const MIN_ENCODED_LEN: usize = 1;
+
+                fn write_bytes(&self, out: &mut Vec<u8>) {
+                    let value = *self as i64;
+                    write_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+                }
+
+                fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError> {
+                    let zigzag = read_varint(input)?;
+                    let value = ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64);
+                    <$t>::try_from(value).map_err(|_| DeserializeError::VarintOverflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_serialize_unsigned!(u8, u16, u32, u64, usize);
+impl_byte_serialize_signed!(i8, i16, i32, i64, isize);
+
+impl ByteSerialize for f32 {
+    const MIN_ENCODED_LEN: usize = 4;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError> {
+        Ok(f32::from_le_bytes(read_array(input)?))
+    }
+}
+
+impl ByteSerialize for f64 {
+    const MIN_ENCODED_LEN: usize = 8;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError> {
+        Ok(f64::from_le_bytes(read_array(input)?))
+    }
+}
+
+impl ByteSerialize for char {
+    const MIN_ENCODED_LEN: usize = 1;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        write_varint(out, u64::from(u32::from(*self)));
+    }
+
+    fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError> {
+        read_char(input)
+    }
+}
+
+impl ByteSerialize for String {
+    const MIN_ENCODED_LEN: usize = 1;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len() as u64);
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError> {
+        let bytes = read_slice(input)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DeserializeError::InvalidUtf8)
+    }
+}
+
+impl<T: ByteSerialize> ByteSerialize for Option<T> {
+    const MIN_ENCODED_LEN: usize = 1;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        self.is_some().write_bytes(out);
+        if let Some(value) = self {
+            value.write_bytes(out);
+        }
+    }
+
+    fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError> {
+        match bool::read_bytes(input)? {
+            true => T::read_bytes(input).map(Some),
+            false => Ok(None),
+        }
+    }
+}
+
+impl<T: ByteSerialize> ByteSerialize for Vec<T> {
+    const MIN_ENCODED_LEN: usize = 1;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len() as u64);
+        for item in self {
+            item.write_bytes(out);
+        }
+    }
+
+    fn read_bytes(input: &mut &[u8]) -> Result<Self, DeserializeError> {
+        let len = usize::read_bytes(input)?;
+        // The length comes from the input, so check it against what is left
+        // before allocating or looping. Items that may take no bytes cannot be
+        // bounded that way and get a fixed cap instead.
+        if T::MIN_ENCODED_LEN == 0 {
+            if len > MAX_ZERO_SIZED_ITEMS {
+                return Err(DeserializeError::InvalidValue(format!(
+                    "{len} items is more than the limit of {MAX_ZERO_SIZED_ITEMS}"
+                )));
+            }
+        } else if len > input.len() / T::MIN_ENCODED_LEN {
+            return Err(DeserializeError::UnexpectedEof);
+        }
+        let mut items = Vec::with_capacity(len.min(input.len()));
+        for _ in 0..len {
+            items.push(T::read_bytes(input)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<TValue: ByteSerialize> Trie<TValue> {
+    /// Encodes the Trie in a compact binary format that `from_bytes` reads back.
+    /// Shared prefixes are stored once, so the output is typically much smaller
+    /// than a JSON map of the same entries. Equal Tries always encode to the
+    /// same bytes.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// trie.insert("app", 2);
+    /// let bytes = trie.to_bytes();
+    /// assert_eq!(Trie::<i32>::from_bytes(&bytes), Ok(trie));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+
+        let root = self.root();
+        out.push(node_flags(root, false));
+        if let Some(value) = root.get_value() {
+            value.write_bytes(&mut out);
+        }
+
+        // Pre-order walk; a child's subtree is written before its next sibling.
+        let mut stack = vec![root.children_iter_sorted()];
+        while let Some(children) = stack.last_mut() {
+            let Some((&c, child)) = children.next() else {
+                stack.pop();
+                continue;
+            };
+
+            let has_siblings = children.len() > 0;
+            write_varint(&mut out, u64::from(u32::from(c)));
+            out.push(node_flags(child, has_siblings));
+            if let Some(value) = child.get_value() {
+                value.write_bytes(&mut out);
+            }
+            if child.has_children() {
+                stack.push(child.children_iter_sorted());
+            }
+        }
+        out
+    }
+
+    /// Decodes a Trie written by `to_bytes`. Fails if the data is truncated,
+    /// malformed, or has bytes left over at the end.
+    ///
+    /// The decoder keeps its own stack rather than recursing, so deeply nested
+    /// input cannot overflow the call stack.
+    pub fn from_bytes(data: &[u8]) -> Result<Trie<TValue>, DeserializeError> {
+        let mut input = data;
+        let header = input
+            .get(..MAGIC.len() + 1)
+            .ok_or(DeserializeError::UnexpectedEof)?;
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(DeserializeError::BadMagic);
+        }
+        if header[MAGIC.len()] != VERSION {
+            return Err(DeserializeError::UnsupportedVersion(header[MAGIC.len()]));
+        }
+        input = &input[MAGIC.len() + 1..];
+
+        let mut word_count = 0;
+        let root_flags = read_flags(&mut input)?;
+        if root_flags & HAS_SIBLINGS != 0 {
+            return Err(DeserializeError::InvalidFlags(root_flags));
+        }
+        let mut root = TrieNode::new();
+        if root_flags & IS_TERMINAL != 0 {
+            root.set_value(TValue::read_bytes(&mut input)?);
+            word_count += 1;
+        }
+
+        if root_flags & HAS_CHILDREN != 0 {
+            // `parent` is the node whose children are being read. Each entry of
+            // `open` holds an ancestor of it, along with the edge character of
+            // the child being read under that ancestor and whether that child
+            // has siblings still to come.
+            let mut parent = root;
+            let mut open: Vec<(char, TrieNode<TValue>, bool)> = Vec::new();
+            'records: loop {
+                let mut c = read_char(&mut input)?;
+                let flags = read_flags(&mut input)?;
+                // Only the root may be empty; any other node with neither a
+                // value nor children ends no key and could not have been written.
+                if flags & (IS_TERMINAL | HAS_CHILDREN) == 0 {
+                    return Err(DeserializeError::InvalidFlags(flags));
+                }
+                let mut has_siblings = flags & HAS_SIBLINGS != 0;
+                let mut node = TrieNode::new();
+                if flags & IS_TERMINAL != 0 {
+                    node.set_value(TValue::read_bytes(&mut input)?);
+                    word_count += 1;
+                }
+                if flags & HAS_CHILDREN != 0 {
+                    open.push((c, core::mem::replace(&mut parent, node), has_siblings));
+                    continue;
+                }
+
+                // `node` is complete. Attach it, then close every ancestor
+                // that has no siblings left to read.
+                loop {
+                    if parent.get_child(c).is_some() {
+                        return Err(DeserializeError::DuplicateChild(c));
+                    }
+                    parent.set_child(c, node);
+                    if has_siblings {
+                        continue 'records;
+                    }
+                    let Some((ancestor_c, ancestor, ancestor_has_siblings)) = open.pop() else {
+                        break 'records;
+                    };
+                    node = core::mem::replace(&mut parent, ancestor);
+                    c = ancestor_c;
+                    has_siblings = ancestor_has_siblings;
+                }
+            }
+            root = parent;
+        }
+
+        if !input.is_empty() {
+            return Err(DeserializeError::TrailingBytes(input.len()));
+        }
+        Ok(Trie::from_root(root, word_count))
+    }
+
+    /// Writes the Trie to the file at `path` in the `to_bytes` format,
+    /// replacing the file if it exists.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut counts = Trie::new();
+    /// counts.insert("apple", 3u32);
+    /// counts.save_to_file("counts.trh")?;
+    /// assert_eq!(Trie::<u32>::load_from_file("counts.trh")?, counts);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    /// Reads a Trie written by `save_to_file`. I/O failures are passed through
+    /// unchanged; data that does not decode is reported as
+    /// `io::ErrorKind::InvalidData`, wrapping the `DeserializeError`.
+    #[cfg(feature = "std")]
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> io::Result<Trie<TValue>> {
+        let data = fs::read(path)?;
+        Self::from_bytes(&data).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+fn node_flags<TValue>(node: &TrieNode<TValue>, has_siblings: bool) -> u8 {
+    let mut flags = 0;
+    if node.is_end_of_word() {
+        flags |= IS_TERMINAL;
+    }
+    if node.has_children() {
+        flags |= HAS_CHILDREN;
+    }
+    if has_siblings {
+        flags |= HAS_SIBLINGS;
+    }
+    flags
+}
+
+fn read_flags(input: &mut &[u8]) -> Result<u8, DeserializeError> {
+    let flags = read_byte(input)?;
+    if flags & !(IS_TERMINAL | HAS_CHILDREN | HAS_SIBLINGS) != 0 {
+        return Err(DeserializeError::InvalidFlags(flags));
+    }
+    Ok(flags)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(input: &mut &[u8]) -> Result<u64, DeserializeError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_byte(input)?;
+        let bits = u64::from(byte & 0x7f);
+        // The tenth byte may only carry the single top bit of a u64.
+        if shift == 63 && bits > 1 {
+            return Err(DeserializeError::VarintOverflow);
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(DeserializeError::VarintOverflow)
+}
+
+fn read_char(input: &mut &[u8]) -> Result<char, DeserializeError> {
+    let code = read_varint(input)?;
+    let code = u32::try_from(code).map_err(|_| DeserializeError::VarintOverflow)?;
+    char::from_u32(code).ok_or(DeserializeError::InvalidChar(code))
+}
+
+fn read_byte(input: &mut &[u8]) -> Result<u8, DeserializeError> {
+    let (&byte, rest) = input.split_first().ok_or(DeserializeError::UnexpectedEof)?;
+    *input = rest;
+    Ok(byte)
+}
+
+fn read_array<const N: usize>(input: &mut &[u8]) -> Result<[u8; N], DeserializeError> {
+    let (bytes, rest) = input
+        .split_first_chunk::<N>()
+        .ok_or(DeserializeError::UnexpectedEof)?;
+    *input = rest;
+    Ok(*bytes)
+}
+
+/// Reads a varint length followed by that many bytes.
+fn read_slice<'a>(input: &mut &'a [u8]) -> Result<&'a [u8], DeserializeError> {
+    let len = usize::read_bytes(input)?;
+    if input.len() < len {
+        return Err(DeserializeError::UnexpectedEof);
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Ok(bytes)
+}
@@ -0,0 +1,110 @@
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// A fluent builder for `Trie`. Options and entries are only recorded until
+/// `build`, which applies them all at once.
+///
+/// Example:
+/// ```Rust
+/// let trie = TrieBuilder::new()
+///     .case_insensitive(true)
+///     .root_capacity(26)
+///     .insert("Hello", 1)
+///     .insert("World", 2)
+///     .build();
+/// assert_eq!(trie.get("hello"), Some(&1));
+/// ```
+pub struct TrieBuilder<TValue> {
+    case_insensitive: bool,
+    sorted_children: bool,
+    root_capacity: usize,
+    entries: Vec<(String, TValue)>,
+}
+
+impl<TValue> TrieBuilder<TValue> {
+    /// Starts a builder with no entries, case-sensitive keys, children in
+    /// whatever order the children map keeps them, and no capacity hint.
+    pub fn new() -> Self {
+        TrieBuilder {
+            case_insensitive: false,
+            sorted_children: false,
+            root_capacity: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// When enabled, every key is lowercased as it is inserted, so keys that
+    /// differ only in case end up as one entry (the last one wins). Lookups on
+    /// the built Trie must use lowercase keys; wrap it in a `CaseFoldedTrie` to
+    /// fold lookups as well.
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// When enabled, the built Trie visits each node's children in `char`
+    /// order on the walks that otherwise follow the children map's own
+    /// order, so `values`, `values_mut`, `iter_mut`, `into_iter` and `drain`
+    /// yield entries in lexicographic key order, as `iter` always does.
+    ///
+    /// Where the children already live in a sorted map (without the `std`
+    /// feature, or with `smallvec-children`) this costs nothing; with a
+    /// `HashMap` each node's children are sorted as the walk reaches them.
+    /// The setting carries over to clones and `subtrie`s of the built Trie.
+    pub fn sorted_children(mut self, enabled: bool) -> Self {
+        self.sorted_children = enabled;
+        self
+    }
+
+    /// Reserves room for `capacity` distinct first characters in the root, so a
+    /// large build doesn't repeatedly grow the root's children map.
+    pub fn root_capacity(mut self, capacity: usize) -> Self {
+        self.root_capacity = capacity;
+        self
+    }
+
+    /// Queues a key-value pair. When a key is queued more than once, the last
+    /// value wins, as with `Trie::insert`.
+    pub fn insert(mut self, key: &str, value: TValue) -> Self {
+        self.entries.push((key.to_string(), value));
+        self
+    }
+
+    /// Queues every key-value pair from `entries`.
+    pub fn insert_all<K, I>(mut self, entries: I) -> Self
+    where
+        K: AsRef<str>,
+        I: IntoIterator<Item = (K, TValue)>,
+    {
+        self.entries.extend(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.as_ref().to_string(), value)),
+        );
+        self
+    }
+
+    /// Consumes the builder and returns the constructed Trie.
+    pub fn build(self) -> Trie<TValue> {
+        let mut trie = Trie::from_root(TrieNode::with_capacity(self.root_capacity), 0);
+        trie.set_sorted_children(self.sorted_children);
+        for (key, value) in self.entries {
+            if self.case_insensitive {
+                let folded: String = key.chars().flat_map(char::to_lowercase).collect();
+                trie.insert(&folded, value);
+            } else {
+                trie.insert(&key, value);
+            }
+        }
+        trie
+    }
+}
+
+/// Allows creating a new TrieBuilder with `TrieBuilder::default()`.
+impl<TValue> Default for TrieBuilder<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
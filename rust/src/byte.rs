@@ -0,0 +1,212 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Number of byte values, and so the number of child slots per node.
+const BYTE_SIZE: usize = 256;
+
+/// A Trie over raw byte strings rather than Unicode text. Each node holds its
+/// children in a fixed 256-slot array indexed by byte, so keys need not be
+/// valid UTF-8 and following an edge is a single array index with no hashing
+/// or character decoding. As with `AsciiTrie`, the price is memory: every node
+/// reserves a slot for every byte value (about 2 KiB per node on 64-bit
+/// targets).
+///
+/// The API mirrors `Trie`, with `&[u8]` keys and `Vec<u8>` completions.
+#[derive(Clone)]
+pub struct ByteTrie<TValue> {
+    root: ByteTrieNode<TValue>,
+    word_count: usize,
+}
+
+#[derive(Clone)]
+struct ByteTrieNode<TValue> {
+    children: [Option<Box<ByteTrieNode<TValue>>>; BYTE_SIZE],
+    // Number of occupied slots in `children`, so emptiness checks don't scan.
+    // A `u8` cannot count all 256 of them.
+    child_count: u16,
+    value: Option<TValue>,
+}
+
+impl<TValue> ByteTrieNode<TValue> {
+    const fn new() -> Self {
+        ByteTrieNode {
+            children: [const { None }; BYTE_SIZE],
+            child_count: 0,
+            value: None,
+        }
+    }
+
+    fn get_child(&self, byte: u8) -> Option<&ByteTrieNode<TValue>> {
+        self.children[byte as usize].as_deref()
+    }
+
+    fn add_child(&mut self, byte: u8) -> &mut ByteTrieNode<TValue> {
+        let slot = &mut self.children[byte as usize];
+        if slot.is_none() {
+            self.child_count += 1;
+        }
+        slot.get_or_insert_with(|| Box::new(ByteTrieNode::new()))
+    }
+}
+
+impl<TValue> ByteTrie<TValue> {
+    /// Initializes a new, empty ByteTrie. Usable in `const` and `static` items.
+    pub const fn new() -> Self {
+        ByteTrie {
+            root: ByteTrieNode::new(),
+            word_count: 0,
+        }
+    }
+
+    /// Returns the number of keys stored in the ByteTrie. This is O(1).
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns true if the ByteTrie contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
+    /// Inserts a key-value pair, returning the previous value if the key was
+    /// already stored.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = ByteTrie::new();
+    /// assert_eq!(trie.insert(b"\x00\xff", 1), None);
+    /// assert_eq!(trie.insert(b"\x00\xff", 2), Some(1));
+    /// ```
+    pub fn insert(&mut self, key: &[u8], value: TValue) -> Option<TValue> {
+        let mut current_node = &mut self.root;
+        for &byte in key {
+            current_node = current_node.add_child(byte);
+        }
+        let previous = current_node.value.replace(value);
+        if previous.is_none() {
+            self.word_count += 1;
+        }
+        previous
+    }
+
+    /// Gets the value stored under `key`.
+    pub fn get(&self, key: &[u8]) -> Option<&TValue> {
+        self.find_node(key)?.value.as_ref()
+    }
+
+    /// Deletes a key and its value, pruning any nodes that only existed for it.
+    /// Returns true if the key was found. As with `Trie`, the empty key cannot be
+    /// removed this way.
+    pub fn delete(&mut self, key: &[u8]) -> bool {
+        if key.is_empty() {
+            return false;
+        }
+        let mut removed = false;
+        Self::delete_recursively(&mut self.root, key, &mut removed);
+        if removed {
+            self.word_count -= 1;
+        }
+        removed
+    }
+
+    /// Recursive helper for `delete`. Returns true if the caller should drop
+    /// `current_node` because it no longer holds a value or any children.
+    fn delete_recursively(
+        current_node: &mut ByteTrieNode<TValue>,
+        key: &[u8],
+        removed: &mut bool,
+    ) -> bool {
+        let Some((&byte, rest)) = key.split_first() else {
+            *removed = current_node.value.take().is_some();
+            return *removed && current_node.child_count == 0;
+        };
+
+        let slot = &mut current_node.children[byte as usize];
+        let Some(child) = slot.as_deref_mut() else {
+            return false;
+        };
+        if Self::delete_recursively(child, rest, removed) {
+            *slot = None;
+            current_node.child_count -= 1;
+            return current_node.value.is_none() && current_node.child_count == 0;
+        }
+        false
+    }
+
+    /// Returns true if any stored key starts with `prefix`.
+    pub fn prefix_search(&self, prefix: &[u8]) -> bool {
+        self.find_node(prefix).is_some()
+    }
+
+    /// Returns up to `max_results` keys that start with `prefix`, in
+    /// lexicographic byte order.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = ByteTrie::new();
+    /// trie.insert(b"\x01\x02", 1);
+    /// trie.insert(b"\x01\xff", 2);
+    /// trie.insert(b"\x02", 3);
+    /// assert_eq!(trie.auto_complete(b"\x01", 10), vec![b"\x01\x02".to_vec(), b"\x01\xff".to_vec()]);
+    /// ```
+    pub fn auto_complete(&self, prefix: &[u8], max_results: usize) -> Vec<Vec<u8>> {
+        let mut results = Vec::new();
+        if max_results == 0 {
+            return results;
+        }
+        if let Some(node) = self.find_node(prefix) {
+            let mut key = prefix.to_vec();
+            Self::collect_keys_recursive(node, &mut key, &mut results, max_results);
+        }
+        results
+    }
+
+    fn collect_keys_recursive(
+        node: &ByteTrieNode<TValue>,
+        key: &mut Vec<u8>,
+        results: &mut Vec<Vec<u8>>,
+        max_results: usize,
+    ) {
+        if node.value.is_some() {
+            results.push(key.clone());
+        }
+        // Slots are ordered by byte value, so walking them in order already
+        // yields keys lexicographically.
+        for (byte, child) in node.children.iter().enumerate() {
+            if results.len() >= max_results {
+                return;
+            }
+            if let Some(child) = child {
+                key.push(byte as u8);
+                Self::collect_keys_recursive(child, key, results, max_results);
+                key.pop();
+            }
+        }
+    }
+
+    /// Adds multiple keys, using `value_generator` to compute each key's value.
+    pub fn add_word_list<T, F>(&mut self, items: &[T], value_generator: F)
+    where
+        T: AsRef<[u8]>,
+        F: Fn(&T) -> TValue,
+    {
+        for item in items {
+            self.insert(item.as_ref(), value_generator(item));
+        }
+    }
+
+    fn find_node(&self, prefix: &[u8]) -> Option<&ByteTrieNode<TValue>> {
+        let mut current_node = &self.root;
+        for &byte in prefix {
+            current_node = current_node.get_child(byte)?;
+        }
+        Some(current_node)
+    }
+}
+
+/// Allows creating a new ByteTrie with `ByteTrie::default()`.
+impl<TValue> Default for ByteTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,91 @@
+use crate::trie::Trie;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A Trie that matches keys case-insensitively. Every key is lowercased before
+/// it reaches the inner Trie, so "Hello", "HELLO" and "hello" are the same key,
+/// and keys handed back (e.g. by `auto_complete`) are in their lowercase form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CaseFoldedTrie<TValue> {
+    inner: Trie<TValue>,
+}
+
+impl<TValue> CaseFoldedTrie<TValue> {
+    /// Initializes a new, empty CaseFoldedTrie.
+    pub fn new() -> Self {
+        CaseFoldedTrie { inner: Trie::new() }
+    }
+
+    /// Returns the number of distinct lowercased keys stored.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns true if no keys are stored.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Inserts a key-value pair, returning the value previously stored under any
+    /// casing of the key.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = CaseFoldedTrie::new();
+    /// trie.insert("Hello", 1);
+    /// assert_eq!(trie.insert("HELLO", 2), Some(1));
+    /// assert_eq!(trie.get("hello"), Some(&2));
+    /// ```
+    pub fn insert(&mut self, key: &str, value: TValue) -> Option<TValue> {
+        self.inner.insert(&fold(key), value)
+    }
+
+    /// Gets the value stored under any casing of `key`.
+    pub fn get(&self, key: &str) -> Option<&TValue> {
+        self.inner.get(&fold(key))
+    }
+
+    /// Deletes the key regardless of casing. Returns true if it was stored.
+    pub fn delete(&mut self, key: &str) -> bool {
+        self.inner.delete(&fold(key))
+    }
+
+    /// Returns true if any stored key starts with `prefix`, ignoring case.
+    pub fn prefix_search(&self, prefix: &str) -> bool {
+        self.inner.prefix_search(&fold(prefix))
+    }
+
+    /// Returns up to `max_results` lowercased keys starting with `prefix`,
+    /// ignoring case.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = CaseFoldedTrie::new();
+    /// trie.insert("Hello", 1);
+    /// assert_eq!(trie.auto_complete("HE", 10), vec!["hello"]);
+    /// ```
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        self.inner.auto_complete(&fold(prefix), max_results)
+    }
+
+    /// Returns a reference to the inner Trie, which holds the lowercased keys.
+    pub fn as_trie(&self) -> &Trie<TValue> {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the inner Trie of lowercased keys.
+    pub fn into_inner(self) -> Trie<TValue> {
+        self.inner
+    }
+}
+
+/// Allows creating a new CaseFoldedTrie with `CaseFoldedTrie::default()`.
+impl<TValue> Default for CaseFoldedTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn fold(key: &str) -> String {
+    key.chars().flat_map(char::to_lowercase).collect()
+}
@@ -0,0 +1,210 @@
+use crate::trie::Trie;
+use crate::trie_node::ChildIntoIter;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Index of a node in the graph.
+type NodeId = u32;
+
+/// A read-only Directed Acyclic Word Graph, built from a `Trie` by
+/// `Trie::into_dawg`.
+///
+/// Every set of identical subtrees in the Trie (same words below them, with
+/// the same values) is stored once and shared by all the edges that led to a
+/// copy. Natural-language word lists share long suffixes as well as prefixes
+/// ("-ing", "-tion"), so this is typically several times fewer nodes than the
+/// Trie, with the same lookups and completions.
+#[derive(Clone, Debug)]
+pub struct Dawg<TValue> {
+    nodes: Vec<DawgNode<TValue>>,
+    root: NodeId,
+    word_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct DawgNode<TValue> {
+    value: Option<TValue>,
+    // Sorted by character, like `ArenaTrie`'s children.
+    children: Vec<(char, NodeId)>,
+}
+
+impl<TValue> DawgNode<TValue> {
+    fn get_child(&self, c: char) -> Option<NodeId> {
+        self.children
+            .binary_search_by_key(&c, |&(child_c, _)| child_c)
+            .ok()
+            .map(|i| self.children[i].1)
+    }
+}
+
+impl<TValue: Eq + Hash> Trie<TValue> {
+    /// Converts the Trie into a `Dawg` by merging identical subtrees.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("tap", ());
+    /// trie.insert("taps", ());
+    /// trie.insert("top", ());
+    /// trie.insert("tops", ());
+    /// let dawg = trie.into_dawg();
+    /// assert!(dawg.contains("tops"));
+    /// assert_eq!(dawg.node_count(), 5); // "a" and "o" lead to the same node
+    /// ```
+    pub fn into_dawg(self) -> Dawg<TValue> {
+        let word_count = self.len();
+        let mut builder = DawgBuilder {
+            nodes: Vec::new(),
+            buckets: HashMap::new(),
+        };
+
+        // Post-order walk with an explicit stack, so every node is interned
+        // after all of its children and deep keys cannot overflow the stack.
+        let (value, children) = self.into_root().into_parts();
+        let mut stack = vec![Frame {
+            label: '\0',
+            value,
+            unvisited: children,
+            children: Vec::new(),
+        }];
+        let root = loop {
+            let top = stack.last_mut().expect("the root frame is popped last");
+            if let Some((c, child)) = top.unvisited.next() {
+                let (value, children) = child.into_parts();
+                stack.push(Frame {
+                    label: c,
+                    value,
+                    unvisited: children,
+                    children: Vec::new(),
+                });
+                continue;
+            }
+
+            let Frame {
+                label,
+                value,
+                mut children,
+                ..
+            } = stack.pop().expect("the stack is not empty");
+            children.sort_unstable_by_key(|&(c, _)| c);
+            let id = builder.intern(DawgNode { value, children });
+            match stack.last_mut() {
+                Some(parent) => parent.children.push((label, id)),
+                None => break id,
+            }
+        };
+
+        Dawg {
+            nodes: builder.nodes,
+            root,
+            word_count,
+        }
+    }
+}
+
+/// One node of the post-order walk in `into_dawg`.
+struct Frame<TValue> {
+    label: char,
+    value: Option<TValue>,
+    unvisited: ChildIntoIter<TValue>,
+    children: Vec<(char, NodeId)>,
+}
+
+struct DawgBuilder<TValue> {
+    nodes: Vec<DawgNode<TValue>>,
+    // Ids of the nodes built so far, bucketed by a hash of their value and
+    // child links. Children are interned first, so equal links mean equal
+    // subtrees and the hash covers the whole subtree.
+    buckets: HashMap<u64, Vec<NodeId>>,
+}
+
+impl<TValue: Eq + Hash> DawgBuilder<TValue> {
+    /// Returns the id of a node equal to `node`, adding it if there is none.
+    fn intern(&mut self, node: DawgNode<TValue>) -> NodeId {
+        let mut hasher = DefaultHasher::new();
+        node.hash(&mut hasher);
+        let bucket = self.buckets.entry(hasher.finish()).or_default();
+        if let Some(&id) = bucket.iter().find(|&&id| self.nodes[id as usize] == node) {
+            return id;
+        }
+
+        let id =
+            NodeId::try_from(self.nodes.len()).expect("Dawg cannot hold more than u32::MAX nodes");
+        self.nodes.push(node);
+        bucket.push(id);
+        id
+    }
+}
+
+impl<TValue> Dawg<TValue> {
+    /// Returns the number of words stored. This is O(1).
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns true if the Dawg contains no words.
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
+    /// Returns true if `key` is a stored word.
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Gets the value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&TValue> {
+        self.node(self.find_node(key)?).value.as_ref()
+    }
+
+    /// Returns true if any stored word starts with `prefix`.
+    pub fn prefix_search(&self, prefix: &str) -> bool {
+        self.find_node(prefix).is_some()
+    }
+
+    /// Returns up to `max_results` words that start with `prefix`, in
+    /// lexicographic order.
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        let mut results = Vec::new();
+        let Some(start) = self.find_node(prefix) else {
+            return results;
+        };
+
+        // Shared nodes are reached once per path into them, so this visits the
+        // same keys, in the same order, as the walk over the original Trie.
+        let mut stack = vec![(start, prefix.to_string())];
+        while let Some((id, key)) = stack.pop() {
+            if results.len() >= max_results {
+                break;
+            }
+            let node = self.node(id);
+            for &(c, child) in node.children.iter().rev() {
+                let mut child_key = key.clone();
+                child_key.push(c);
+                stack.push((child, child_key));
+            }
+            if node.value.is_some() {
+                results.push(key);
+            }
+        }
+        results
+    }
+
+    /// Returns the number of distinct nodes, including the root.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn node(&self, id: NodeId) -> &DawgNode<TValue> {
+        &self.nodes[id as usize]
+    }
+
+    fn find_node(&self, prefix: &str) -> Option<NodeId> {
+        let mut current = self.root;
+        for c in prefix.chars() {
+            current = self.node(current).get_child(c)?;
+        }
+        Some(current)
+    }
+}
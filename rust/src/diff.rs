@@ -0,0 +1,116 @@
+use crate::trie::Trie;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// The changes that turn one Trie into another, produced by `Trie::diff` and
+/// applied with `Trie::apply_diff`.
+///
+/// Each list is in lexicographic key order. The diff owns copies of the values,
+/// so it can be applied to the very Trie it was computed from, e.g. to hot-reload
+/// a word list by changing only what differs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieDiff<TValue> {
+    /// Keys only the other Trie has, with their values.
+    pub added: Vec<(String, TValue)>,
+    /// Keys only the original Trie has.
+    pub removed: Vec<String>,
+    /// Keys both have with different values: the original value, then the new one.
+    pub modified: Vec<(String, TValue, TValue)>,
+}
+
+impl<TValue> TrieDiff<TValue> {
+    /// Returns true if the two tries were equal.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Returns the total number of changed keys.
+    pub fn len(&self) -> usize {
+        self.added.len() + self.removed.len() + self.modified.len()
+    }
+
+    /// Applies the changes to `trie`. Same as `trie.apply_diff(self)`.
+    pub fn apply_to(self, trie: &mut Trie<TValue>) {
+        for key in &self.removed {
+            if key.is_empty() {
+                trie.take_root_value();
+            } else {
+                trie.delete(key);
+            }
+        }
+        for (key, value) in self.added {
+            trie.insert(&key, value);
+        }
+        for (key, _, value) in self.modified {
+            trie.insert(&key, value);
+        }
+    }
+}
+
+impl<TValue: Clone + PartialEq> Trie<TValue> {
+    /// Compares the Trie with `other` and returns what `other` adds, removes
+    /// and modifies. Both tries are walked once, side by side in key order,
+    /// without any lookups.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut old = Trie::new();
+    /// old.insert("cat", 1);
+    /// old.insert("dog", 2);
+    /// let mut new = Trie::new();
+    /// new.insert("cat", 5);
+    /// new.insert("eel", 3);
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.added, vec![("eel".to_string(), 3)]);
+    /// assert_eq!(diff.removed, vec!["dog".to_string()]);
+    /// assert_eq!(diff.modified, vec![("cat".to_string(), 1, 5)]);
+    /// old.apply_diff(diff);
+    /// assert_eq!(old, new);
+    /// ```
+    pub fn diff(&self, other: &Trie<TValue>) -> TrieDiff<TValue> {
+        let mut diff = TrieDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            modified: Vec::new(),
+        };
+        let mut mine = self.iter().peekable();
+        let mut theirs = other.iter().peekable();
+        loop {
+            let order = match (mine.peek(), theirs.peek()) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some((my_key, _)), Some((their_key, _))) => my_key.cmp(their_key),
+            };
+            match order {
+                Ordering::Less => {
+                    let (key, _) = mine.next().expect("peeked");
+                    diff.removed.push(key);
+                }
+                Ordering::Greater => {
+                    let (key, value) = theirs.next().expect("peeked");
+                    diff.added.push((key, value.clone()));
+                }
+                Ordering::Equal => {
+                    let (key, old) = mine.next().expect("peeked");
+                    let (_, new) = theirs.next().expect("peeked");
+                    if old != new {
+                        diff.modified.push((key, old.clone(), new.clone()));
+                    }
+                }
+            }
+        }
+        diff
+    }
+}
+
+impl<TValue> Trie<TValue> {
+    /// Applies a `TrieDiff`: removes its removed keys and stores the new value
+    /// of every added or modified key. Keys are set to the diff's target state
+    /// whatever they hold now, so applying a diff twice, or to a Trie that has
+    /// drifted since, does not fail.
+    pub fn apply_diff(&mut self, diff: TrieDiff<TValue>) {
+        diff.apply_to(self);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::hash::BuildHasher;
+
+impl<TValue, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Renders the Trie as a Graphviz DOT digraph, ready for `dot -Tsvg`.
+    /// Every node gets its own id, the root is labeled `ROOT`, edges are labeled
+    /// with their character, and nodes that end a stored key are drawn as double
+    /// circles.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("hi", 1);
+    /// std::fs::write("trie.dot", trie.to_dot())?;
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.to_dot_truncated(usize::MAX)
+    }
+
+    /// Like `to_dot`, but stops `max_depth` edges below the root so large Tries
+    /// stay readable. Nodes whose children were cut off are drawn dashed.
+    pub fn to_dot_truncated(&self, max_depth: usize) -> String {
+        let mut dot = String::from("digraph Trie {\n");
+        dot.push_str("    node [shape=circle, label=\"\"];\n");
+
+        let mut next_id = 0usize;
+        let mut queue: VecDeque<(usize, usize, &TrieNode<TValue, H>)> = VecDeque::new();
+        queue.push_back((next_id, 0, self.root()));
+        while let Some((id, depth, node)) = queue.pop_front() {
+            let truncated = depth >= max_depth && node.has_children();
+            write_node(&mut dot, id, depth == 0, node.is_end_of_word(), truncated);
+            if depth >= max_depth {
+                continue;
+            }
+
+            for (&c, child) in node.children_iter_sorted() {
+                next_id += 1;
+                writeln!(dot, "    n{id} -> n{next_id} [label=\"{}\"];", escape(c))
+                    .expect("writing to a String cannot fail");
+                queue.push_back((next_id, depth + 1, child));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn write_node(dot: &mut String, id: usize, is_root: bool, is_terminal: bool, truncated: bool) {
+    let mut attributes = Vec::new();
+    if is_root {
+        attributes.push("label=\"ROOT\"");
+    }
+    if is_terminal {
+        attributes.push("shape=doublecircle");
+    }
+    if truncated {
+        attributes.push("style=dashed");
+    }
+    writeln!(dot, "    n{id} [{}];", attributes.join(", "))
+        .expect("writing to a String cannot fail");
+}
+
+/// Escapes the characters that would end or break a quoted DOT label.
+fn escape(c: char) -> String {
+    match c {
+        '"' => "\\\"".to_string(),
+        '\\' => "\\\\".to_string(),
+        '\n' => "\\n".to_string(),
+        c => c.to_string(),
+    }
+}
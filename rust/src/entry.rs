@@ -0,0 +1,179 @@
+use crate::map::DefaultHashBuilder;
+use crate::trie_node::TrieNode;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// A view into a single key of a Trie, which may either be vacant or occupied.
+/// This enum is constructed from the `entry` method on `Trie`.
+pub enum Entry<'a, TValue, H = DefaultHashBuilder> {
+    /// The key is stored in the Trie.
+    Occupied(OccupiedEntry<'a, TValue, H>),
+    /// The key is not stored in the Trie.
+    Vacant(VacantEntry<'a, TValue, H>),
+}
+
+/// A view into a key that is stored in the Trie.
+pub struct OccupiedEntry<'a, TValue, H = DefaultHashBuilder> {
+    // The deepest node that survives if this key is removed. `tail` is the path
+    // from it down to the key's node, and every node along that path only
+    // exists for this key, so removal can drop the whole branch at once.
+    anchor: &'a mut TrieNode<TValue, H>,
+    tail: Vec<char>,
+    word_count: &'a mut usize,
+}
+
+/// A view into a key that is not stored in the Trie.
+pub struct VacantEntry<'a, TValue, H = DefaultHashBuilder> {
+    // The deepest node of the key's path that already exists. `remaining` holds
+    // the characters whose nodes are created when a value is inserted.
+    node: &'a mut TrieNode<TValue, H>,
+    remaining: Vec<char>,
+    word_count: &'a mut usize,
+}
+
+impl<'a, TValue, H: BuildHasher + Clone> Entry<'a, TValue, H> {
+    /// Ensures a value is in the entry by inserting `default` if it is vacant,
+    /// and returns a mutable reference to the value.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// *trie.entry("apple").or_insert(0) += 1;
+    /// assert_eq!(trie.get("apple"), Some(&1));
+    /// ```
+    pub fn or_insert(self, default: TValue) -> &'a mut TValue {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of `default` if it
+    /// is vacant, and returns a mutable reference to the value.
+    pub fn or_insert_with<F: FnOnce() -> TValue>(self, default: F) -> &'a mut TValue {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting `TValue::default()` if it is
+    /// vacant, and returns a mutable reference to the value.
+    pub fn or_default(self) -> &'a mut TValue
+    where
+        TValue: Default,
+    {
+        self.or_insert_with(TValue::default)
+    }
+
+    /// Calls `f` with the stored value if the entry is occupied, then returns the
+    /// entry so it can be chained with `or_insert`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.entry("apple").and_modify(|v| *v += 1).or_insert(1);
+    /// trie.entry("apple").and_modify(|v| *v += 1).or_insert(1);
+    /// assert_eq!(trie.get("apple"), Some(&2));
+    /// ```
+    pub fn and_modify<F: FnOnce(&mut TValue)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, TValue, H: BuildHasher + Clone> OccupiedEntry<'a, TValue, H> {
+    pub(crate) fn new(
+        anchor: &'a mut TrieNode<TValue, H>,
+        tail: Vec<char>,
+        word_count: &'a mut usize,
+    ) -> Self {
+        Self {
+            anchor,
+            tail,
+            word_count,
+        }
+    }
+
+    /// Gets a reference to the value in the entry.
+    pub fn get(&self) -> &TValue {
+        let mut node = &*self.anchor;
+        for &c in &self.tail {
+            node = node.get_child(c).expect("occupied entry path exists");
+        }
+        node.get_value().expect("occupied entry holds a value")
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    pub fn get_mut(&mut self) -> &mut TValue {
+        Self::value_mut(&mut *self.anchor, &self.tail)
+    }
+
+    /// Converts the entry into a mutable reference to its value, bound to the
+    /// lifetime of the Trie borrow.
+    pub fn into_mut(self) -> &'a mut TValue {
+        Self::value_mut(self.anchor, &self.tail)
+    }
+
+    /// Sets the value of the entry and returns the old value.
+    pub fn insert(&mut self, value: TValue) -> TValue {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the key from the Trie, pruning any nodes that only existed for it,
+    /// and returns its value.
+    pub fn remove(self) -> TValue {
+        *self.word_count -= 1;
+        let Some((&first, rest)) = self.tail.split_first() else {
+            // The key's node is kept because other words pass through it.
+            return self
+                .anchor
+                .clear_value()
+                .expect("occupied entry holds a value");
+        };
+
+        let mut node = self
+            .anchor
+            .remove_child(first)
+            .expect("occupied entry path exists");
+        for &c in rest {
+            node = node.remove_child(c).expect("occupied entry path exists");
+        }
+        node.clear_value().expect("occupied entry holds a value")
+    }
+
+    fn value_mut<'n>(mut node: &'n mut TrieNode<TValue, H>, tail: &[char]) -> &'n mut TValue {
+        for &c in tail {
+            node = node.get_child_mut(c).expect("occupied entry path exists");
+        }
+        node.get_value_mut().expect("occupied entry holds a value")
+    }
+}
+
+impl<'a, TValue, H: BuildHasher + Clone> VacantEntry<'a, TValue, H> {
+    pub(crate) fn new(
+        node: &'a mut TrieNode<TValue, H>,
+        remaining: Vec<char>,
+        word_count: &'a mut usize,
+    ) -> Self {
+        Self {
+            node,
+            remaining,
+            word_count,
+        }
+    }
+
+    /// Inserts the value for the entry's key, creating any missing nodes, and
+    /// returns a mutable reference to it.
+    pub fn insert(self, value: TValue) -> &'a mut TValue {
+        let mut node = self.node;
+        for c in self.remaining {
+            node = node.add_child(c);
+        }
+        *self.word_count += 1;
+        node.set_value(value);
+        node.get_value_mut().expect("value was just set")
+    }
+}
@@ -0,0 +1,190 @@
+//! A C API over `Trie<i64>`, enabled by the `ffi` feature.
+//!
+//! The header `include/trie_hard.h` is generated from this module with
+//! `cbindgen --config cbindgen.toml --output include/trie_hard.h`. Build the
+//! library for C with `cargo rustc --lib --release --features ffi --crate-type
+//! staticlib` (or `cdylib`).
+//!
+//! Keys are NUL-terminated UTF-8 strings. A null or non-UTF-8 key matches
+//! nothing: lookups report it missing and `trie_insert` ignores it. Every
+//! function also accepts a null trie pointer and treats it as an empty trie.
+
+use crate::trie::Trie;
+use std::ffi::{CStr, CString, c_char};
+
+/// An opaque handle to a `Trie<i64>`. Create one with `trie_new` and release
+/// it with `trie_free`.
+pub struct TrieHandle {
+    trie: Box<Trie<i64>>,
+}
+
+/// Reads a key passed from C, or `None` if it is null or not UTF-8.
+///
+/// # Safety
+///
+/// `key` must be null or point to a NUL-terminated string that stays valid
+/// for `'a`.
+unsafe fn key_from_c<'a>(key: *const c_char) -> Option<&'a str> {
+    if key.is_null() {
+        return None;
+    }
+    // SAFETY: non-null, and the caller guarantees it is NUL-terminated.
+    unsafe { CStr::from_ptr(key) }.to_str().ok()
+}
+
+/// Creates a new, empty trie. The caller owns the handle and must release it
+/// with `trie_free`.
+#[unsafe(no_mangle)]
+pub extern "C" fn trie_new() -> *mut TrieHandle {
+    Box::into_raw(Box::new(TrieHandle {
+        trie: Box::new(Trie::new()),
+    }))
+}
+
+/// Releases a trie created by `trie_new`. Passing null does nothing.
+///
+/// # Safety
+///
+/// `trie` must be null or a handle from `trie_new` that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn trie_free(trie: *mut TrieHandle) {
+    if !trie.is_null() {
+        // SAFETY: the caller guarantees the handle came from `trie_new`.
+        drop(unsafe { Box::from_raw(trie) });
+    }
+}
+
+/// Stores `value` under `key`, replacing any previous value.
+///
+/// # Safety
+///
+/// `trie` must be null or a live handle from `trie_new`, and `key` must be
+/// null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn trie_insert(trie: *mut TrieHandle, key: *const c_char, value: i64) {
+    // SAFETY: guaranteed by the caller.
+    let (Some(handle), Some(key)) = (unsafe { trie.as_mut() }, unsafe { key_from_c(key) }) else {
+        return;
+    };
+    handle.trie.insert(key, value);
+}
+
+/// Looks up `key`. If it is stored, writes its value to `out` (when `out` is
+/// not null) and returns true; otherwise leaves `out` alone and returns false.
+///
+/// # Safety
+///
+/// `trie` must be null or a live handle from `trie_new`, `key` must be null
+/// or a NUL-terminated string, and `out` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn trie_get(
+    trie: *const TrieHandle,
+    key: *const c_char,
+    out: *mut i64,
+) -> bool {
+    // SAFETY: guaranteed by the caller.
+    let (Some(handle), Some(key)) = (unsafe { trie.as_ref() }, unsafe { key_from_c(key) }) else {
+        return false;
+    };
+    let Some(&value) = handle.trie.get(key) else {
+        return false;
+    };
+    if !out.is_null() {
+        // SAFETY: non-null, and the caller guarantees it is writable.
+        unsafe { out.write(value) };
+    }
+    true
+}
+
+/// Removes `key`. Returns true if it was stored.
+///
+/// # Safety
+///
+/// `trie` must be null or a live handle from `trie_new`, and `key` must be
+/// null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn trie_delete(trie: *mut TrieHandle, key: *const c_char) -> bool {
+    // SAFETY: guaranteed by the caller.
+    match (unsafe { trie.as_mut() }, unsafe { key_from_c(key) }) {
+        (Some(handle), Some(key)) => handle.trie.delete(key),
+        _ => false,
+    }
+}
+
+/// Returns true if any stored key starts with `prefix`.
+///
+/// # Safety
+///
+/// `trie` must be null or a live handle from `trie_new`, and `prefix` must be
+/// null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn trie_prefix_search(
+    trie: *const TrieHandle,
+    prefix: *const c_char,
+) -> bool {
+    // SAFETY: guaranteed by the caller.
+    match (unsafe { trie.as_ref() }, unsafe { key_from_c(prefix) }) {
+        (Some(handle), Some(prefix)) => handle.trie.prefix_search(prefix),
+        _ => false,
+    }
+}
+
+/// Writes up to `max` keys starting with `prefix` to `out`, in lexicographic
+/// order, and their count to `out_len`.
+///
+/// Each key is a new NUL-terminated string owned by the caller, to be released
+/// with `trie_string_free`. Keys containing a NUL character cannot be passed to
+/// C and are skipped.
+///
+/// # Safety
+///
+/// `trie` must be null or a live handle from `trie_new`, `prefix` must be null
+/// or a NUL-terminated string, `out` must be null or valid for writing `max`
+/// pointers, and `out_len` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn trie_auto_complete(
+    trie: *const TrieHandle,
+    prefix: *const c_char,
+    max: usize,
+    out: *mut *mut c_char,
+    out_len: *mut usize,
+) {
+    let mut written = 0;
+    // SAFETY: guaranteed by the caller.
+    if let (Some(handle), Some(prefix), false) = (
+        unsafe { trie.as_ref() },
+        unsafe { key_from_c(prefix) },
+        out.is_null(),
+    ) {
+        let keys = handle
+            .trie
+            .prefix_iter(prefix)
+            .filter_map(|(key, _)| CString::new(key).ok())
+            .take(max);
+        for key in keys {
+            // SAFETY: `written < max`, and the caller guarantees `out` holds
+            // `max` pointers.
+            unsafe { out.add(written).write(key.into_raw()) };
+            written += 1;
+        }
+    }
+    if !out_len.is_null() {
+        // SAFETY: non-null, and the caller guarantees it is writable.
+        unsafe { out_len.write(written) };
+    }
+}
+
+/// Releases a string returned by `trie_auto_complete`. Passing null does
+/// nothing.
+///
+/// # Safety
+///
+/// `s` must be null or a string from `trie_auto_complete` that has not been
+/// freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn trie_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: the caller guarantees the string came from `CString::into_raw`.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
@@ -0,0 +1,83 @@
+use crate::trie::Trie;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A Trie that counts how often each word was seen, for word frequencies,
+/// event counts or n-gram models.
+///
+/// Example:
+/// ```Rust
+/// let mut counts = FrequencyTrie::new();
+/// counts.count_all("the cat saw the car".split(' '));
+/// assert_eq!(counts.frequency("the"), 2);
+/// assert_eq!(counts.top_k_completions("ca", 1), vec![("car".to_string(), 1)]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrequencyTrie {
+    inner: Trie<usize>,
+}
+
+impl FrequencyTrie {
+    /// Initializes a new, empty FrequencyTrie.
+    pub fn new() -> Self {
+        FrequencyTrie { inner: Trie::new() }
+    }
+
+    /// Returns the number of distinct words counted.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns true if no words have been counted.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Counts one occurrence of `word`.
+    pub fn count(&mut self, word: &str) {
+        self.inner
+            .entry(word)
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+    }
+
+    /// Counts one occurrence of each word in `words`.
+    pub fn count_all(&mut self, words: impl IntoIterator<Item = impl AsRef<str>>) {
+        for word in words {
+            self.count(word.as_ref());
+        }
+    }
+
+    /// Returns how many times `word` was counted, 0 if never.
+    pub fn frequency(&self, word: &str) -> usize {
+        self.inner.get(word).copied().unwrap_or(0)
+    }
+
+    /// Returns up to `k` words starting with `prefix` with their counts, most
+    /// frequent first. Words with the same count are in lexicographic order.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut counts = FrequencyTrie::new();
+    /// counts.count_all(["card", "care", "care", "cart"]);
+    /// let top = counts.top_k_completions("car", 2);
+    /// assert_eq!(top, vec![("care".to_string(), 2), ("card".to_string(), 1)]);
+    /// ```
+    pub fn top_k_completions(&self, prefix: &str, k: usize) -> Vec<(String, usize)> {
+        self.inner
+            .auto_complete_ranked(prefix, k)
+            .into_iter()
+            .map(|(word, &count)| (word, count))
+            .collect()
+    }
+
+    /// Returns a reference to the inner Trie of counts.
+    pub fn as_trie(&self) -> &Trie<usize> {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the inner Trie of counts.
+    pub fn into_inner(self) -> Trie<usize> {
+        self.inner
+    }
+}
@@ -0,0 +1,233 @@
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use core::ops::Range;
+
+/// Index of a node in a `FrozenTrie`.
+type NodeId = u32;
+
+const ROOT: NodeId = 0;
+
+/// A read-only Trie laid out for query throughput, built from a `Trie` by
+/// `Trie::freeze` once bulk construction is done.
+///
+/// Every node lives in one contiguous `Vec`, in breadth-first order, so the
+/// children of a node are a run of consecutive ids. A node only records where
+/// its run starts and how long it is, and the characters leading into the
+/// nodes are kept in a separate array, also by id, so finding a child is a
+/// binary search over a few adjacent `char`s with no hashing. `thaw` turns it
+/// back into a mutable `Trie`.
+#[derive(Clone, Debug)]
+pub struct FrozenTrie<TValue> {
+    nodes: Vec<FrozenNode<TValue>>,
+    // The character on the edge into each node, by id. The root's is unused.
+    labels: Vec<char>,
+    word_count: usize,
+}
+
+#[derive(Clone, Debug)]
+struct FrozenNode<TValue> {
+    first_child: NodeId,
+    child_count: NodeId,
+    value: Option<TValue>,
+}
+
+impl<TValue> FrozenNode<TValue> {
+    fn children(&self) -> Range<NodeId> {
+        self.first_child..self.first_child + self.child_count
+    }
+}
+
+impl<TValue, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Converts the Trie into a read-only `FrozenTrie`, which answers the same
+    /// queries faster.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "apple" => 1, "apply" => 2 };
+    /// let frozen = trie.freeze();
+    /// assert_eq!(frozen.get("apply"), Some(&2));
+    /// assert_eq!(frozen.auto_complete("app", 10), vec!["apple", "apply"]);
+    /// ```
+    pub fn freeze(self) -> FrozenTrie<TValue> {
+        let word_count = self.len();
+        let mut nodes = Vec::new();
+        let mut labels = vec!['\0'];
+        // Nodes are numbered in the order they are queued, and every node's
+        // children are queued together, so they get consecutive ids.
+        let mut queue = VecDeque::from([self.into_root()]);
+        while let Some(node) = queue.pop_front() {
+            let (value, children) = node.into_parts();
+            let mut children: Vec<_> = children.collect();
+            children.sort_unstable_by_key(|&(c, _)| c);
+            nodes.push(FrozenNode {
+                first_child: labels.len() as NodeId,
+                child_count: children.len() as NodeId,
+                value,
+            });
+            for (c, child) in children {
+                labels.push(c);
+                queue.push_back(child);
+            }
+        }
+
+        FrozenTrie {
+            nodes,
+            labels,
+            word_count,
+        }
+    }
+}
+
+impl<TValue> FrozenTrie<TValue> {
+    /// Returns the number of words stored. This is O(1).
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns true if the FrozenTrie contains no words.
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
+    /// Gets the value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&TValue> {
+        self.node(self.find_node(key)?).value.as_ref()
+    }
+
+    /// Returns true if any stored word starts with `prefix`. As with
+    /// `Trie::prefix_search`, the empty prefix is always found.
+    pub fn prefix_search(&self, prefix: &str) -> bool {
+        self.find_node(prefix).is_some()
+    }
+
+    /// Returns up to `max_results` words that start with `prefix`, in
+    /// lexicographic order.
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        match self.find_node(prefix) {
+            Some(id) => FrozenIter::new(self, id, prefix.to_string())
+                .take(max_results)
+                .map(|(key, _)| key)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns an iterator over the (key, value) pairs in lexicographic order.
+    pub fn iter(&self) -> FrozenIter<'_, TValue> {
+        FrozenIter::new(self, ROOT, String::new())
+    }
+
+    /// Converts back into a mutable `Trie` with the same entries.
+    pub fn thaw(self) -> Trie<TValue> {
+        // Children always have larger ids than their parent, so building the
+        // nodes from the last id down finds each node's children ready.
+        let mut built: Vec<Option<TrieNode<TValue>>> = Vec::new();
+        built.resize_with(self.nodes.len(), || None);
+        for (id, node) in self.nodes.into_iter().enumerate().rev() {
+            let mut trie_node = TrieNode::with_capacity(node.child_count as usize);
+            for child in node.children() {
+                let child_node = built[child as usize]
+                    .take()
+                    .expect("children are built before their parent");
+                trie_node.set_child(self.labels[child as usize], child_node);
+            }
+            if let Some(value) = node.value {
+                trie_node.set_value(value);
+            }
+            built[id] = Some(trie_node);
+        }
+
+        let root = built[ROOT as usize].take().expect("the root is built last");
+        Trie::from_root(root, self.word_count)
+    }
+
+    fn node(&self, id: NodeId) -> &FrozenNode<TValue> {
+        &self.nodes[id as usize]
+    }
+
+    fn get_child(&self, id: NodeId, c: char) -> Option<NodeId> {
+        let children = self.node(id).children();
+        let labels = &self.labels[children.start as usize..children.end as usize];
+        let offset = labels.binary_search(&c).ok()?;
+        Some(children.start + offset as NodeId)
+    }
+
+    fn find_node(&self, prefix: &str) -> Option<NodeId> {
+        let mut current = ROOT;
+        for c in prefix.chars() {
+            current = self.get_child(current, c)?;
+        }
+        Some(current)
+    }
+}
+
+impl<'a, TValue> IntoIterator for &'a FrozenTrie<TValue> {
+    type Item = (String, &'a TValue);
+    type IntoIter = FrozenIter<'a, TValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the (key, value) pairs of a `FrozenTrie` in lexicographic
+/// order, created by `FrozenTrie::iter`.
+pub struct FrozenIter<'a, TValue> {
+    trie: &'a FrozenTrie<TValue>,
+    key: String,
+    // The children still to visit at each level below the start node, which
+    // is yielded first if `start` is set.
+    stack: Vec<Range<NodeId>>,
+    start: Option<NodeId>,
+}
+
+impl<'a, TValue> FrozenIter<'a, TValue> {
+    fn new(trie: &'a FrozenTrie<TValue>, start: NodeId, key: String) -> Self {
+        FrozenIter {
+            trie,
+            key,
+            stack: Vec::new(),
+            start: Some(start),
+        }
+    }
+}
+
+impl<'a, TValue> Iterator for FrozenIter<'a, TValue> {
+    type Item = (String, &'a TValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(start) = self.start.take() {
+            let node = self.trie.node(start);
+            self.stack.push(node.children());
+            if let Some(value) = &node.value {
+                return Some((self.key.clone(), value));
+            }
+        }
+
+        loop {
+            let children = self.stack.last_mut()?;
+            match children.next() {
+                Some(id) => {
+                    let node = self.trie.node(id);
+                    self.key.push(self.trie.labels[id as usize]);
+                    self.stack.push(node.children());
+                    if let Some(value) = &node.value {
+                        return Some((self.key.clone(), value));
+                    }
+                }
+                None => {
+                    self.stack.pop();
+                    // Leaving the start node's children must keep the prefix.
+                    if !self.stack.is_empty() {
+                        self.key.pop();
+                    }
+                }
+            }
+        }
+    }
+}
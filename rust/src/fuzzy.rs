@@ -0,0 +1,91 @@
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+impl<TValue, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Returns every stored key within `max_dist` Levenshtein edits (single
+    /// character insertions, deletions or substitutions) of `query`, along with
+    /// its distance and value. Results are sorted by distance, then by key.
+    ///
+    /// The search keeps one row of the edit-distance table per trie level, so
+    /// each shared prefix is only computed once, and abandons any branch where
+    /// every entry of the row already exceeds `max_dist`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("hello", 1);
+    /// trie.insert("help", 2);
+    /// trie.insert("world", 3);
+    /// let matches = trie.fuzzy_search("helo", 1);
+    /// assert_eq!(matches, vec![("hello".to_string(), 1, &1), ("help".to_string(), 1, &2)]);
+    /// ```
+    pub fn fuzzy_search(&self, query: &str, max_dist: usize) -> Vec<(String, usize, &TValue)> {
+        let query: Vec<char> = query.chars().collect();
+        // Distance from the empty key to each prefix of the query.
+        let first_row: Vec<usize> = (0..=query.len()).collect();
+
+        let mut results = Vec::new();
+        if let Some(value) = self.root().get_value() {
+            if query.len() <= max_dist {
+                results.push((String::new(), query.len(), value));
+            }
+        }
+
+        let mut key = String::new();
+        for (&c, child) in self.root().children_iter() {
+            fuzzy_recursive(
+                child,
+                c,
+                &query,
+                &first_row,
+                max_dist,
+                &mut key,
+                &mut results,
+            );
+        }
+
+        results.sort_unstable_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        results
+    }
+}
+
+/// Recursive helper for `fuzzy_search`. Extends the edit-distance table by the
+/// edge character `c` leading into `node`.
+fn fuzzy_recursive<'a, TValue, H>(
+    node: &'a TrieNode<TValue, H>,
+    c: char,
+    query: &[char],
+    previous_row: &[usize],
+    max_dist: usize,
+    key: &mut String,
+    results: &mut Vec<(String, usize, &'a TValue)>,
+) {
+    let mut row = Vec::with_capacity(previous_row.len());
+    row.push(previous_row[0] + 1);
+    for j in 1..previous_row.len() {
+        let substitution = previous_row[j - 1] + usize::from(query[j - 1] != c);
+        let deletion = previous_row[j] + 1;
+        let insertion = row[j - 1] + 1;
+        row.push(substitution.min(deletion).min(insertion));
+    }
+
+    key.push(c);
+    if let Some(value) = node.get_value() {
+        let distance = row[query.len()];
+        if distance <= max_dist {
+            results.push((key.clone(), distance, value));
+        }
+    }
+
+    // Row entries never decrease further down the trie, so once all of them are
+    // over budget no descendant can match.
+    if row.iter().any(|&distance| distance <= max_dist) {
+        for (&next, child) in node.children_iter() {
+            fuzzy_recursive(child, next, query, &row, max_dist, key, results);
+        }
+    }
+    key.pop();
+}
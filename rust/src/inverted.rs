@@ -0,0 +1,37 @@
+use crate::trie::Trie;
+use alloc::vec::Vec;
+
+impl Trie<Vec<usize>> {
+    /// Builds an inverted index over `docs`, given as `(id, text)` pairs: each
+    /// whitespace-separated token of a text maps to the positions in `docs` of
+    /// the documents containing it. Each list is in ascending order and names
+    /// a document once, however often the token appears in it. The ids are
+    /// not stored; `docs[i].0` recovers the id of document `i`.
+    ///
+    /// Tokens are taken exactly as written, so callers wanting
+    /// case-insensitive or punctuation-free search should normalize the texts
+    /// first. As with any Trie, `prefix_iter` then finds every token starting
+    /// with a prefix along with its documents.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let docs = [("a.txt", "hello world"), ("b.txt", "hello hello trie")];
+    /// let index = Trie::build_inverted_index(&docs);
+    /// assert_eq!(index.get("hello"), Some(&vec![0, 1]));
+    /// assert_eq!(index.get("trie"), Some(&vec![1]));
+    /// ```
+    pub fn build_inverted_index(docs: &[(&str, &str)]) -> Trie<Vec<usize>> {
+        let mut index: Trie<Vec<usize>> = Trie::new();
+        for (position, (_, text)) in docs.iter().enumerate() {
+            for token in text.split_whitespace() {
+                let postings = index.entry(token).or_default();
+                // Documents are visited in order, so a repeat of the token in
+                // this document can only be the last entry.
+                if postings.last() != Some(&position) {
+                    postings.push(position);
+                }
+            }
+        }
+        index
+    }
+}
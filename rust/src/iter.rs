@@ -0,0 +1,645 @@
+use crate::map::DefaultHashBuilder;
+use crate::trie_node::{
+    OrderedChildIntoIter, OrderedChildIter, OrderedChildIterMut, SortedChildIter, TrieNode,
+};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// An iterator over the (key, value) pairs of a Trie in lexicographic order,
+/// created by `Trie::iter`. Call `rev()` on it for reverse order.
+///
+/// This is a `PrefixIter` over the whole Trie, so it walks with an explicit
+/// stack rather than recursion and never overflows the call stack on long keys.
+pub struct TrieIter<'a, TValue, H = DefaultHashBuilder> {
+    inner: PrefixIter<'a, TValue, H>,
+}
+
+impl<'a, TValue, H> TrieIter<'a, TValue, H> {
+    pub(crate) fn new(root: &'a TrieNode<TValue, H>) -> Self {
+        Self {
+            inner: PrefixIter::new(root, String::new()),
+        }
+    }
+}
+
+impl<'a, TValue, H> Iterator for TrieIter<'a, TValue, H> {
+    type Item = (String, &'a TValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<TValue, H> DoubleEndedIterator for TrieIter<'_, TValue, H> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+/// A mutable iterator over the (key, value) pairs of a Trie, created by
+/// `Trie::iter_mut`.
+///
+/// Uses the same explicit-stack depth-first walk as `TrieIter`, following
+/// each node's children in map order, or in `char` order for a Trie built
+/// with sorted children.
+pub struct TrieIterMut<'a, TValue, H = DefaultHashBuilder> {
+    root_value: Option<&'a mut TValue>,
+    stack: Vec<(String, OrderedChildIterMut<'a, TValue, H>)>,
+    sorted: bool,
+}
+
+impl<'a, TValue, H> TrieIterMut<'a, TValue, H> {
+    pub(crate) fn new(root: &'a mut TrieNode<TValue, H>, sorted: bool) -> Self {
+        let (root_value, children) = root.value_and_children_mut_ordered(sorted);
+        Self {
+            root_value,
+            stack: vec![(String::new(), children)],
+            sorted,
+        }
+    }
+}
+
+impl<'a, TValue, H> Iterator for TrieIterMut<'a, TValue, H> {
+    type Item = (String, &'a mut TValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.root_value.take() {
+            return Some((String::new(), value));
+        }
+
+        while let Some((prefix, children)) = self.stack.last_mut() {
+            let Some((&c, child)) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let mut key = prefix.clone();
+            key.push(c);
+            // Splitting the child hands out its value and its children as
+            // disjoint borrows, which is what lets us keep both around.
+            let (value, grandchildren) = child.value_and_children_mut_ordered(self.sorted);
+            self.stack.push((key.clone(), grandchildren));
+            if let Some(value) = value {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+/// An iterator over the (key, value) pairs whose keys start with a prefix, in
+/// lexicographic order, created by `Trie::prefix_iter`.
+///
+/// Entries are produced lazily: children are sorted one node at a time as the
+/// walk reaches them, so no more of the Trie is visited than is consumed.
+///
+/// It is double-ended. `next_back` runs a second walk from the largest key
+/// down, started on its first call, and the two walks stop once they meet, so
+/// interleaving `next` and `next_back` yields every entry exactly once.
+pub struct PrefixIter<'a, TValue, H = DefaultHashBuilder> {
+    // The entry of the starting node, yielded first if that node holds a value.
+    pending: Option<(String, &'a TValue)>,
+    stack: Vec<(String, SortedChildIter<'a, TValue, H>)>,
+    // The starting node, kept until the backward walk begins.
+    start: Option<(&'a TrieNode<TValue, H>, String)>,
+    // One frame per node on the backward walk's path: the key, the children
+    // not yet visited from the largest down, and the node's own value, which
+    // comes after all of its descendants in reverse order.
+    back_stack: Vec<(String, SortedChildIter<'a, TValue, H>, Option<&'a TValue>)>,
+    // The last key each end produced, to tell when the walks have met.
+    front_last: Option<String>,
+    back_last: Option<String>,
+}
+
+impl<'a, TValue, H> PrefixIter<'a, TValue, H> {
+    /// Starts a walk at `node`, whose key is `prefix`.
+    pub(crate) fn new(node: &'a TrieNode<TValue, H>, prefix: String) -> Self {
+        Self {
+            pending: node.get_value().map(|value| (prefix.clone(), value)),
+            stack: vec![(prefix.clone(), node.children_iter_sorted())],
+            start: Some((node, prefix)),
+            back_stack: Vec::new(),
+            front_last: None,
+            back_last: None,
+        }
+    }
+
+    /// An iterator that yields nothing, for prefixes that are not in the Trie.
+    pub(crate) fn empty() -> Self {
+        Self {
+            pending: None,
+            stack: Vec::new(),
+            start: None,
+            back_stack: Vec::new(),
+            front_last: None,
+            back_last: None,
+        }
+    }
+
+    fn next_unchecked(&mut self) -> Option<(String, &'a TValue)> {
+        if let Some(entry) = self.pending.take() {
+            return Some(entry);
+        }
+
+        while let Some((prefix, children)) = self.stack.last_mut() {
+            let Some((&c, child)) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            // A pre-order walk over sorted children visits a key before every
+            // key it is a prefix of, which is exactly lexicographic order.
+            let mut key = prefix.clone();
+            key.push(c);
+            self.stack.push((key.clone(), child.children_iter_sorted()));
+            if let Some(value) = child.get_value() {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    fn next_back_unchecked(&mut self) -> Option<(String, &'a TValue)> {
+        if let Some((node, prefix)) = self.start.take() {
+            self.back_stack
+                .push((prefix, node.children_iter_sorted(), node.get_value()));
+        }
+
+        while let Some((prefix, children, _)) = self.back_stack.last_mut() {
+            if let Some((&c, child)) = children.next_back() {
+                let mut key = prefix.clone();
+                key.push(c);
+                self.back_stack
+                    .push((key, child.children_iter_sorted(), child.get_value()));
+                continue;
+            }
+            // Reverse pre-order: a node comes after everything below it.
+            let (key, _, value) = self.back_stack.pop()?;
+            if let Some(value) = value {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    /// Ends both walks once they have met.
+    fn finish(&mut self) -> Option<(String, &'a TValue)> {
+        self.pending = None;
+        self.stack.clear();
+        self.start = None;
+        self.back_stack.clear();
+        None
+    }
+}
+
+/// Overwrites `slot` with `key`, reusing its buffer.
+fn remember(slot: &mut Option<String>, key: &str) {
+    match slot {
+        Some(last) => {
+            last.clear();
+            last.push_str(key);
+        }
+        None => *slot = Some(key.to_string()),
+    }
+}
+
+impl<'a, TValue, H> Iterator for PrefixIter<'a, TValue, H> {
+    type Item = (String, &'a TValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.next_unchecked()?;
+        if self.back_last.as_ref().is_some_and(|last| key >= *last) {
+            return self.finish();
+        }
+        remember(&mut self.front_last, &key);
+        Some((key, value))
+    }
+}
+
+impl<TValue, H> DoubleEndedIterator for PrefixIter<'_, TValue, H> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.next_back_unchecked()?;
+        if self.front_last.as_ref().is_some_and(|last| key <= *last) {
+            return self.finish();
+        }
+        remember(&mut self.back_last, &key);
+        Some((key, value))
+    }
+}
+
+/// An iterator over the (key, value) pairs with `start <= key < end`, in
+/// lexicographic order, created by `Trie::range_iter`. Without an `end`, as
+/// for `Trie::keys_starting_at_or_after`, it runs to the last key.
+///
+/// The walk skips every branch whose keys all sort before `start`, and stops
+/// outright at the first key that reaches `end`, since every key after it in a
+/// lexicographic walk is larger still.
+pub struct RangeIter<'a, TValue, H = DefaultHashBuilder> {
+    pending: Option<&'a TValue>,
+    stack: Vec<(String, SortedChildIter<'a, TValue, H>)>,
+    start: String,
+    end: Option<String>,
+}
+
+impl<'a, TValue, H> RangeIter<'a, TValue, H> {
+    pub(crate) fn new(root: &'a TrieNode<TValue, H>, start: &str, end: &str) -> Self {
+        if start >= end {
+            return Self {
+                pending: None,
+                stack: Vec::new(),
+                start: String::new(),
+                end: None,
+            };
+        }
+        Self {
+            end: Some(end.to_string()),
+            ..Self::starting_at(root, start)
+        }
+    }
+
+    /// Starts a walk over every key with `start <= key`.
+    pub(crate) fn starting_at(root: &'a TrieNode<TValue, H>, start: &str) -> Self {
+        Self {
+            // The empty key sorts before everything, so it is only in range
+            // when the range starts at the empty key.
+            pending: root.get_value().filter(|_| start.is_empty()),
+            stack: vec![(String::new(), root.children_iter_sorted())],
+            start: start.to_string(),
+            end: None,
+        }
+    }
+}
+
+impl<'a, TValue, H> Iterator for RangeIter<'a, TValue, H> {
+    type Item = (String, &'a TValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.pending.take() {
+            return Some((String::new(), value));
+        }
+
+        while let Some((prefix, children)) = self.stack.last_mut() {
+            let Some((&c, child)) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let mut key = prefix.clone();
+            key.push(c);
+            if self.end.as_deref().is_some_and(|end| key.as_str() >= end) {
+                self.stack.clear();
+                return None;
+            }
+            // A key below `start` that is not a prefix of it differs from
+            // `start` at a smaller character, and so does every extension.
+            if key < self.start && !self.start.starts_with(&key) {
+                continue;
+            }
+
+            self.stack.push((key.clone(), child.children_iter_sorted()));
+            if let Some(value) = child.get_value() {
+                if key >= self.start {
+                    return Some((key, value));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A lazy iterator over the words that start with a prefix, in lexicographic
+/// order, created by `Trie::auto_complete_iter`.
+///
+/// This is the iterator form of `Trie::auto_complete`: instead of fixing
+/// `max_results` up front, take as many completions as are needed and the walk
+/// stops there.
+pub struct AutoCompleteIter<'a, TValue, H = DefaultHashBuilder> {
+    inner: PrefixIter<'a, TValue, H>,
+}
+
+impl<'a, TValue, H> AutoCompleteIter<'a, TValue, H> {
+    pub(crate) fn new(inner: PrefixIter<'a, TValue, H>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<TValue, H> Iterator for AutoCompleteIter<'_, TValue, H> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+}
+
+impl<TValue, H> DoubleEndedIterator for AutoCompleteIter<'_, TValue, H> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, _)| key)
+    }
+}
+
+/// An iterator over the keys of a Trie in lexicographic order, created by
+/// `Trie::keys`.
+///
+/// This is a `PrefixIter` that drops the values, so it is just as lazy.
+pub struct Keys<'a, TValue, H = DefaultHashBuilder> {
+    inner: PrefixIter<'a, TValue, H>,
+}
+
+impl<'a, TValue, H> Keys<'a, TValue, H> {
+    /// Starts a walk at `node`, whose key is `prefix`.
+    pub(crate) fn new(node: &'a TrieNode<TValue, H>, prefix: String) -> Self {
+        Self {
+            inner: PrefixIter::new(node, prefix),
+        }
+    }
+}
+
+impl<TValue, H> Iterator for Keys<'_, TValue, H> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+}
+
+impl<TValue, H> DoubleEndedIterator for Keys<'_, TValue, H> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, _)| key)
+    }
+}
+
+/// An iterator over the values of a Trie, created by `Trie::values`.
+///
+/// Only node references are kept on the stack, so no key strings are built.
+pub struct Values<'a, TValue, H = DefaultHashBuilder> {
+    root_value: Option<&'a TValue>,
+    stack: Vec<OrderedChildIter<'a, TValue, H>>,
+    sorted: bool,
+}
+
+impl<'a, TValue, H> Values<'a, TValue, H> {
+    /// Walks the values under `root` in map order, which is all that counting
+    /// them needs.
+    pub(crate) fn new(root: &'a TrieNode<TValue, H>) -> Self {
+        Self::ordered(root, false)
+    }
+
+    /// Walks the values under `root`, visiting each node's children in `char`
+    /// order if `sorted` is set.
+    pub(crate) fn ordered(root: &'a TrieNode<TValue, H>, sorted: bool) -> Self {
+        Self {
+            root_value: root.get_value(),
+            stack: vec![root.children_iter_ordered(sorted)],
+            sorted,
+        }
+    }
+}
+
+impl<'a, TValue, H> Iterator for Values<'a, TValue, H> {
+    type Item = &'a TValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.root_value.take() {
+            return Some(value);
+        }
+
+        while let Some(children) = self.stack.last_mut() {
+            let Some((_, child)) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            self.stack.push(child.children_iter_ordered(self.sorted));
+            if let Some(value) = child.get_value() {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+/// A mutable iterator over the values of a Trie, created by `Trie::values_mut`.
+pub struct ValuesMut<'a, TValue, H = DefaultHashBuilder> {
+    root_value: Option<&'a mut TValue>,
+    stack: Vec<OrderedChildIterMut<'a, TValue, H>>,
+    sorted: bool,
+}
+
+impl<'a, TValue, H> ValuesMut<'a, TValue, H> {
+    pub(crate) fn new(root: &'a mut TrieNode<TValue, H>, sorted: bool) -> Self {
+        let (root_value, children) = root.value_and_children_mut_ordered(sorted);
+        Self {
+            root_value,
+            stack: vec![children],
+            sorted,
+        }
+    }
+}
+
+impl<'a, TValue, H> Iterator for ValuesMut<'a, TValue, H> {
+    type Item = &'a mut TValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.root_value.take() {
+            return Some(value);
+        }
+
+        while let Some(children) = self.stack.last_mut() {
+            let Some((_, child)) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let (value, grandchildren) = child.value_and_children_mut_ordered(self.sorted);
+            self.stack.push(grandchildren);
+            if let Some(value) = value {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+/// An owning iterator over the (key, value) pairs of a Trie, created by
+/// `Trie::into_iter`.
+///
+/// Nodes are taken apart as the walk reaches them, moving each value out
+/// without cloning it.
+pub struct IntoIter<TValue, H = DefaultHashBuilder> {
+    remaining: usize,
+    // The entry of the starting node, yielded first if that node holds a value.
+    pending: Option<(String, TValue)>,
+    stack: Vec<(String, OrderedChildIntoIter<TValue, H>)>,
+    sorted: bool,
+}
+
+impl<TValue, H> IntoIter<TValue, H> {
+    pub(crate) fn new(root: TrieNode<TValue, H>, word_count: usize, sorted: bool) -> Self {
+        Self::with_prefix(root, String::new(), word_count, sorted)
+    }
+
+    /// Starts a walk at `node`, whose key is `prefix`. `word_count` must equal
+    /// the number of values stored under `node`.
+    pub(crate) fn with_prefix(
+        node: TrieNode<TValue, H>,
+        prefix: String,
+        word_count: usize,
+        sorted: bool,
+    ) -> Self {
+        let (value, children) = node.into_parts_ordered(sorted);
+        Self {
+            remaining: word_count,
+            pending: value.map(|value| (prefix.clone(), value)),
+            stack: vec![(prefix, children)],
+            sorted,
+        }
+    }
+}
+
+impl<TValue, H> Iterator for IntoIter<TValue, H> {
+    type Item = (String, TValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(entry) = self.pending.take() {
+            self.remaining -= 1;
+            return Some(entry);
+        }
+
+        while let Some((prefix, children)) = self.stack.last_mut() {
+            let Some((c, child)) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let mut key = prefix.clone();
+            key.push(c);
+            let (value, grandchildren) = child.into_parts_ordered(self.sorted);
+            self.stack.push((key.clone(), grandchildren));
+            if let Some(value) = value {
+                self.remaining -= 1;
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<TValue, H> ExactSizeIterator for IntoIter<TValue, H> {}
+
+/// A draining iterator over the (key, value) pairs of a Trie, created by
+/// `Trie::drain`.
+///
+/// Each entry is removed from the Trie as it is yielded, so `len` counts down
+/// as the drain goes and the Trie is a valid Trie of the remaining entries
+/// between steps. Dropping the drain removes whatever it has not yielded, so
+/// the Trie is left empty even if the drain is dropped early or a panic
+/// unwinds through the loop consuming it. A leaked drain leaves the entries it
+/// has not yielded in place.
+pub struct TrieDrain<'a, TValue, H = DefaultHashBuilder> {
+    root: &'a mut TrieNode<TValue, H>,
+    word_count: &'a mut usize,
+    sorted: bool,
+    // An empty root to swap in on drop. Making one needs the hasher bounds,
+    // which a `Drop` impl cannot require.
+    empty: Option<TrieNode<TValue, H>>,
+}
+
+impl<'a, TValue, H> TrieDrain<'a, TValue, H> {
+    pub(crate) fn new(
+        root: &'a mut TrieNode<TValue, H>,
+        word_count: &'a mut usize,
+        sorted: bool,
+        empty: TrieNode<TValue, H>,
+    ) -> Self {
+        Self {
+            root,
+            word_count,
+            sorted,
+            empty: Some(empty),
+        }
+    }
+}
+
+impl<TValue, H: BuildHasher + Clone> Iterator for TrieDrain<'_, TValue, H> {
+    type Item = (String, TValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.root.clear_value() {
+            *self.word_count -= 1;
+            return Some((String::new(), value));
+        }
+
+        loop {
+            // Follow first children down to the first node holding a value,
+            // noting the deepest node above it that leads to other keys: the
+            // nodes below that one exist only for the key being removed.
+            let mut path = Vec::new();
+            let mut anchor_depth = 0;
+            let mut node = &*self.root;
+            while path.is_empty() || !node.is_end_of_word() {
+                let first = node.children_iter_ordered(self.sorted).next();
+                let Some((&c, child)) = first else {
+                    break;
+                };
+                if node.child_count() > 1 {
+                    anchor_depth = path.len();
+                }
+                path.push(c);
+                node = child;
+            }
+            if path.is_empty() {
+                return None;
+            }
+
+            let value = if node.has_children() {
+                let mut node = &mut *self.root;
+                for &c in &path {
+                    node = node.get_child_mut(c).expect("path was just walked");
+                }
+                node.clear_value()
+            } else {
+                // Cut the branch off below the anchor and take it apart one
+                // node at a time, so dropping it does not recurse per character.
+                let mut anchor = &mut *self.root;
+                for &c in &path[..anchor_depth] {
+                    anchor = anchor.get_child_mut(c).expect("path was just walked");
+                }
+                let mut detached = anchor
+                    .remove_child(path[anchor_depth])
+                    .expect("path was just walked");
+                for &c in &path[anchor_depth + 1..] {
+                    detached = detached.remove_child(c).expect("path was just walked");
+                }
+                detached.clear_value()
+            };
+            // A leaf without a value ends no key; it has been cut off, so look
+            // again.
+            if let Some(value) = value {
+                *self.word_count -= 1;
+                return Some((path.into_iter().collect(), value));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (*self.word_count, Some(*self.word_count))
+    }
+}
+
+/// Removes the entries the drain did not yield.
+impl<TValue, H> Drop for TrieDrain<'_, TValue, H> {
+    fn drop(&mut self) {
+        if let Some(empty) = self.empty.take() {
+            *self.root = empty;
+            *self.word_count = 0;
+        }
+    }
+}
+
+impl<TValue, H: BuildHasher + Clone> ExactSizeIterator for TrieDrain<'_, TValue, H> {}
@@ -1,79 +1,4566 @@
+//! A fast, memory-efficient Trie (prefix tree) for word lookup and autocomplete.
+//!
+//! [`Trie`] maps string keys to values and answers prefix queries. Completions
+//! can be collected eagerly with [`Trie::auto_complete`], or pulled lazily from
+//! an [`AutoCompleteIter`] returned by [`Trie::auto_complete_iter`]:
+//!
+//! ```Rust
+//! use trie_hard_rs::Trie;
+//!
+//! let mut trie = Trie::new();
+//! trie.insert("apple", 1);
+//! trie.insert("application", 2);
+//! let first: Vec<String> = trie.auto_complete_iter("app").take(1).collect();
+//! assert_eq!(first, vec!["apple"]);
+//! ```
+//!
+//! Specialized variants cover other trade-offs: [`AsciiTrie`], [`ByteTrie`],
+//! [`RadixTrie`], [`ArenaTrie`], [`CaseFoldedTrie`], [`PhoneticTrie`] and the
+//! read-only [`FrozenTrie`] and [`Dawg`]. [`TrieSet`]
+//! holds plain word sets, [`FrequencyTrie`] counts words and [`MultiTrie`]
+//! keeps several values per key.
+//!
+//! The crate is `no_std` when the default `std` feature is turned off, and
+//! then needs only `alloc`. File and reader I/O, [`Dawg`], [`TimedTrie`] and
+//! [`RouterTrie`] are only available with `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod macros;
+
+pub mod aho_corasick;
+pub mod arena;
+pub mod ascii;
+pub mod binary;
+pub mod builder;
+pub mod byte;
+pub mod case_folded;
+#[cfg(feature = "std")]
+pub mod dawg;
+pub mod diff;
+mod dot;
+pub mod entry;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod frequency;
+pub mod frozen;
+mod fuzzy;
+mod inverted;
+pub mod iter;
+mod map;
+pub mod multi;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod phonetic;
+pub mod radix;
+#[cfg(feature = "regex")]
+mod regex;
+#[cfg(feature = "std")]
+pub mod router;
+#[cfg(feature = "serde")]
+mod serde_impl;
+pub mod set;
+#[cfg(feature = "smallvec-children")]
+mod small_map;
+pub mod snapshot;
+pub mod stats;
+pub mod suffix;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod text;
+#[cfg(feature = "std")]
+pub mod timed;
 pub mod trie;
 mod trie_node;
-pub use trie::Trie;
+pub mod tst;
+pub mod view;
+pub mod walk;
+mod wildcard;
+pub use aho_corasick::{AcMatch, AhoCorasickAutomaton};
+pub use arena::ArenaTrie;
+pub use ascii::AsciiTrie;
+pub use binary::{ByteSerialize, DeserializeError};
+pub use builder::TrieBuilder;
+pub use byte::ByteTrie;
+pub use case_folded::CaseFoldedTrie;
+#[cfg(feature = "std")]
+pub use dawg::Dawg;
+pub use diff::TrieDiff;
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use frequency::FrequencyTrie;
+pub use frozen::{FrozenIter, FrozenTrie};
+pub use iter::{
+    AutoCompleteIter, IntoIter, Keys, PrefixIter, RangeIter, TrieDrain, TrieIter, TrieIterMut,
+    Values, ValuesMut,
+};
+pub use map::DefaultHashBuilder;
+pub use multi::MultiTrie;
+#[cfg(feature = "rayon")]
+pub use parallel::{IntoParIter, ParIter};
+pub use phonetic::PhoneticTrie;
+pub use radix::RadixTrie;
+#[cfg(feature = "std")]
+pub use router::RouterTrie;
+pub use set::TrieSet;
+pub use snapshot::TrieSnapshot;
+pub use stats::TrieStats;
+pub use suffix::SuffixTrie;
+#[cfg(feature = "sync")]
+pub use sync::SyncTrie;
+pub use text::{LineError, TryFromTrieError};
+#[cfg(feature = "std")]
+pub use timed::TimedTrie;
+pub use trie::{CompletionResult, PrefixMatchResult, Trie};
+pub use tst::TstTrie;
+pub use view::{Cursor, TrieRef};
+pub use walk::WalkControl;
 #[cfg(test)]
 mod tests {
-    use crate::trie::Trie;
+    use crate::arena::ArenaTrie;
+    use crate::ascii::AsciiTrie;
+    use crate::binary::DeserializeError;
+    use crate::builder::TrieBuilder;
+    use crate::byte::ByteTrie;
+    use crate::case_folded::CaseFoldedTrie;
+    use crate::entry::Entry;
+    use crate::frequency::FrequencyTrie;
+    use crate::multi::MultiTrie;
+    use crate::phonetic::{PhoneticTrie, soundex};
+    use crate::radix::RadixTrie;
+    #[cfg(feature = "std")]
+    use crate::router::RouterTrie;
+    use crate::set::TrieSet;
+    use crate::suffix::SuffixTrie;
+    use crate::text::{LineError, TryFromTrieError};
+    #[cfg(feature = "std")]
+    use crate::timed::TimedTrie;
+    use crate::trie::{PrefixMatchResult, Trie};
+    use crate::tst::TstTrie;
+    use crate::view::Cursor;
+    use crate::walk::WalkControl;
+    use std::collections::{BTreeMap, HashMap};
+    #[cfg(feature = "std")]
+    use std::time::{Duration, Instant};
+    #[test]
+    fn test_new_trie_is_empty() {
+        let trie: Trie<i32> = Trie::new();
+        assert_eq!(trie.get("anything"), None);
+        assert!(!trie.prefix_search("anything"));
+        assert!(trie.auto_complete("", 10).is_empty());
+        assert_eq!(trie.len(), 0);
+        assert!(trie.is_empty());
+    }
+
+    #[test]
+    fn test_default_constructor() {
+        let trie: Trie<i32> = Trie::default();
+        assert_eq!(trie.get("test"), None);
+    }
+
+    #[test]
+    fn test_insert_and_get_single_word() {
+        let mut trie = Trie::new();
+        trie.insert("hello", 42);
+
+        assert_eq!(trie.get("hello"), Some(&42));
+        assert_eq!(trie.get("hell"), None);
+        assert_eq!(trie.get("hello_world"), None);
+        assert_eq!(trie.get(""), None);
+    }
+
+    #[test]
+    fn test_insert_and_get_multiple_words() {
+        let trie = trie! { "cat" => 1, "car" => 2, "card" => 3, "care" => 4, "careful" => 5 };
+
+        assert_eq!(trie.get("cat"), Some(&1));
+        assert_eq!(trie.get("car"), Some(&2));
+        assert_eq!(trie.get("card"), Some(&3));
+        assert_eq!(trie.get("care"), Some(&4));
+        assert_eq!(trie.get("careful"), Some(&5));
+
+        // Test partial matches don't exist
+        assert_eq!(trie.get("ca"), None);
+        assert_eq!(trie.get("careless"), None);
+    }
+
+    #[test]
+    fn test_update_existing_key() {
+        let mut trie = Trie::new();
+        trie.insert("test", 100);
+        assert_eq!(trie.get("test"), Some(&100));
+
+        // Update the same key
+        trie.insert("test", 200);
+        assert_eq!(trie.get("test"), Some(&200));
+    }
+
+    #[test]
+    fn test_get_mut_updates_in_place() {
+        let mut trie = trie! { "apple" => 1, "app" => 10 };
+
+        *trie.get_mut("apple").unwrap() += 1;
+        assert_eq!(trie.get("apple"), Some(&2));
+
+        // Other keys, including the prefix, are untouched
+        assert_eq!(trie.get("app"), Some(&10));
+
+        // Missing keys and non-terminal prefixes yield None
+        assert!(trie.get_mut("ap").is_none());
+        assert!(trie.get_mut("banana").is_none());
+        assert_eq!(trie.len(), 2);
+    }
+
+    #[test]
+    fn test_get_mut_without_clone_values() {
+        // A value type that deliberately does not implement Clone
+        struct NotClone;
+
+        let mut trie: Trie<NotClone> = Trie::new();
+        assert!(trie.get_mut("missing").is_none());
+        assert!(trie.get("missing").is_none());
+        assert!(!trie.delete("missing"));
+        assert!(trie.is_empty());
+    }
+
+    #[test]
+    fn test_building_without_clone_values() {
+        // Every way of filling a Trie takes its values by value
+        struct NotClone(usize);
+
+        let mut trie: Trie<NotClone> = Trie::new();
+        trie.insert("one", NotClone(1));
+        trie.add_word_list(&["two", "three"], |word| NotClone(word.len()));
+        trie.extend([("four", NotClone(4))]);
+        assert_eq!(trie.get("three").map(|value| value.0), Some(5));
+        assert_eq!(trie.pop("one").map(|value| value.0), Some(1));
+
+        let collected: Trie<NotClone> = trie.into_iter().collect();
+        assert_eq!(collected.len(), 3);
+
+        let map = BTreeMap::from([(String::from("five"), NotClone(5))]);
+        let converted = Trie::from(map);
+        assert_eq!(converted.get("five").map(|value| value.0), Some(5));
+    }
+
+    #[test]
+    fn test_entry_counts_frequencies() {
+        let mut trie = Trie::new();
+        for word in ["the", "cat", "the", "car", "the", "cat"] {
+            trie.entry(word)
+                .and_modify(|count| *count += 1)
+                .or_insert(1);
+        }
+
+        assert_eq!(trie.get("the"), Some(&3));
+        assert_eq!(trie.get("cat"), Some(&2));
+        assert_eq!(trie.get("car"), Some(&1));
+        assert_eq!(trie.len(), 3);
+    }
+
+    #[test]
+    fn test_entry_vacant_insert() {
+        let mut trie = Trie::new();
+        trie.insert("car", 1);
+
+        // "ca" exists as a path but not as a word, so the entry is vacant
+        match trie.entry("ca") {
+            Entry::Occupied(_) => panic!("expected a vacant entry"),
+            Entry::Vacant(entry) => *entry.insert(5) += 1,
+        }
+        assert_eq!(trie.get("ca"), Some(&6));
+
+        // A brand new branch is created on insert
+        *trie.entry("dog").or_default() += 7;
+        assert_eq!(trie.get("dog"), Some(&7));
+        assert_eq!(trie.len(), 3);
+    }
+
+    #[test]
+    fn test_entry_occupied_operations() {
+        let mut trie = trie! { "card" => 1, "care" => 2 };
+
+        let Entry::Occupied(mut entry) = trie.entry("card") else {
+            panic!("expected an occupied entry");
+        };
+        assert_eq!(*entry.get(), 1);
+        *entry.get_mut() += 10;
+        assert_eq!(entry.insert(20), 11);
+        assert_eq!(*entry.into_mut(), 20);
+        assert_eq!(trie.get("card"), Some(&20));
+        assert_eq!(trie.get("care"), Some(&2));
+    }
+
+    #[test]
+    fn test_entry_remove_prunes_nodes() {
+        let mut trie = trie! { "car" => 1, "carpet" => 2, "cart" => 3 };
+
+        // Removing a leaf drops the branch that only existed for it
+        if let Entry::Occupied(entry) = trie.entry("carpet") {
+            assert_eq!(entry.remove(), 2);
+        }
+        assert!(!trie.prefix_search("carp"));
+        assert_eq!(trie.get("cart"), Some(&3));
+
+        // Removing a word with children keeps the children reachable
+        if let Entry::Occupied(entry) = trie.entry("car") {
+            assert_eq!(entry.remove(), 1);
+        }
+        assert_eq!(trie.get("car"), None);
+        assert_eq!(trie.get("cart"), Some(&3));
+        assert_eq!(trie.len(), 1);
+
+        if let Entry::Occupied(entry) = trie.entry("cart") {
+            assert_eq!(entry.remove(), 3);
+        }
+        assert!(trie.is_empty());
+        assert!(!trie.prefix_search("c"));
+    }
+
+    #[test]
+    fn test_iter_round_trip() {
+        let mut trie = Trie::new();
+        let words = ["", "a", "ab", "abc", "b", "bcd", "café", "🦀"];
+        for (i, word) in words.iter().enumerate() {
+            trie.insert(word, i);
+        }
+
+        let mut pairs: Vec<(String, usize)> = trie.iter().map(|(k, v)| (k, *v)).collect();
+        assert_eq!(pairs.len(), trie.len());
+
+        // Re-inserting every pair yields a trie with exactly the same contents
+        let mut rebuilt = Trie::new();
+        for (key, value) in &pairs {
+            rebuilt.insert(key, *value);
+        }
+        assert!(rebuilt == trie);
+        pairs.sort();
+
+        let mut expected: Vec<(String, usize)> = words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| (w.to_string(), i))
+            .collect();
+        expected.sort();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn test_iter_empty_trie() {
+        let trie: Trie<i32> = Trie::new();
+        assert_eq!(trie.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_iter_mut_bulk_update() {
+        let mut trie = trie! { "" => 1, "one" => 1, "two" => 2, "twelve" => 12 };
+
+        for (key, value) in trie.iter_mut() {
+            *value *= 10;
+            if key == "two" {
+                *value += 1;
+            }
+        }
+
+        assert_eq!(trie.get(""), Some(&10));
+        assert_eq!(trie.get("one"), Some(&10));
+        assert_eq!(trie.get("two"), Some(&21));
+        assert_eq!(trie.get("twelve"), Some(&120));
+        assert_eq!(trie.get("tw"), None);
+    }
+
+    #[test]
+    fn test_keys_lexicographic_order() {
+        let mut trie = Trie::new();
+        for word in [
+            "banana", "app", "apple", "", "b", "zebra", "apricot", "Apple",
+        ] {
+            trie.insert(word, 0);
+        }
+
+        let keys: Vec<String> = trie.keys().collect();
+        let mut expected = keys.clone();
+        expected.sort();
+        assert_eq!(keys, expected);
+        assert_eq!(
+            keys,
+            vec![
+                "", "Apple", "app", "apple", "apricot", "b", "banana", "zebra"
+            ]
+        );
+
+        // The iterator is lazy and can be stopped early
+        let first_two: Vec<String> = trie.keys().take(2).collect();
+        assert_eq!(first_two, vec!["", "Apple"]);
+    }
+
+    #[test]
+    fn test_sorted_auto_complete_limits() {
+        let mut trie = Trie::new();
+        for word in ["tests", "test", "tester", "testing", "testimony"] {
+            trie.insert(word, 0);
+        }
+
+        assert_eq!(
+            trie.sorted_auto_complete("test", 3),
+            vec!["test", "tester", "testimony"]
+        );
+        assert!(trie.sorted_auto_complete("test", 0).is_empty());
+        assert!(trie.sorted_auto_complete("xyz", 10).is_empty());
+    }
+
+    #[test]
+    fn test_values_sum() {
+        let mut trie = Trie::new();
+        let inserted = [
+            ("", 5),
+            ("a", 1),
+            ("ab", 2),
+            ("abc", 3),
+            ("b", 4),
+            ("bd", 10),
+        ];
+        for (word, value) in inserted {
+            trie.insert(word, value);
+        }
+
+        assert_eq!(trie.values().count(), inserted.len());
+        assert_eq!(
+            trie.values().sum::<i32>(),
+            inserted.iter().map(|(_, v)| v).sum::<i32>()
+        );
+        assert_eq!(trie.values().max(), Some(&10));
+
+        let empty: Trie<i32> = Trie::new();
+        assert_eq!(empty.values().next(), None);
+    }
+
+    #[test]
+    fn test_values_mut_doubles_every_value() {
+        let mut trie = trie! { "" => 1, "one" => 1, "two" => 2, "three" => 3 };
+
+        trie.values_mut().for_each(|v| *v *= 2);
+
+        assert_eq!(trie.get(""), Some(&2));
+        assert_eq!(trie.get("one"), Some(&2));
+        assert_eq!(trie.get("two"), Some(&4));
+        assert_eq!(trie.get("three"), Some(&6));
+        assert_eq!(trie.len(), 4);
+    }
+
+    #[test]
+    fn test_drain_yields_all_entries() {
+        let mut trie = Trie::new();
+        for (i, word) in ["", "a", "ab", "abc", "b", "bcd"].iter().enumerate() {
+            trie.insert(word, i);
+        }
+
+        let mut expected: Vec<(String, usize)> = trie.iter().map(|(k, v)| (k, *v)).collect();
+        let mut drained: Vec<(String, usize)> = trie.drain().collect();
+        expected.sort();
+        drained.sort();
+        assert_eq!(drained, expected);
+
+        assert!(trie.is_empty());
+        assert_eq!(trie.get("abc"), None);
+        assert!(!trie.prefix_search("a"));
+
+        // The trie is still usable afterwards
+        trie.insert("again", 1);
+        assert_eq!(trie.len(), 1);
+    }
+
+    #[test]
+    fn test_drain_counts_down_and_empties_on_drop() {
+        let mut trie = trie! { "one" => 1, "two" => 2, "three" => 3 };
+
+        let mut drain = trie.drain();
+        assert_eq!(drain.len(), 3);
+        assert!(drain.next().is_some());
+        assert_eq!(drain.len(), 2);
+        drop(drain);
+
+        assert!(trie.is_empty());
+        assert_eq!(trie.iter().count(), 0);
+        assert_eq!(trie.get("two"), None);
+    }
+
+    #[test]
+    fn test_drain_empties_trie_after_panic() {
+        let mut trie = trie! { "one" => 1, "two" => 2 };
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            for (_, value) in trie.drain() {
+                if value > 0 {
+                    panic!("consumer failed mid-drain");
+                }
+            }
+        }));
+        assert!(result.is_err());
+        assert!(trie.is_empty());
+        assert!(trie.auto_complete("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_drain_counts_down_in_the_trie() {
+        let mut trie = trie! { "" => 0, "one" => 1, "on" => 2, "two" => 3 };
+
+        // Leaking the drain partway shows the Trie as the drain left it: the
+        // yielded entries are gone and `len` matches what remains.
+        let mut drain = trie.drain();
+        let first = drain.next().unwrap();
+        let second = drain.next().unwrap();
+        assert_eq!(drain.len(), 2);
+        std::mem::forget(drain);
+
+        assert_eq!(trie.len(), 2);
+        assert_eq!(trie.iter().count(), 2);
+        assert_eq!(trie.get(&first.0), None);
+        assert_eq!(trie.get(&second.0), None);
+
+        let rest: Vec<_> = trie.drain().collect();
+        assert_eq!(rest.len(), 2);
+        assert!(trie.is_empty());
+        assert_eq!(trie.node_count(), 1);
+    }
+
+    #[test]
+    fn test_for_loop_over_references() {
+        let mut trie = trie! { "" => 0, "red" => 1, "rest" => 2 };
+
+        let mut seen = Vec::new();
+        for (key, value) in &trie {
+            seen.push((key, *value));
+        }
+        seen.sort();
+        assert_eq!(
+            seen,
+            vec![
+                ("".to_string(), 0),
+                ("red".to_string(), 1),
+                ("rest".to_string(), 2)
+            ]
+        );
+
+        for (_, value) in &mut trie {
+            *value += 100;
+        }
+        assert_eq!(trie.get(""), Some(&100));
+        assert_eq!(trie.get("red"), Some(&101));
+        assert_eq!(trie.get("rest"), Some(&102));
+    }
+
+    #[test]
+    fn test_consuming_into_iter() {
+        let trie = trie! {
+            "" => "root".to_string(),
+            "alpha" => "a".to_string(),
+            "alphabet" => "ab".to_string(),
+            "beta" => "b".to_string(),
+        };
+
+        let iter = trie.into_iter();
+        assert_eq!(iter.len(), 4);
+
+        let mut pairs = Vec::new();
+        for (key, value) in iter {
+            pairs.push((key, value));
+        }
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            vec![
+                ("".to_string(), "root".to_string()),
+                ("alpha".to_string(), "a".to_string()),
+                ("alphabet".to_string(), "ab".to_string()),
+                ("beta".to_string(), "b".to_string()),
+            ]
+        );
+
+        // Dropping a partially consumed iterator releases the remaining nodes
+        let trie = trie! { "one" => vec![1], "two" => vec![2] };
+        let mut iter = trie.into_iter();
+        assert!(iter.next().is_some());
+        assert_eq!(iter.len(), 1);
+    }
+
+    #[test]
+    fn test_collect_into_trie() {
+        let collected: Trie<i32> = vec![("hello", 1), ("world", 2), ("help", 3)]
+            .into_iter()
+            .collect();
+
+        let inserted = trie! { "hello" => 1, "world" => 2, "help" => 3 };
+
+        assert_eq!(collected, inserted);
+
+        // Owned string keys work too
+        let owned: Trie<usize> = vec!["a".to_string(), "bb".to_string()]
+            .into_iter()
+            .map(|k| {
+                let len = k.len();
+                (k, len)
+            })
+            .collect();
+        assert_eq!(owned.get("bb"), Some(&2));
+    }
+
+    #[test]
+    fn test_collect_duplicate_keys_last_write_wins() {
+        let trie: Trie<i32> = [("key", 1), ("other", 5), ("key", 2), ("key", 3)]
+            .into_iter()
+            .collect();
+        assert_eq!(trie.get("key"), Some(&3));
+        assert_eq!(trie.get("other"), Some(&5));
+        assert_eq!(trie.len(), 2);
+    }
+
+    #[test]
+    fn test_collect_word_set() {
+        let words = ["apple", "app", "banana", "app"];
+        let set: Trie<()> = words.iter().collect();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.get("app"), Some(&()));
+        assert_eq!(set.get("ban"), None);
+
+        let owned: Trie<()> = words.iter().map(|w| w.to_uppercase()).collect();
+        assert_eq!(
+            owned.keys().collect::<Vec<_>>(),
+            vec!["APP", "APPLE", "BANANA"]
+        );
+    }
+
+    #[test]
+    fn test_extend_overlapping_keys() {
+        let mut trie = trie! { "alpha" => 1, "beta" => 2 };
+
+        trie.extend(vec![("beta", 20), ("gamma", 3)]);
+        trie.extend([("alpha".to_string(), 10), ("delta".to_string(), 4)]);
+
+        assert_eq!(trie.get("alpha"), Some(&10));
+        assert_eq!(trie.get("beta"), Some(&20));
+        assert_eq!(trie.get("gamma"), Some(&3));
+        assert_eq!(trie.get("delta"), Some(&4));
+        assert_eq!(trie.len(), 4);
+
+        // Extending with duplicates inside one batch keeps the last value
+        trie.extend([("gamma", 30), ("gamma", 300)]);
+        assert_eq!(trie.get("gamma"), Some(&300));
+        assert_eq!(trie.len(), 4);
+    }
+
+    #[test]
+    fn test_extend_word_set() {
+        let mut set: Trie<()> = ["cat"].iter().collect();
+        set.extend(["car", "cat", "cart"]);
+        set.extend(vec!["dog".to_string()]);
+        assert_eq!(
+            set.keys().collect::<Vec<_>>(),
+            vec!["car", "cart", "cat", "dog"]
+        );
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let mut original = trie! { "car" => 1, "card" => 2, "" => 0 };
+
+        let mut copy = original.clone();
+        assert_eq!(copy.len(), 3);
+        assert_eq!(copy.get("card"), Some(&2));
+
+        // Inserting into the clone does not affect the original
+        copy.insert("cart", 3);
+        *copy.get_mut("car").unwrap() = 100;
+        assert_eq!(original.get("cart"), None);
+        assert_eq!(original.get("car"), Some(&1));
+        assert_eq!(original.len(), 3);
+
+        // Deleting from the original does not affect the clone
+        assert!(original.delete("card"));
+        assert!(!original.prefix_search("card"));
+        assert_eq!(copy.get("card"), Some(&2));
+        assert_eq!(copy.len(), 4);
+    }
+
+    #[test]
+    fn test_equality_ignores_insertion_order() {
+        let a: Trie<i32> = [("", 0), ("car", 1), ("card", 2), ("dog", 3)]
+            .into_iter()
+            .collect();
+        let b: Trie<i32> = [("dog", 3), ("card", 2), ("", 0), ("car", 1)]
+            .into_iter()
+            .collect();
+        assert!(a == b);
+        assert!(a == a.clone());
+        assert!(Trie::<i32>::new() == Trie::new());
+    }
+
+    #[test]
+    fn test_inequality_cases() {
+        let base: Trie<i32> = [("car", 1), ("card", 2)].into_iter().collect();
+
+        // Differing by one key
+        let other_key: Trie<i32> = [("car", 1), ("care", 2)].into_iter().collect();
+        assert!(base != other_key);
+
+        // Same keys, different values
+        let other_value: Trie<i32> = [("car", 1), ("card", 3)].into_iter().collect();
+        assert!(base != other_value);
+
+        // Strict subset in both directions
+        let subset: Trie<i32> = [("car", 1)].into_iter().collect();
+        assert!(base != subset);
+        assert!(subset != base);
+
+        // A key that only exists as an intermediate node is not a match
+        let prefix_only: Trie<i32> = [("ca", 1), ("card", 2)].into_iter().collect();
+        assert!(base != prefix_only);
+    }
+
+    #[test]
+    fn test_debug_lists_sorted_entries() {
+        let trie: Trie<i32> = [("car", 2), ("apple", 1), ("cart", 3)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            format!("{trie:?}"),
+            r#"Trie { "apple" => 1, "car" => 2, "cart" => 3 }"#
+        );
+
+        let empty: Trie<i32> = Trie::new();
+        assert_eq!(format!("{empty:?}"), "Trie {}");
+
+        // Now that Trie is Debug, whole tries can be compared with assert_eq!
+        assert_eq!(trie.clone(), trie);
+    }
+
+    #[test]
+    fn test_display_tree_structure() {
+        let trie: Trie<i32> = [("", 0), ("car", 1), ("cat", 2), ("do", 3)]
+            .into_iter()
+            .collect();
+        let expected = "\
+(root) = 0
+├── c
+│   └── a
+│       ├── r = 1
+│       └── t = 2
+└── d
+    └── o = 3
+";
+        assert_eq!(trie.display_tree(), expected);
+        assert_eq!(Trie::<i32>::new().display_tree(), "(root)\n");
+    }
+
+    #[test]
+    fn test_index_operator() {
+        let trie: Trie<i32> = [("", 0), ("apple", 1), ("app", 2)].into_iter().collect();
+        assert_eq!(trie["apple"], 1);
+        assert_eq!(trie["app"], 2);
+        assert_eq!(trie[""], 0);
+        assert_eq!(trie.get_or_panic("apple", "apple is always present"), &1);
+    }
+
+    #[test]
+    #[should_panic(expected = "key 'ap' not found in Trie")]
+    fn test_index_missing_key_panics() {
+        let trie: Trie<i32> = [("apple", 1)].into_iter().collect();
+        let _ = trie["ap"];
+    }
+
+    #[test]
+    fn test_index_panic_message_format() {
+        let trie: Trie<i32> = Trie::new();
+        let panic = std::panic::catch_unwind(|| trie["foo"]).unwrap_err();
+        assert_eq!(
+            panic.downcast_ref::<String>().map(String::as_str),
+            Some("key 'foo' not found in Trie")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "config is missing 'timeout'")]
+    fn test_get_or_panic_custom_message() {
+        let trie: Trie<i32> = Trie::new();
+        trie.get_or_panic("timeout", "config is missing 'timeout'");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hashmap_round_trip() {
+        let mut map = HashMap::new();
+        map.insert("".to_string(), 0);
+        map.insert("car".to_string(), 1);
+        map.insert("card".to_string(), 2);
+        map.insert("dog".to_string(), 3);
+
+        let trie = Trie::from(map.clone());
+        assert_eq!(trie.len(), 4);
+        assert_eq!(trie.get("card"), Some(&2));
+        assert!(trie.prefix_search("ca"));
+
+        assert_eq!(trie.into_hashmap(), map);
+    }
+
+    #[test]
+    fn test_btreemap_conversions() {
+        let map: BTreeMap<String, i32> = [("b", 2), ("a", 1), ("ab", 3)]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+        let trie: Trie<i32> = map.clone().into();
+        assert_eq!(trie.len(), 3);
+
+        let sorted = trie.into_btreemap();
+        assert_eq!(sorted, map);
+        assert_eq!(
+            sorted.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("a".to_string(), 1),
+                ("ab".to_string(), 3),
+                ("b".to_string(), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_with_prefix_matches_auto_complete() {
+        let mut trie = Trie::new();
+        let words = [
+            "", "a", "ab", "abc", "abd", "abde", "b", "bc", "bcd", "car", "card", "care", "café",
+        ];
+        for word in words {
+            trie.insert(word, 0);
+        }
+
+        for prefix in ["", "a", "ab", "abd", "b", "ca", "car", "caf", "x", "abcz"] {
+            assert_eq!(
+                trie.count_with_prefix(prefix),
+                trie.auto_complete(prefix, usize::MAX).len(),
+                "prefix {prefix:?}"
+            );
+        }
+        assert_eq!(trie.count_with_prefix(""), trie.len());
+        assert_eq!(trie.count_with_prefix("nothing"), 0);
+        assert_eq!(Trie::<i32>::new().count_with_prefix(""), 0);
+    }
+
+    #[test]
+    fn test_delete_prefix_removes_subtrie() {
+        let mut trie: Trie<i32> = [
+            ("app", 1),
+            ("apple", 2),
+            ("application", 3),
+            ("apt", 4),
+            ("banana", 5),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(trie.delete_prefix("appl"), 2);
+        assert!(!trie.prefix_search("appl"));
+        assert_eq!(trie.get("app"), Some(&1));
+        assert_eq!(trie.get("apt"), Some(&4));
+        assert_eq!(trie.len(), 3);
+
+        // The prefix itself is removed when it is a stored word
+        assert_eq!(trie.delete_prefix("app"), 1);
+        assert!(!trie.prefix_search("app"));
+        assert!(trie.prefix_search("ap"));
+        assert_eq!(trie.len(), 2);
+
+        // Missing prefixes remove nothing
+        assert_eq!(trie.delete_prefix("zzz"), 0);
+        assert_eq!(trie.delete_prefix("aptitude"), 0);
+        assert_eq!(trie.len(), 2);
+    }
+
+    #[test]
+    fn test_delete_prefix_prunes_dangling_path() {
+        let mut trie: Trie<i32> = [("a", 1), ("abcdef", 2)].into_iter().collect();
+        assert_eq!(trie.delete_prefix("abcd"), 1);
+        assert!(!trie.prefix_search("ab"));
+        assert_eq!(trie.get("a"), Some(&1));
+
+        assert_eq!(trie.delete_prefix("a"), 1);
+        assert!(trie.is_empty());
+        assert_eq!(trie.keys().count(), 0);
+        assert!(!trie.prefix_search("a"));
+    }
+
+    #[test]
+    fn test_delete_prefix_empty_clears_trie() {
+        let mut trie: Trie<i32> = [("", 0), ("a", 1), ("b", 2)].into_iter().collect();
+        assert_eq!(trie.delete_prefix(""), 3);
+        assert!(trie.is_empty());
+        assert_eq!(trie.get(""), None);
+        assert!(!trie.prefix_search("a"));
+    }
+
+    #[test]
+    fn test_longest_prefix_match() {
+        let trie: Trie<&str> = [
+            ("10.", "private"),
+            ("10.0.", "lab"),
+            ("10.0.0.", "rack"),
+            ("192.168.", "home"),
+        ]
+        .into_iter()
+        .collect();
+
+        // Several stored keys match; the longest wins
+        assert_eq!(trie.longest_prefix_match("10.0.0.7"), Some((7, &"rack")));
+        assert_eq!(trie.longest_prefix_match("10.0.9.1"), Some((5, &"lab")));
+        assert_eq!(trie.longest_prefix_match("10.1.1.1"), Some((3, &"private")));
+
+        // Exact match
+        assert_eq!(trie.longest_prefix_match("192.168."), Some((8, &"home")));
+
+        // No stored key is a prefix of the query
+        assert_eq!(trie.longest_prefix_match("172.16.0.1"), None);
+        assert_eq!(trie.longest_prefix_match(""), None);
+
+        // The query is shorter than every stored key it leads towards
+        assert_eq!(trie.longest_prefix_match("192.1"), None);
+    }
+
+    #[test]
+    fn test_longest_prefix_match_byte_lengths() {
+        let trie = trie! { "" => 0, "café" => 1 };
+
+        let query = "cafés";
+        let (len, value) = trie.longest_prefix_match(query).unwrap();
+        assert_eq!((len, value), (5, &1));
+        assert_eq!(&query[len..], "s");
+
+        // The empty key matches everything with length zero
+        assert_eq!(trie.longest_prefix_match("tea"), Some((0, &0)));
+    }
+
+    #[test]
+    fn test_all_prefixes_of() {
+        let trie: Trie<i32> = [
+            ("app", 1),
+            ("application", 2),
+            ("applications", 3),
+            ("apple", 4),
+        ]
+        .into_iter()
+        .collect();
+
+        // The query itself is a stored key and is included last
+        assert_eq!(
+            trie.all_prefixes_of("applications"),
+            vec![
+                ("app".to_string(), &1),
+                ("application".to_string(), &2),
+                ("applications".to_string(), &3),
+            ]
+        );
+        assert_eq!(
+            trie.all_prefixes_of("applesauce"),
+            vec![("app".to_string(), &1), ("apple".to_string(), &4)]
+        );
+
+        // No matches
+        assert!(trie.all_prefixes_of("ap").is_empty());
+        assert!(trie.all_prefixes_of("banana").is_empty());
+        assert!(trie.all_prefixes_of("").is_empty());
+    }
+
+    #[test]
+    fn test_all_prefixes_of_with_empty_key() {
+        let trie: Trie<i32> = [("", 0), ("a", 1)].into_iter().collect();
+        assert_eq!(
+            trie.all_prefixes_of("ab"),
+            vec![("".to_string(), &0), ("a".to_string(), &1)]
+        );
+        assert_eq!(trie.all_prefixes_of("z"), vec![("".to_string(), &0)]);
+    }
+
+    #[test]
+    fn test_shortest_unique_prefix() {
+        let trie: Trie<i32> = [
+            ("cat", 1),
+            ("car", 2),
+            ("dog", 3),
+            ("dove", 4),
+            ("zebra", 5),
+        ]
+        .into_iter()
+        .collect();
+
+        // "ca" still prefixes both words, so the full key is needed
+        assert_eq!(trie.shortest_unique_prefix("cat"), Some("cat".to_string()));
+        assert_eq!(trie.shortest_unique_prefix("car"), Some("car".to_string()));
+
+        // "do" is shared with "dove", so one more character is needed
+        assert_eq!(trie.shortest_unique_prefix("dog"), Some("dog".to_string()));
+        assert_eq!(trie.shortest_unique_prefix("dove"), Some("dov".to_string()));
+
+        // A single character is enough when nothing else starts with it
+        assert_eq!(trie.shortest_unique_prefix("zebra"), Some("z".to_string()));
+
+        // Keys that are not stored have no unique prefix
+        assert_eq!(trie.shortest_unique_prefix("do"), None);
+        assert_eq!(trie.shortest_unique_prefix("cow"), None);
+    }
+
+    #[test]
+    fn test_shortest_unique_prefix_edge_cases() {
+        // A key that is a prefix of another can only be identified in full
+        let trie: Trie<i32> = [("car", 1), ("card", 2)].into_iter().collect();
+        assert_eq!(trie.shortest_unique_prefix("car"), Some("car".to_string()));
+        assert_eq!(
+            trie.shortest_unique_prefix("card"),
+            Some("card".to_string())
+        );
+
+        // With a single stored key, nothing needs to be typed at all
+        let single: Trie<i32> = [("only", 1)].into_iter().collect();
+        assert_eq!(single.shortest_unique_prefix("only"), Some(String::new()));
+
+        // Unicode keys are cut on character boundaries
+        let unicode: Trie<i32> = [("éa", 1), ("éb", 2), ("ü", 3)].into_iter().collect();
+        assert_eq!(unicode.shortest_unique_prefix("éb"), Some("éb".to_string()));
+        assert_eq!(unicode.shortest_unique_prefix("ü"), Some("ü".to_string()));
+    }
+
+    #[test]
+    fn test_subtrie_strips_prefix() {
+        let trie: Trie<i32> = [("apple", 1), ("application", 2), ("app", 3), ("banana", 4)]
+            .into_iter()
+            .collect();
+
+        let sub = trie.subtrie("app").unwrap();
+        let expected: Trie<i32> = [("le", 1), ("lication", 2), ("", 3)].into_iter().collect();
+        assert_eq!(sub, expected);
+        assert_eq!(sub.len(), 3);
+
+        // A prefix that ends mid-edge still works
+        let sub = trie.subtrie("appl").unwrap();
+        assert_eq!(sub.keys().collect::<Vec<_>>(), vec!["e", "ication"]);
+
+        assert!(trie.subtrie("cherry").is_none());
+        assert!(Trie::<i32>::new().subtrie("").is_none());
+        assert_eq!(trie.subtrie("").unwrap(), trie);
+    }
+
+    #[test]
+    fn test_subtrie_is_independent() {
+        let mut trie: Trie<i32> = [("user.name", 1), ("user.email", 2)].into_iter().collect();
+        let mut sub = trie.subtrie("user.").unwrap();
+
+        sub.insert("phone", 3);
+        *sub.get_mut("name").unwrap() = 10;
+        assert_eq!(trie.get("user.phone"), None);
+        assert_eq!(trie.get("user.name"), Some(&1));
+
+        trie.delete("user.email");
+        assert_eq!(sub.get("email"), Some(&2));
+    }
+
+    #[test]
+    fn test_merge_counts_conflicts() {
+        let mut a: Trie<i32> = [("", 0), ("car", 1), ("card", 2), ("dog", 3)]
+            .into_iter()
+            .collect();
+        let b: Trie<i32> = [
+            ("", 100),
+            ("car", 10),
+            ("care", 20),
+            ("cat", 30),
+            ("eel", 40),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut seen = Vec::new();
+        let conflicts = a.merge(b, |mine, theirs| {
+            seen.push((mine, theirs));
+            mine * 1000 + theirs
+        });
+        seen.sort();
+
+        assert_eq!(conflicts, 2);
+        assert_eq!(seen, vec![(0, 100), (1, 10)]);
+        assert_eq!(a.get(""), Some(&100));
+        assert_eq!(a.get("car"), Some(&1010));
+
+        // Non-conflicting keys from both sides are present
+        assert_eq!(a.get("card"), Some(&2));
+        assert_eq!(a.get("dog"), Some(&3));
+        assert_eq!(a.get("care"), Some(&20));
+        assert_eq!(a.get("cat"), Some(&30));
+        assert_eq!(a.get("eel"), Some(&40));
+        assert_eq!(a.len(), 7);
+    }
+
+    #[test]
+    fn test_merge_variants() {
+        let base: Trie<i32> = [("a", 1), ("b", 5)].into_iter().collect();
+        let other: Trie<i32> = [("b", 3), ("c", 7)].into_iter().collect();
+
+        let mut overwrite = base.clone();
+        assert_eq!(overwrite.merge_overwrite(other.clone()), 1);
+        assert_eq!(overwrite.get("b"), Some(&3));
+
+        let mut keep = base.clone();
+        assert_eq!(keep.merge_keep_self(other.clone()), 1);
+        assert_eq!(keep.get("b"), Some(&5));
+
+        let mut sum = base.clone();
+        assert_eq!(sum.merge_sum(other.clone()), 1);
+        assert_eq!(sum.get("b"), Some(&8));
+
+        let mut max = base.clone();
+        assert_eq!(max.merge_max(other.clone()), 1);
+        assert_eq!(max.get("b"), Some(&5));
+
+        for merged in [overwrite, keep, sum, max] {
+            assert_eq!(merged.len(), 3);
+            assert_eq!(merged.get("a"), Some(&1));
+            assert_eq!(merged.get("c"), Some(&7));
+        }
+
+        // Merging into an empty trie copies everything without conflicts
+        let mut empty = Trie::new();
+        assert_eq!(empty.merge_overwrite(base.clone()), 0);
+        assert_eq!(empty, base);
+    }
+
+    #[test]
+    fn test_retain_by_key_and_value() {
+        let mut trie: Trie<i32> = [("", 0), ("car", 1), ("card", 2), ("care", 3), ("dog", 4)]
+            .into_iter()
+            .collect();
+
+        let mut visited = Vec::new();
+        let removed = trie.retain(|key, value| {
+            visited.push(key.to_string());
+            !key.starts_with("car") || *value == 2
+        });
+        visited.sort();
+
+        assert_eq!(visited, vec!["", "car", "card", "care", "dog"]);
+        assert_eq!(removed, 2);
+        assert_eq!(trie.len(), 3);
+        assert_eq!(trie.keys().collect::<Vec<_>>(), vec!["", "card", "dog"]);
+
+        // "care" was a leaf, so its node is gone; "card" keeps the "car" path alive
+        assert!(!trie.prefix_search("care"));
+        assert!(trie.prefix_search("car"));
+    }
+
+    #[test]
+    fn test_retain_none_or_all() {
+        let mut trie: Trie<i32> = [("a", 1), ("ab", 2), ("abc", 3)].into_iter().collect();
+        let original = trie.clone();
+        assert_eq!(trie.retain(|_, _| true), 0);
+        assert_eq!(trie, original);
+
+        assert_eq!(trie.retain(|_, _| false), 3);
+        assert!(trie.is_empty());
+        assert!(!trie.prefix_search("a"));
+    }
+
+    #[test]
+    fn test_retain_prunes_dangling_intermediate_nodes() {
+        let mut trie: Trie<i32> = [("a", 1), ("abcdef", 2), ("abxyz", 3)]
+            .into_iter()
+            .collect();
+        assert_eq!(trie.retain(|key, _| key.len() == 1), 2);
+        assert_eq!(trie.len(), 1);
+        assert!(!trie.prefix_search("ab"));
+        assert_eq!(trie.get("a"), Some(&1));
+    }
+
+    #[test]
+    fn test_pop_returns_value() {
+        let mut trie = trie! {
+            "car" => "vehicle".to_string(),
+            "card" => "paper".to_string(),
+            "cart" => "wagon".to_string(),
+        };
+
+        assert_eq!(trie.pop("card"), Some("paper".to_string()));
+        assert_eq!(trie.get("card"), None);
+        assert!(!trie.prefix_search("card"));
+        assert_eq!(trie.len(), 2);
+
+        // Popping a word with children keeps the children
+        assert_eq!(trie.pop("car"), Some("vehicle".to_string()));
+        assert_eq!(trie.get("cart"), Some(&"wagon".to_string()));
+        assert_eq!(trie.len(), 1);
+
+        // Missing keys and bare prefixes leave the trie untouched
+        assert_eq!(trie.pop("car"), None);
+        assert_eq!(trie.pop("ca"), None);
+        assert_eq!(trie.pop(""), None);
+        assert_eq!(trie.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_returns_previous_value() {
+        let mut trie = Trie::new();
+        assert_eq!(trie.insert("key", "first".to_string()), None);
+        assert_eq!(
+            trie.insert("key", "second".to_string()),
+            Some("first".to_string())
+        );
+        assert_eq!(trie.get("key"), Some(&"second".to_string()));
+
+        // A key that only exists as a prefix path is a fresh insert
+        assert_eq!(trie.insert("ke", "prefix".to_string()), None);
+        assert_eq!(trie.len(), 2);
+    }
+
+    #[test]
+    fn test_try_insert() {
+        let mut trie = Trie::new();
+        trie.insert("car", 1);
+
+        // Success hands back a live reference to the new value
+        let value = trie.try_insert("card", 2).unwrap();
+        *value += 40;
+        assert_eq!(trie.get("card"), Some(&42));
+        assert_eq!(trie.len(), 2);
+
+        // A prefix path that is not yet a word can be claimed
+        assert_eq!(trie.try_insert("ca", 3), Ok(&mut 3));
+        assert_eq!(trie.len(), 3);
+    }
+
+    #[test]
+    fn test_try_insert_existing_key_leaves_trie_unmodified() {
+        let mut trie: Trie<String> = [("dup", "original".to_string()), ("dupe", "x".to_string())]
+            .into_iter()
+            .collect();
+        let before = trie.clone();
+
+        assert_eq!(
+            trie.try_insert("dup", "replacement".to_string()),
+            Err("replacement".to_string())
+        );
+        assert_eq!(trie, before);
+        assert_eq!(trie.len(), 2);
+        assert_eq!(trie.get("dup"), Some(&"original".to_string()));
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut trie = Trie::new();
+        trie.insert("car", 1);
+        trie.insert("card", 2);
+
+        assert_eq!(trie.replace("car", 10), Some(1));
+        assert_eq!(trie.get("car"), Some(&10));
+        assert_eq!(trie.replace("car", 11), Some(10));
+        assert_eq!(trie.len(), 2);
+    }
+
+    #[test]
+    fn test_replace_absent_key_leaves_trie_unmodified() {
+        let mut trie: Trie<i32> = [("car", 1), ("card", 2)].into_iter().collect();
+        let before = trie.clone();
+        let nodes = trie.node_count();
+
+        // Neither a missing key, a path that is only a prefix, nor the
+        // unstored empty key may be added
+        assert_eq!(trie.replace("cart", 3), None);
+        assert_eq!(trie.replace("ca", 4), None);
+        assert_eq!(trie.replace("", 5), None);
+        assert_eq!(trie, before);
+        assert_eq!(trie.node_count(), nodes);
+        assert_eq!(trie.len(), 2);
+    }
+
+    #[test]
+    fn test_batch_get_matches_get() {
+        let mut trie: Trie<usize> = ["ex.com/a", "ex.com/a/b", "ex.com/ab", "ex.com/b", "ça", "ç"]
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (*key, i))
+            .collect();
+        trie.insert("", 99);
+
+        // Unsorted, with repeats, missing keys, strict prefixes of stored keys
+        // and keys that run past the end of a stored path.
+        let keys = [
+            "ex.com/b",
+            "ex.com/a/b",
+            "",
+            "ex.com/a/bc",
+            "ex.com/",
+            "ex.com/a",
+            "ç",
+            "ex.com/a/b",
+            "ex.org",
+            "ex.com/ab",
+            "ça",
+            "çb",
+        ];
+        let expected: Vec<Option<usize>> = keys.iter().map(|key| trie.get(key).copied()).collect();
+        assert_eq!(trie.batch_get(&keys), expected);
+        assert!(trie.batch_get(&[]).is_empty());
+        assert_eq!(Trie::<u8>::new().batch_get(&["", "a"]), vec![None, None]);
+    }
+
+    #[test]
+    fn test_first_and_last_key_match_sorted_keys() {
+        let key_sets: [&[&str]; 5] = [
+            &["banana", "apple", "cherry", "app", "applesauce"],
+            &["", "a", "ab"],
+            &["zeta", "zet", "alpha", "alp"],
+            &["ça", "cz", "🦀", "c"],
+            &[""],
+        ];
+        for keys in key_sets {
+            let trie: Trie<()> = keys.iter().map(|key| (*key, ())).collect();
+            let sorted: Vec<String> = trie.keys().collect();
+            assert_eq!(trie.first_key().as_ref(), sorted.first(), "{keys:?}");
+            assert_eq!(trie.last_key().as_ref(), sorted.last(), "{keys:?}");
+        }
+
+        let empty = Trie::<()>::new();
+        assert_eq!(empty.first_key(), None);
+        assert_eq!(empty.last_key(), None);
+        assert_eq!(empty.keys_starting_at_or_after("").count(), 0);
+    }
+
+    #[test]
+    fn test_keys_starting_at_or_after() {
+        let trie: Trie<()> = ["", "app", "apple", "apply", "apt", "banana", "band", "🦀"]
+            .iter()
+            .map(|key| (*key, ()))
+            .collect();
+        for seek in [
+            "", "a", "app", "appl", "applz", "b", "bananas", "c", "🦀", "🦀🦀",
+        ] {
+            let expected: Vec<String> = trie.keys().filter(|key| key.as_str() >= seek).collect();
+            assert_eq!(
+                trie.keys_starting_at_or_after(seek).collect::<Vec<_>>(),
+                expected,
+                "{seek}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rank_and_select_agree_with_sorted_keys() {
+        let trie: Trie<()> = [
+            "", "app", "apple", "apply", "apt", "banana", "band", "ça", "🦀",
+        ]
+        .iter()
+        .map(|key| (*key, ()))
+        .collect();
+        let sorted: Vec<String> = trie.keys().collect();
+
+        let first = trie.first_key().unwrap();
+        assert_eq!(trie.rank(&first), 0);
+        for (i, key) in sorted.iter().enumerate() {
+            assert_eq!(trie.rank(key), i, "{key}");
+            assert_eq!(trie.select(trie.rank(key)).as_ref(), Some(key));
+        }
+        assert_eq!(trie.select(sorted.len()), None);
+
+        // Keys that are not stored rank where they would be inserted.
+        for missing in ["a", "appl", "applz", "apz", "b", "bananas", "zzz", "🦀🦀"] {
+            let expected = sorted.iter().filter(|key| key.as_str() < missing).count();
+            assert_eq!(trie.rank(missing), expected, "{missing}");
+        }
+
+        let empty = Trie::<()>::new();
+        assert_eq!(empty.rank("anything"), 0);
+        assert_eq!(empty.select(0), None);
+    }
+
+    #[test]
+    fn test_stats_agree_with_individual_statistics() {
+        let mut trie: Trie<usize> = ["a", "app", "apple", "apply", "banana", "band", "ça", "🦀🦀"]
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (*key, i))
+            .collect();
+        trie.insert("", 99);
+        let stats = trie.stats();
+
+        assert_eq!(stats.word_count, trie.len());
+        assert_eq!(stats.node_count, trie.node_count());
+        assert_eq!(stats.min_key_length, trie.min_key_length());
+        assert_eq!(stats.max_key_length, trie.max_key_length());
+        assert_eq!(stats.avg_key_length, trie.avg_key_length());
+        assert!(stats.min_key_length as f64 <= stats.avg_key_length);
+        assert!(stats.avg_key_length <= stats.max_key_length as f64);
+        assert_eq!(
+            stats.total_chars,
+            trie.keys().map(|key| key.chars().count()).sum::<usize>()
+        );
+        // The root branches into 'a', 'b', 'ç' and '🦀'.
+        assert_eq!(stats.branching_factor_max, 4);
+        assert!(stats.branching_factor_avg >= 1.0);
+        assert_eq!(
+            stats.estimated_heap_bytes,
+            trie.memory_estimate() - std::mem::size_of::<Trie<usize>>()
+        );
+
+        let table = stats.to_string();
+        assert_eq!(table.lines().count(), 9);
+        assert!(table.starts_with("words"));
+        assert!(table.lines().next().unwrap().ends_with(" 9"));
+
+        let empty = Trie::<u8>::new().stats();
+        assert_eq!((empty.word_count, empty.node_count), (0, 1));
+        assert_eq!((empty.min_key_length, empty.max_key_length), (0, 0));
+        assert_eq!(
+            (empty.avg_key_length, empty.branching_factor_avg),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_map_values_converts_types_and_keeps_keys() {
+        let mut counts: Trie<u32> = [("the", 6), ("cat", 1), ("cats", 3), ("ça", 2)]
+            .into_iter()
+            .collect();
+        counts.insert("", 8);
+        let total: u32 = counts.values().sum();
+
+        let frequencies: Trie<f64> = counts.map_values(|_, count| *count as f64 / total as f64);
+        assert!(frequencies.keys().eq(counts.keys()));
+        assert_eq!(frequencies.get("the"), Some(&0.3));
+        assert_eq!(frequencies.get(""), Some(&0.4));
+        let labelled = counts.map_values(|key, count| format!("{key}:{count}"));
+        assert_eq!(labelled.get("cats").map(String::as_str), Some("cats:3"));
+
+        // Values that are not Clone are moved, not copied.
+        struct Token(u32);
+        let tokens: Trie<Token> = counts.map_values(|_, count| Token(*count));
+        let lengths = tokens.map_values_into(|key, token| key.len() as u32 + token.0);
+        assert_eq!(lengths.len(), counts.len());
+        assert!(lengths.keys().eq(counts.keys()));
+        assert_eq!(lengths.get("cats"), Some(&7));
+
+        let common = counts.filter_map_values(|_, count| (*count >= 3).then_some(*count > 5));
+        assert!(common.len() < counts.len());
+        assert_eq!(common.keys().collect::<Vec<_>>(), vec!["", "cats", "the"]);
+        assert_eq!(common.get("the"), Some(&true));
+        assert_eq!(common.get("cats"), Some(&false));
+        assert!(counts.filter_map_values(|_, _| None::<()>).is_empty());
+    }
+
+    #[test]
+    fn test_zip_keeps_only_shared_keys() {
+        let words = ["", "a", "app", "apple", "band", "ça"];
+        let frequencies: Trie<f64> = words.iter().map(|key| (*key, key.len() as f64)).collect();
+        let vectors: Trie<Vec<f32>> = words
+            .iter()
+            .map(|key| (*key, vec![0.5; key.len()]))
+            .collect();
+
+        // Identical key sets zip completely.
+        let full = frequencies.zip(&vectors);
+        assert_eq!(full.len(), words.len());
+        assert!(full.keys().eq(frequencies.keys()));
+        assert_eq!(full.get("app"), Some(&(3.0, vec![0.5; 3])));
+
+        // Disjoint key sets zip to nothing, leaving no stray nodes.
+        let others = trie! { "b" => 1, "apricot" => 2, "zebra" => 3 };
+        let none = frequencies.zip(&others);
+        assert!(none.is_empty());
+        assert_eq!(none.node_count(), 1);
+
+        // A partial overlap keeps just the shared keys, even where one trie
+        // only has a longer or shorter key on the same path.
+        let some = trie! { "app" => 'x', "apples" => 'y', "ban" => 'z', "ça" => 'w' };
+        let partial = frequencies.zip(&some);
+        assert_eq!(partial.keys().collect::<Vec<_>>(), vec!["app", "ça"]);
+        assert_eq!(partial.get("ça"), Some(&(3.0, 'w')));
+        assert_eq!(partial.node_count(), 6);
+    }
+
+    #[test]
+    fn test_zip_very_long_key() {
+        let long_key = "z".repeat(100_000);
+        let mut left = trie! { long_key.as_str() => 1, "zz" => 2 };
+        let mut right = trie! { long_key.as_str() => 'a', "zzz" => 'b' };
+
+        let mut zipped = left.zip(&right);
+        assert_eq!(zipped.len(), 1);
+        assert_eq!(zipped.get(&long_key), Some(&(1, 'a')));
+        assert_eq!(zipped.node_count(), 100_001);
+
+        // Take the chains apart key by key rather than dropping them whole.
+        assert_eq!(zipped.pop(&long_key), Some((1, 'a')));
+        assert!(left.delete(&long_key));
+        assert!(right.delete(&long_key));
+    }
+
+    #[test]
+    fn test_partition_splits_without_modifying() {
+        let mut vocabulary: Trie<u32> = [
+            ("a", 900),
+            ("an", 400),
+            ("ant", 2),
+            ("zebra", 1),
+            ("ça", 50),
+        ]
+        .into_iter()
+        .collect();
+        vocabulary.insert("", 7);
+        let before = vocabulary.clone();
+
+        let (common, rare) = vocabulary.partition(|_, count| *count >= 50);
+        assert_eq!(vocabulary, before);
+        assert_eq!(common.len() + rare.len(), vocabulary.len());
+        assert_eq!(common.keys().collect::<Vec<_>>(), vec!["a", "an", "ça"]);
+        assert_eq!(rare.keys().collect::<Vec<_>>(), vec!["", "ant", "zebra"]);
+        assert_eq!(common.intersection(&rare).len(), 0);
+        assert_eq!(common.union(&rare), vocabulary);
+
+        // The predicate sees each key as well as its value.
+        let (short, long) = vocabulary.partition(|key, _| key.chars().count() <= 2);
+        assert_eq!((short.len(), long.len()), (4, 2));
+
+        let (all, none) = vocabulary.partition(|_, _| true);
+        assert_eq!(all, vocabulary);
+        assert!(none.is_empty());
+        assert_eq!(none.node_count(), 1);
+    }
+
+    #[test]
+    fn test_fuzzy_search_exact_only() {
+        let trie: Trie<i32> = [("hello", 1), ("help", 2), ("hell", 3)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            trie.fuzzy_search("hello", 0),
+            vec![("hello".to_string(), 0, &1)]
+        );
+        assert!(trie.fuzzy_search("helo", 0).is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_search_single_edit() {
+        let trie: Trie<i32> = [
+            ("cat", 1),
+            ("cart", 2),
+            ("at", 3),
+            ("cut", 4),
+            ("act", 5),
+            ("dog", 6),
+        ]
+        .into_iter()
+        .collect();
+
+        // Substitution (cut), insertion (cart) and deletion (at) are one edit each
+        assert_eq!(
+            trie.fuzzy_search("cat", 1),
+            vec![
+                ("cat".to_string(), 0, &1),
+                ("at".to_string(), 1, &3),
+                ("cart".to_string(), 1, &2),
+                ("cut".to_string(), 1, &4),
+            ]
+        );
+
+        // Plain Levenshtein counts a transposition as two edits
+        let two = trie.fuzzy_search("cat", 2);
+        assert!(two.contains(&("act".to_string(), 2, &5)));
+        assert!(!two.iter().any(|(key, _, _)| key == "dog"));
+    }
+
+    #[test]
+    fn test_fuzzy_search_edge_cases() {
+        let empty: Trie<i32> = Trie::new();
+        assert!(empty.fuzzy_search("anything", 3).is_empty());
+
+        let trie: Trie<i32> = [("", 0), ("a", 1), ("ab", 2), ("abc", 3)]
+            .into_iter()
+            .collect();
+
+        // An empty query is within n edits of every key of length n
+        assert_eq!(
+            trie.fuzzy_search("", 1),
+            vec![("".to_string(), 0, &0), ("a".to_string(), 1, &1)]
+        );
+        assert_eq!(trie.fuzzy_search("", 10).len(), 4);
+
+        // Unicode characters count as single edits
+        let unicode: Trie<i32> = [("café", 1)].into_iter().collect();
+        assert_eq!(
+            unicode.fuzzy_search("cafe", 1),
+            vec![("café".to_string(), 1, &1)]
+        );
+    }
+
+    #[test]
+    fn test_wildcard_match_question_mark() {
+        let trie: Trie<i32> = [("hello", 1), ("hallo", 2), ("hllo", 3), ("hellos", 4)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            trie.wildcard_match("h?llo"),
+            vec![("hallo".to_string(), &2), ("hello".to_string(), &1)]
+        );
+        assert_eq!(trie.wildcard_match("hllo"), vec![("hllo".to_string(), &3)]);
+        assert!(trie.wildcard_match("?").is_empty());
+    }
+
+    #[test]
+    fn test_wildcard_match_star() {
+        let trie: Trie<i32> = [
+            ("ho", 1),
+            ("hello", 2),
+            ("hippo", 3),
+            ("help", 4),
+            ("oh", 5),
+        ]
+        .into_iter()
+        .collect();
+
+        let keys: Vec<String> = trie
+            .wildcard_match("h*o")
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec!["hello", "hippo", "ho"]);
+
+        // Several stars that could match the same key report it once
+        let keys: Vec<String> = trie
+            .wildcard_match("*l*")
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec!["hello", "help"]);
+
+        assert_eq!(trie.wildcard_match("*").len(), 5);
+        assert_eq!(trie.wildcard_match("?*p"), vec![("help".to_string(), &4)]);
+    }
+
+    #[test]
+    fn test_wildcard_match_no_matches() {
+        let trie: Trie<i32> = [("hello", 1), ("world", 2)].into_iter().collect();
+        assert!(trie.wildcard_match("x*").is_empty());
+        assert!(trie.wildcard_match("h?").is_empty());
+        assert!(trie.wildcard_match("").is_empty());
+
+        let empty: Trie<i32> = Trie::new();
+        assert!(empty.wildcard_match("*").is_empty());
+    }
+
+    #[test]
+    fn test_case_folded_get_ignores_case() {
+        let mut trie = CaseFoldedTrie::new();
+        assert_eq!(trie.insert("Hello", 1), None);
+
+        assert_eq!(trie.get("hello"), Some(&1));
+        assert_eq!(trie.get("HELLO"), Some(&1));
+        assert_eq!(trie.get("hElLo"), Some(&1));
+        assert_eq!(trie.get("hell"), None);
+
+        // Any casing of an existing key updates it
+        assert_eq!(trie.insert("HELLO", 2), Some(1));
+        assert_eq!(trie.len(), 1);
+        assert_eq!(trie.get("Hello"), Some(&2));
+
+        // Case folding is Unicode-aware
+        trie.insert("ÉCOLE", 3);
+        assert_eq!(trie.get("école"), Some(&3));
+    }
+
+    #[test]
+    fn test_case_folded_auto_complete_lowercases() {
+        let mut trie = CaseFoldedTrie::new();
+        trie.insert("Hello", 1);
+        trie.insert("HELP", 2);
+        trie.insert("World", 3);
+
+        assert_eq!(trie.auto_complete("HE", 10), vec!["hello", "help"]);
+        assert!(trie.prefix_search("wOR"));
+        assert!(!trie.prefix_search("x"));
+    }
+
+    #[test]
+    fn test_case_folded_delete() {
+        let mut trie = CaseFoldedTrie::new();
+        trie.insert("Apple", 1);
+
+        assert!(trie.delete("APPLE"));
+        assert!(!trie.delete("apple"));
+        assert_eq!(trie.get("Apple"), None);
+        assert!(trie.is_empty());
+        assert!(trie.into_inner().is_empty());
+    }
+
+    #[test]
+    fn test_case_folded_default_without_default_values() {
+        // The value type need not implement Default.
+        struct NoDefault(i32);
+        let mut trie: CaseFoldedTrie<NoDefault> = CaseFoldedTrie::default();
+        trie.insert("Key", NoDefault(7));
+        assert_eq!(trie.get("KEY").map(|v| v.0), Some(7));
+    }
+
+    #[test]
+    fn test_prefix_iter_is_sorted() {
+        let trie: Trie<i32> = [
+            ("apple", 1),
+            ("app", 2),
+            ("apricot", 3),
+            ("banana", 4),
+            ("ap", 5),
+        ]
+        .into_iter()
+        .collect();
+
+        let entries: Vec<(String, &i32)> = trie.prefix_iter("ap").collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("ap".to_string(), &5),
+                ("app".to_string(), &2),
+                ("apple".to_string(), &1),
+                ("apricot".to_string(), &3),
+            ]
+        );
+
+        // The empty prefix walks the whole Trie
+        assert_eq!(trie.prefix_iter("").count(), 5);
+    }
+
+    #[test]
+    fn test_prefix_iter_is_lazy() {
+        let trie: Trie<i32> = [("app", 1), ("apple", 2), ("application", 3)]
+            .into_iter()
+            .collect();
+
+        let mut iter = trie.prefix_iter("app");
+        assert_eq!(iter.next(), Some(("app".to_string(), &1)));
+        assert_eq!(iter.next(), Some(("apple".to_string(), &2)));
+        assert_eq!(iter.next(), Some(("application".to_string(), &3)));
+        assert_eq!(iter.next(), None);
+
+        let first_two: Vec<String> = trie.prefix_iter("a").take(2).map(|(k, _)| k).collect();
+        assert_eq!(first_two, vec!["app", "apple"]);
+    }
+
+    #[test]
+    fn test_prefix_iter_missing_prefix() {
+        let trie: Trie<i32> = [("apple", 1)].into_iter().collect();
+        assert_eq!(trie.prefix_iter("b").next(), None);
+        assert_eq!(trie.prefix_iter("apples").next(), None);
+
+        let empty: Trie<i32> = Trie::new();
+        assert_eq!(empty.prefix_iter("").next(), None);
+    }
+
+    #[test]
+    fn test_auto_complete_is_lexicographic() {
+        let words = [
+            "zebra",
+            "apple",
+            "mango",
+            "applesauce",
+            "apply",
+            "ape",
+            "banana",
+        ];
+        let trie: Trie<usize> = words.iter().enumerate().map(|(i, w)| (*w, i)).collect();
+
+        let mut expected: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        expected.sort();
+
+        assert_eq!(trie.auto_complete("", usize::MAX), expected);
+        assert_eq!(trie.auto_complete("", 3), trie.sorted_auto_complete("", 3));
+        assert_eq!(
+            trie.auto_complete("ap", 3),
+            vec!["ape", "apple", "applesauce"]
+        );
+    }
+
+    #[test]
+    fn test_range_iter_bounds() {
+        let trie: Trie<i32> = [("apple", 1), ("banana", 2), ("cherry", 3), ("date", 4)]
+            .into_iter()
+            .collect();
+        let range =
+            |start, end| -> Vec<String> { trie.range_iter(start, end).map(|(k, _)| k).collect() };
+
+        // No keys in range, including empty and inverted ranges
+        assert!(range("c", "cherry").is_empty());
+        assert!(range("x", "z").is_empty());
+        assert!(range("b", "b").is_empty());
+        assert!(range("d", "a").is_empty());
+
+        // Exactly one key; the end bound is exclusive
+        assert_eq!(range("banana", "cherry"), vec!["banana"]);
+        assert_eq!(
+            trie.range_iter("b", "c").next(),
+            Some(("banana".to_string(), &2))
+        );
+
+        // All keys
+        assert_eq!(range("", "z"), vec!["apple", "banana", "cherry", "date"]);
+        assert_eq!(
+            range("apple", "datf"),
+            vec!["apple", "banana", "cherry", "date"]
+        );
+    }
+
+    #[test]
+    fn test_range_iter_shared_prefixes() {
+        let trie: Trie<i32> = [
+            ("", 0),
+            ("test", 1),
+            ("tester", 2),
+            ("testing", 3),
+            ("tests", 4),
+            ("testy", 5),
+            ("tea", 6),
+        ]
+        .into_iter()
+        .collect();
+        let range =
+            |start, end| -> Vec<String> { trie.range_iter(start, end).map(|(k, _)| k).collect() };
+
+        // A stored key that is a prefix of `start` sorts before it
+        assert_eq!(range("tester", "testy"), vec!["tester", "testing", "tests"]);
+        assert_eq!(range("testa", "testj"), vec!["tester", "testing"]);
+        assert_eq!(range("test", "test\u{0}"), vec!["test"]);
+
+        // The empty key is only included when the range starts at ""
+        assert_eq!(range("", "te"), vec![""]);
+        assert_eq!(range("", "test").len(), 2);
+        assert_eq!(range("t", "u").len(), 6);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json_round_trip() {
+        let trie: Trie<i32> = [("apple", 1), ("app", 2), ("banana", 3), ("", 4)]
+            .into_iter()
+            .collect();
+
+        let json = serde_json::to_string(&trie).unwrap();
+        assert_eq!(json, r#"{"":4,"app":2,"apple":1,"banana":3}"#);
+
+        let decoded: Trie<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, trie);
+        assert_eq!(decoded.len(), 4);
+
+        let empty: Trie<i32> = serde_json::from_str("{}").unwrap();
+        assert!(empty.is_empty());
+        assert!(serde_json::from_str::<Trie<i32>>("[1, 2]").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_bincode_round_trip() {
+        let trie: Trie<String> = ["hello", "help", "world", "café"]
+            .into_iter()
+            .map(|word| (word, word.to_uppercase()))
+            .collect();
+
+        let bytes = bincode::serialize(&trie).unwrap();
+        let decoded: Trie<String> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, trie);
+        assert_eq!(decoded.get("café"), Some(&"CAFÉ".to_string()));
+    }
+
+    #[test]
+    fn test_to_bytes_round_trip_large() {
+        let trie: Trie<u32> = (0..10_000u32)
+            .map(|i| (format!("word{i:05}_{}", i % 7), i))
+            .collect();
+
+        let bytes = trie.to_bytes();
+        let decoded = Trie::<u32>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, trie);
+        assert_eq!(decoded.len(), 10_000);
+
+        // Equal tries encode identically regardless of insertion order
+        let mut entries: Vec<(String, u32)> = trie.iter().map(|(k, v)| (k, *v)).collect();
+        entries.sort();
+        entries.reverse();
+        let reversed: Trie<u32> = entries.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+        assert_eq!(reversed.to_bytes(), bytes);
+
+        // Shared prefixes are written once, so this is far smaller than JSON
+        let json = serde_json::to_vec(&trie.clone().into_btreemap()).unwrap();
+        assert!(bytes.len() * 2 < json.len());
+    }
+
+    #[test]
+    fn test_to_bytes_round_trip_values() {
+        let words: Trie<String> = ["", "hello", "help", "café", "日本", "🦀"]
+            .into_iter()
+            .map(|w| (w, w.repeat(2)))
+            .collect();
+        assert_eq!(Trie::from_bytes(&words.to_bytes()), Ok(words));
+
+        let signed: Trie<i64> = [
+            ("min", i64::MIN),
+            ("max", i64::MAX),
+            ("neg", -1),
+            ("zero", 0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(Trie::from_bytes(&signed.to_bytes()), Ok(signed));
+
+        let set: Trie<()> = ["a", "ab", "abc"].into_iter().collect();
+        assert_eq!(Trie::from_bytes(&set.to_bytes()), Ok(set));
+
+        let nested: Trie<Vec<Option<f64>>> = [("x", vec![Some(1.5), None])].into_iter().collect();
+        assert_eq!(Trie::from_bytes(&nested.to_bytes()), Ok(nested));
+
+        let empty: Trie<i32> = Trie::new();
+        assert_eq!(empty.to_bytes(), b"TRH\x01\x00");
+        assert_eq!(Trie::from_bytes(&empty.to_bytes()), Ok(empty));
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_malformed_data() {
+        let trie: Trie<i32> = [("hello", 1), ("help", 2)].into_iter().collect();
+        let bytes = trie.to_bytes();
+
+        assert_eq!(
+            Trie::<i32>::from_bytes(b""),
+            Err(DeserializeError::UnexpectedEof)
+        );
+        assert_eq!(
+            Trie::<i32>::from_bytes(b"JSON{}"),
+            Err(DeserializeError::BadMagic)
+        );
+        assert_eq!(
+            Trie::<i32>::from_bytes(b"TRH\x09\x00"),
+            Err(DeserializeError::UnsupportedVersion(9))
+        );
+        assert_eq!(
+            Trie::<i32>::from_bytes(b"TRH\x01\xff"),
+            Err(DeserializeError::InvalidFlags(0xff))
+        );
+
+        // Every truncation is caught rather than producing a partial trie
+        for len in 0..bytes.len() {
+            assert!(Trie::<i32>::from_bytes(&bytes[..len]).is_err());
+        }
+
+        let mut padded = bytes.clone();
+        padded.push(0);
+        assert_eq!(
+            Trie::<i32>::from_bytes(&padded),
+            Err(DeserializeError::TrailingBytes(1))
+        );
+
+        // Root with one child 'a', followed by a sibling that is also 'a'
+        assert_eq!(
+            Trie::<()>::from_bytes(b"TRH\x01\x02a\x05a\x01"),
+            Err(DeserializeError::DuplicateChild('a'))
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_crafted_vec_lengths() {
+        // The root holds a Vec<()> whose length is u64::MAX
+        let huge = b"TRH\x01\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01";
+        assert!(matches!(
+            Trie::<Vec<()>>::from_bytes(huge),
+            Err(DeserializeError::InvalidValue(_))
+        ));
+        // A Vec<u32> claiming 1000 items with two bytes left
+        assert_eq!(
+            Trie::<Vec<u32>>::from_bytes(b"TRH\x01\x01\xe8\x07\x01\x02"),
+            Err(DeserializeError::UnexpectedEof)
+        );
+
+        // Lengths within the limits still decode
+        let trie = trie! { "unit" => vec![(); 1000], "nums" => vec![] };
+        assert_eq!(Trie::from_bytes(&trie.to_bytes()), Ok(trie));
+        let trie = trie! { "nums" => vec![1u32, 2, 3] };
+        assert_eq!(Trie::from_bytes(&trie.to_bytes()), Ok(trie));
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_dead_child_records() {
+        // A child 'a' with neither a value nor children
+        assert_eq!(
+            Trie::<()>::from_bytes(b"TRH\x01\x02a\x00"),
+            Err(DeserializeError::InvalidFlags(0x00))
+        );
+        // The same, but as the first of two siblings
+        assert_eq!(
+            Trie::<()>::from_bytes(b"TRH\x01\x02a\x04b\x01"),
+            Err(DeserializeError::InvalidFlags(0x04))
+        );
+        // A dead leaf below a live branch
+        assert_eq!(
+            Trie::<()>::from_bytes(b"TRH\x01\x02a\x03b\x00"),
+            Err(DeserializeError::InvalidFlags(0x00))
+        );
+    }
+
+    /// The node ids, terminal node ids and labeled edges of a `to_dot` graph.
+    struct ParsedDot {
+        nodes: Vec<String>,
+        terminals: Vec<String>,
+        edges: HashMap<(String, char), String>,
+    }
+
+    fn parse_dot(dot: &str) -> ParsedDot {
+        let mut nodes = Vec::new();
+        let mut terminals = Vec::new();
+        let mut edges = HashMap::new();
+        for line in dot.lines().map(str::trim) {
+            if let Some((from, rest)) = line.split_once(" -> ") {
+                let (to, label) = rest.split_once(" [label=\"").unwrap();
+                let c = label.trim_end_matches("\"];").chars().next().unwrap();
+                edges.insert((from.to_string(), c), to.to_string());
+            } else if line.starts_with('n') && !line.starts_with("node ") {
+                let id = line.split_once(' ').unwrap().0.to_string();
+                if line.contains("doublecircle") {
+                    terminals.push(id.clone());
+                }
+                nodes.push(id);
+            }
+        }
+        ParsedDot {
+            nodes,
+            terminals,
+            edges,
+        }
+    }
+
+    #[test]
+    fn test_to_dot_structure() {
+        let words = ["", "car", "card", "care", "cat", "dog"];
+        let trie: Trie<()> = words.into_iter().collect();
+        let dot = trie.to_dot();
+        assert!(dot.starts_with("digraph Trie {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("n0 [label=\"ROOT\", shape=doublecircle];"));
+
+        let ParsedDot {
+            nodes,
+            terminals,
+            edges,
+        } = parse_dot(&dot);
+        // ROOT, c, ca, car, card, care, cat, d, do, dog
+        assert_eq!(nodes.len(), 10);
+        assert_eq!(nodes.len(), trie.node_count());
+        assert_eq!(edges.len(), nodes.len() - 1);
+        assert_eq!(terminals.len(), trie.len());
+
+        // Every key traces a path from ROOT to a double circle
+        for word in words {
+            let mut id = "n0".to_string();
+            for c in word.chars() {
+                id = edges[&(id, c)].clone();
+            }
+            assert!(terminals.contains(&id), "{word} does not end on a terminal");
+        }
+    }
+
+    #[test]
+    fn test_to_dot_truncated_and_escaping() {
+        let trie: Trie<i32> = [("ab\"c", 1), ("a\\", 2)].into_iter().collect();
+
+        let dot = trie.to_dot();
+        assert!(dot.contains("[label=\"\\\"\"]"));
+        assert!(dot.contains("[label=\"\\\\\"]"));
+
+        assert_eq!(parse_dot(&trie.to_dot_truncated(1)).nodes.len(), 2);
+        assert!(trie.to_dot_truncated(1).contains("style=dashed"));
+        assert_eq!(trie.to_dot_truncated(10), dot);
+
+        let empty: Trie<i32> = Trie::new();
+        assert_eq!(
+            empty.to_dot(),
+            "digraph Trie {\n    node [shape=circle, label=\"\"];\n    n0 [label=\"ROOT\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_ascii_trie_basic_operations() {
+        const EMPTY: AsciiTrie<i32> = AsciiTrie::new();
+        let mut trie = EMPTY;
+        assert!(trie.is_empty());
+
+        assert_eq!(trie.insert("apple", 1), None);
+        assert_eq!(trie.insert("app", 2), None);
+        assert_eq!(trie.insert("apple", 3), Some(1));
+        assert_eq!(trie.insert("", 0), None);
+        assert_eq!(trie.len(), 3);
+
+        assert_eq!(trie.get("apple"), Some(&3));
+        assert_eq!(trie.get("app"), Some(&2));
+        assert_eq!(trie.get(""), Some(&0));
+        assert_eq!(trie.get("ap"), None);
+        assert!(trie.prefix_search("ap"));
+        assert!(!trie.prefix_search("b"));
+
+        // Deleting a key keeps the keys that share its path
+        assert!(trie.delete("app"));
+        assert!(!trie.delete("app"));
+        assert!(!trie.delete(""));
+        assert_eq!(trie.get("apple"), Some(&3));
+        assert!(trie.delete("apple"));
+        assert!(!trie.prefix_search("a"));
+        assert_eq!(trie.len(), 1);
+    }
+
+    #[test]
+    fn test_ascii_trie_auto_complete_and_word_list() {
+        let mut trie = AsciiTrie::new();
+        trie.add_word_list(&["card", "care", "car", "Cat", "cat", "dog"], |w| w.len());
+
+        assert_eq!(trie.auto_complete("car", 10), vec!["car", "card", "care"]);
+        assert_eq!(trie.auto_complete("", 3), vec!["Cat", "car", "card"]);
+        assert_eq!(trie.auto_complete("", 100).len(), 6);
+        assert!(trie.auto_complete("car", 0).is_empty());
+        assert!(trie.auto_complete("x", 10).is_empty());
+        assert_eq!(trie.get("care"), Some(&4));
+    }
+
+    #[test]
+    #[should_panic(expected = "AsciiTrie only supports ASCII keys")]
+    fn test_ascii_trie_rejects_non_ascii() {
+        let mut trie = AsciiTrie::new();
+        trie.insert("café", 1);
+    }
+
+    #[test]
+    fn test_byte_trie_basic_operations() {
+        let mut trie = ByteTrie::new();
+        assert!(trie.is_empty());
+
+        // Keys need not be valid UTF-8
+        assert_eq!(trie.insert(b"\xff\xfe", 1), None);
+        assert_eq!(trie.insert(b"\xff", 2), None);
+        assert_eq!(trie.insert(b"\xff\xfe", 3), Some(1));
+        assert_eq!(trie.insert(b"", 0), None);
+        assert_eq!(trie.len(), 3);
+
+        assert_eq!(trie.get(b"\xff\xfe"), Some(&3));
+        assert_eq!(trie.get(b"\xff"), Some(&2));
+        assert_eq!(trie.get(b""), Some(&0));
+        assert_eq!(trie.get(b"\xfe"), None);
+        assert!(trie.prefix_search(b"\xff"));
+        assert!(!trie.prefix_search(b"\x00"));
+
+        // Deleting a key keeps the keys that share its path
+        assert!(trie.delete(b"\xff"));
+        assert!(!trie.delete(b"\xff"));
+        assert!(!trie.delete(b""));
+        assert_eq!(trie.get(b"\xff\xfe"), Some(&3));
+        assert!(trie.delete(b"\xff\xfe"));
+        assert!(!trie.prefix_search(b"\xff"));
+        assert_eq!(trie.len(), 1);
+    }
+
+    #[test]
+    fn test_byte_trie_auto_complete_and_word_list() {
+        let mut trie = ByteTrie::new();
+        trie.add_word_list(&["card", "care", "car", "Cat", "dog"], |w| w.len());
+        trie.insert(&[b'c', 0x80], 9);
+
+        assert_eq!(
+            trie.auto_complete(b"car", 10),
+            vec![b"car".to_vec(), b"card".to_vec(), b"care".to_vec()]
+        );
+        // Bytes order after ASCII, so the non-UTF-8 key comes last under "c"
+        assert_eq!(trie.auto_complete(b"c", 10).last(), Some(&vec![b'c', 0x80]));
+        assert_eq!(
+            trie.auto_complete(b"", 2),
+            vec![b"Cat".to_vec(), b"car".to_vec()]
+        );
+        assert!(trie.auto_complete(b"car", 0).is_empty());
+        assert!(trie.auto_complete(b"x", 10).is_empty());
+        assert_eq!(trie.get(b"care"), Some(&4));
+    }
+
+    #[test]
+    fn test_byte_trie_node_with_every_byte_as_child() {
+        let mut trie = ByteTrie::new();
+        for byte in 0..=u8::MAX {
+            trie.insert(&[byte], byte);
+        }
+        assert_eq!(trie.len(), 256);
+        assert_eq!(trie.auto_complete(b"", usize::MAX).len(), 256);
+
+        for byte in 0..=u8::MAX {
+            assert!(trie.delete(&[byte]));
+        }
+        assert!(trie.is_empty());
+        assert!(!trie.prefix_search(b"\x00"));
+    }
+
+    #[test]
+    fn test_radix_trie_splits_edges() {
+        let mut trie = RadixTrie::new();
+        assert_eq!(trie.insert("unconditionally", 1), None);
+        // One node for the whole key, not one per character
+        assert_eq!(trie.node_count(), 2);
+
+        // Diverging partway through a label splits it
+        trie.insert("uncommon", 2);
+        assert_eq!(trie.node_count(), 4);
+        // A key ending inside a label splits it without a new branch
+        trie.insert("un", 3);
+        assert_eq!(trie.node_count(), 5);
+        assert_eq!(trie.insert("uncommon", 4), Some(2));
+        assert_eq!(trie.len(), 3);
+
+        assert_eq!(trie.get("unconditionally"), Some(&1));
+        assert_eq!(trie.get("uncommon"), Some(&4));
+        assert_eq!(trie.get("un"), Some(&3));
+        assert_eq!(trie.get("unco"), None);
+        assert_eq!(trie.get("uncommonly"), None);
+
+        *trie.get_mut("un").unwrap() += 10;
+        assert_eq!(trie.get("un"), Some(&13));
+
+        // Unicode labels split on character boundaries
+        let mut unicode = RadixTrie::new();
+        unicode.insert("café", 1);
+        unicode.insert("cafè", 2);
+        assert_eq!(unicode.get("café"), Some(&1));
+        assert_eq!(unicode.get("cafè"), Some(&2));
+        assert_eq!(unicode.node_count(), 4);
+    }
+
+    #[test]
+    fn test_radix_trie_delete_merges_nodes() {
+        let mut trie = RadixTrie::new();
+        trie.add_word_list(&["test", "tester", "testing", "team"], |w| w.len());
+        let full = trie.node_count();
+
+        assert!(trie.delete("tester"));
+        assert!(!trie.delete("tester"));
+        assert!(!trie.delete("tes"));
+        assert_eq!(trie.node_count(), full - 1);
+
+        // "test" was the only thing keeping "ing" separate from it
+        assert!(trie.delete("test"));
+        assert_eq!(trie.get("testing"), Some(&7));
+        assert_eq!(trie.node_count(), full - 2);
+
+        // Removing "team" leaves the root with a single merged edge
+        assert!(trie.delete("team"));
+        assert_eq!(trie.node_count(), 2);
+        assert_eq!(trie.auto_complete("", 10), vec!["testing"]);
+        assert!(trie.delete("testing"));
+        assert!(trie.is_empty());
+        assert_eq!(trie.node_count(), 1);
+    }
+
+    #[test]
+    fn test_radix_trie_prefix_queries() {
+        let mut trie = RadixTrie::new();
+        trie.add_word_list(&["car", "card", "care", "careful", "cat", "dog"], |_| ());
+
+        assert!(trie.prefix_search("ca"));
+        assert!(trie.prefix_search("caref"));
+        assert!(trie.prefix_search(""));
+        assert!(!trie.prefix_search("cab"));
+        assert!(!trie.prefix_search("carefully"));
+
+        assert_eq!(
+            trie.auto_complete("car", 10),
+            vec!["car", "card", "care", "careful"]
+        );
+        // A prefix ending partway through a label still completes
+        assert_eq!(trie.auto_complete("caref", 10), vec!["careful"]);
+        assert_eq!(trie.auto_complete("do", 10), vec!["dog"]);
+        assert_eq!(trie.auto_complete("", 3), vec!["car", "card", "care"]);
+        assert!(trie.auto_complete("x", 10).is_empty());
+        assert!(trie.auto_complete("car", 0).is_empty());
+    }
+
+    #[test]
+    fn test_arena_trie_basic_operations() {
+        let mut trie = ArenaTrie::with_capacity(16);
+        assert!(trie.is_empty());
+        assert_eq!(trie.insert("apple", 1), None);
+        assert_eq!(trie.insert("app", 2), None);
+        assert_eq!(trie.insert("apple", 3), Some(1));
+        assert_eq!(trie.insert("", 0), None);
+        assert_eq!(trie.insert("日本", 4), None);
+        assert_eq!(trie.len(), 4);
+
+        assert_eq!(trie.get("apple"), Some(&3));
+        assert_eq!(trie.get("app"), Some(&2));
+        assert_eq!(trie.get(""), Some(&0));
+        assert_eq!(trie.get("日本"), Some(&4));
+        assert_eq!(trie.get("ap"), None);
+        *trie.get_mut("app").unwrap() += 10;
+        assert_eq!(trie.get("app"), Some(&12));
+
+        assert!(trie.prefix_search("ap"));
+        assert!(trie.prefix_search("日"));
+        assert!(!trie.prefix_search("b"));
+    }
+
+    #[test]
+    fn test_arena_trie_delete_reuses_slots() {
+        let mut trie = ArenaTrie::new();
+        trie.add_word_list(&["car", "card", "cat"], |w| w.len());
+        // root, c, ca, car, card, cat
+        assert_eq!(trie.node_count(), 6);
+
+        assert!(trie.delete("card"));
+        assert!(!trie.delete("card"));
+        assert!(!trie.delete("ca"));
+        assert!(!trie.delete(""));
+        assert_eq!(trie.node_count(), 5);
+        assert_eq!(trie.get("car"), Some(&3));
+
+        // The freed slot is reused rather than growing the arena
+        trie.insert("cab", 3);
+        assert_eq!(trie.node_count(), 6);
+        assert!(trie.delete("car"));
+        assert!(trie.delete("cat"));
+        assert!(trie.delete("cab"));
+        assert!(trie.is_empty());
+        assert_eq!(trie.node_count(), 1);
+        assert!(!trie.prefix_search("c"));
+    }
+
+    #[test]
+    fn test_arena_trie_auto_complete() {
+        let mut trie = ArenaTrie::new();
+        trie.add_word_list(&["careful", "car", "card", "care", "cat", "dog"], |_| ());
+
+        assert_eq!(
+            trie.auto_complete("car", 10),
+            vec!["car", "card", "care", "careful"]
+        );
+        assert_eq!(trie.auto_complete("", 3), vec!["car", "card", "care"]);
+        assert_eq!(trie.auto_complete("", 100).len(), 6);
+        assert!(trie.auto_complete("car", 0).is_empty());
+        assert!(trie.auto_complete("x", 10).is_empty());
+    }
+
+    #[test]
+    fn test_tst_trie_basic_operations() {
+        let mut trie = TstTrie::new();
+        assert!(trie.is_empty());
+        assert!(!trie.prefix_search(""));
+        assert_eq!(trie.insert("apple", 1), None);
+        assert_eq!(trie.insert("app", 2), None);
+        assert_eq!(trie.insert("apple", 3), Some(1));
+        assert_eq!(trie.insert("", 0), None);
+        assert_eq!(trie.insert("日本", 4), None);
+        assert_eq!(trie.len(), 4);
+
+        assert_eq!(trie.get("apple"), Some(&3));
+        assert_eq!(trie.get(""), Some(&0));
+        assert_eq!(trie.get("日本"), Some(&4));
+        assert_eq!(trie.get("ap"), None);
+        assert_eq!(trie.get("apples"), None);
+        *trie.get_mut("app").unwrap() += 10;
+        assert_eq!(trie.get("app"), Some(&12));
+
+        assert!(trie.prefix_search("ap"));
+        assert!(trie.prefix_search("日"));
+        assert!(!trie.prefix_search("b"));
+        assert_eq!(trie.auto_complete("", 10), vec!["", "app", "apple", "日本"]);
+        assert!(!trie.delete(""));
+    }
+
+    #[test]
+    fn test_tst_trie_delete_frees_nodes() {
+        let mut trie = TstTrie::new();
+        trie.add_word_list(&["b", "a", "c"], |w| w.len());
+        assert_eq!(trie.node_count(), 3);
+
+        // "b" still routes to its siblings, so its node stays but no longer
+        // counts as a prefix
+        assert!(trie.delete("b"));
+        assert!(!trie.delete("b"));
+        assert!(!trie.prefix_search("b"));
+        assert_eq!(trie.auto_complete("", 10), vec!["a", "c"]);
+        assert!(trie.delete("a"));
+        assert_eq!(trie.node_count(), 1);
+        assert_eq!(trie.get("c"), Some(&1));
+
+        trie.insert("cab", 3);
+        assert!(trie.delete("c"));
+        assert!(trie.delete("cab"));
+        assert!(trie.is_empty());
+        assert_eq!(trie.node_count(), 0);
+        trie.insert("z", 26);
+        assert_eq!(trie.node_count(), 1);
+    }
+
+    #[test]
+    fn test_tst_trie_matches_trie() {
+        // A fixed pseudo-random sequence of inserts and deletes, mirrored on a
+        // Trie and checked against it after every step
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+        let alphabet = ['a', 'b', 'c', 'é', 'z'];
+        let mut tst = TstTrie::new();
+        let mut trie = Trie::new();
+        for step in 0..3000 {
+            let key: String = (0..next(5))
+                .map(|_| alphabet[next(alphabet.len() as u64) as usize])
+                .collect();
+            if next(3) == 0 {
+                assert_eq!(tst.delete(&key), trie.delete(&key), "delete {key:?}");
+            } else {
+                assert_eq!(tst.insert(&key, step), trie.insert(&key, step));
+            }
+            assert_eq!(tst.len(), trie.len());
+            assert_eq!(tst.get(&key), trie.get(&key));
+            let prefix: String = key.chars().take(2).collect();
+            assert_eq!(tst.prefix_search(&prefix), trie.prefix_search(&prefix));
+            assert_eq!(
+                tst.auto_complete(&prefix, 7),
+                trie.sorted_auto_complete(&prefix, 7)
+            );
+        }
+        assert_eq!(
+            tst.auto_complete("", usize::MAX),
+            trie.keys().collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_build_matches_sequential() {
+        let mut items: Vec<(String, usize)> = (0..5000)
+            .map(|i| {
+                (
+                    format!("{}{}", ["app", "ban", "cat", "日本", "🦀"][i % 5], i / 3),
+                    i,
+                )
+            })
+            .collect();
+        // Repeated keys, where the last occurrence must win, and the empty key
+        items.push(("app0".to_string(), 99));
+        items.push((String::new(), 7));
+
+        let mut sequential = Trie::new();
+        for (key, value) in &items {
+            sequential.insert(key, *value);
+        }
+
+        for threads in [1, 2, 8] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let parallel = pool.install(|| Trie::par_build(&items));
+            assert_eq!(parallel, sequential);
+            assert_eq!(parallel.len(), sequential.len());
+            assert_eq!(parallel.get("app0"), Some(&99));
+        }
+
+        let empty: Trie<i32> = Trie::par_build::<&str>(&[]);
+        assert!(empty.is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_insert_all_into_existing_trie() {
+        let mut trie: Trie<usize> = [("apple", 1), ("zebra", 2)].into_iter().collect();
+        let items: Vec<(String, usize)> = (0..3000)
+            .map(|i| (format!("{}{}", ["app", "zeb", "日本"][i % 3], i / 2), i))
+            .chain([("apple".to_string(), 10), (String::new(), 0)])
+            .collect();
+
+        let mut sequential = trie.clone();
+        for (key, value) in &items {
+            sequential.insert(key, *value);
+        }
+        trie.par_insert_all(&items);
+        assert_eq!(trie, sequential);
+        assert_eq!(trie.len(), sequential.len());
+        assert_eq!(trie.get("apple"), Some(&10));
+        assert_eq!(trie.get("zebra"), Some(&2));
+
+        trie.par_insert_all::<&str>(&[]);
+        assert_eq!(trie, sequential);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_insert_all_keeps_trie_when_clone_panics() {
+        #[derive(Debug, PartialEq)]
+        struct Fragile(usize);
+        impl Clone for Fragile {
+            fn clone(&self) -> Self {
+                assert_ne!(self.0, 1234, "cannot clone");
+                Fragile(self.0)
+            }
+        }
+
+        let mut trie: Trie<Fragile> = Trie::new();
+        trie.insert("apple", Fragile(1));
+        trie.insert("zebra", Fragile(2));
+        let items: Vec<(String, Fragile)> = (0..2000)
+            .map(|i| {
+                (
+                    format!("{}{}", ["app", "zeb", "日本"][i % 3], i),
+                    Fragile(i),
+                )
+            })
+            .collect();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            trie.par_insert_all(&items);
+        }));
+        assert!(result.is_err());
+        assert_eq!(trie.len(), 2);
+        assert_eq!(trie.get("apple"), Some(&Fragile(1)));
+        assert_eq!(trie.get("zebra"), Some(&Fragile(2)));
+        assert_eq!(trie.node_count(), 11);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter_matches_iter() {
+        use rayon::prelude::*;
+
+        let mut trie: Trie<usize> = (0..2000)
+            .map(|i| (format!("{}{}", ["ant", "bee", "cow", "🦀"][i % 4], i), i))
+            .collect();
+        trie.insert("", 5000);
+
+        let parallel: Vec<(String, &usize)> = trie.par_iter().collect();
+        let sequential: Vec<(String, &usize)> = trie.iter().collect();
+        assert_eq!(parallel, sequential);
+        assert_eq!(trie.par_iter().filter(|(_, v)| **v >= 1000).count(), 1001);
+
+        let mut owned: Vec<(String, usize)> = trie.clone().into_par_iter().collect();
+        owned.sort_unstable();
+        let expected: Vec<(String, usize)> = trie.iter().map(|(k, &v)| (k, v)).collect();
+        assert_eq!(owned, expected);
+
+        assert_eq!(Trie::<usize>::new().par_iter().count(), 0);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_sync_trie_shared_across_threads() {
+        use crate::sync::SyncTrie;
+        use std::thread;
+
+        let trie = SyncTrie::new();
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let handle = trie.clone();
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        handle.insert(&format!("t{t}_{i:03}"), i);
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        // Every handle sees the same Trie
+        assert_eq!(trie.len(), 400);
+        assert_eq!(trie.get("t2_042"), Some(42));
+        assert!(trie.prefix_search("t3_"));
+        assert_eq!(
+            trie.auto_complete("t1_09", 3),
+            vec!["t1_090", "t1_091", "t1_092"]
+        );
+
+        let readers: Vec<_> = (0..4)
+            .map(|t| {
+                let handle = trie.clone();
+                thread::spawn(move || handle.auto_complete(&format!("t{t}_"), usize::MAX).len())
+            })
+            .collect();
+        for reader in readers {
+            assert_eq!(reader.join().unwrap(), 100);
+        }
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_sync_trie_snapshot_is_independent() {
+        use crate::sync::SyncTrie;
+
+        let trie = SyncTrie::from(Trie::from_iter([("apple", 1)]));
+        let snapshot = trie.snapshot();
+        assert!(trie.delete("apple"));
+        assert!(!trie.delete("apple"));
+        assert!(trie.is_empty());
+        assert_eq!(snapshot.get("apple"), Some(&1));
+    }
+
+    #[test]
+    fn test_auto_complete_iter_is_lazy_and_sorted() {
+        let trie: Trie<i32> = [
+            ("app", 1),
+            ("apple", 2),
+            ("application", 3),
+            ("apt", 4),
+            ("b", 5),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut iter = trie.auto_complete_iter("app");
+        assert_eq!(iter.next().as_deref(), Some("app"));
+        assert_eq!(iter.next().as_deref(), Some("apple"));
+        assert_eq!(iter.next().as_deref(), Some("application"));
+        assert_eq!(iter.next(), None);
+
+        // Matches auto_complete for any limit
+        for limit in 0..6 {
+            let lazy: Vec<String> = trie.auto_complete_iter("a").take(limit).collect();
+            assert_eq!(lazy, trie.auto_complete("a", limit));
+        }
+        assert_eq!(trie.auto_complete_iter("x").next(), None);
+    }
+
+    #[test]
+    fn test_auto_complete_with_values() {
+        let words = [
+            ("car", 1),
+            ("card", 2),
+            ("care", 3),
+            ("careful", 4),
+            ("cat", 5),
+            ("dog", 6),
+        ];
+        let trie: Trie<i32> = words.into_iter().collect();
+
+        let results = trie.auto_complete_with_values("car", 10);
+        for (key, value) in &results {
+            assert_eq!(trie.get(key), Some(*value));
+        }
+
+        // Same keys in the same order as auto_complete, for every limit
+        for limit in 0..7 {
+            let keys: Vec<String> = trie
+                .auto_complete_with_values("", limit)
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect();
+            assert_eq!(keys, trie.auto_complete("", limit));
+        }
+
+        assert_eq!(
+            trie.auto_complete_with_values("ca", 2),
+            vec![("car".to_string(), &1), ("card".to_string(), &2)]
+        );
+        assert!(trie.auto_complete_with_values("x", 10).is_empty());
+    }
+
+    #[test]
+    fn test_auto_complete_ranked_by_frequency() {
+        let frequencies = [
+            ("the", 500),
+            ("then", 40),
+            ("there", 90),
+            ("these", 90),
+            ("thesis", 5),
+            ("they", 300),
+            ("cat", 1000),
+        ];
+        let trie: Trie<u32> = frequencies.into_iter().collect();
+
+        let keys = |results: Vec<(String, &u32)>| -> Vec<String> {
+            results.into_iter().map(|(key, _)| key).collect()
+        };
+
+        // Highest frequency first; "there" and "these" tie and stay sorted
+        assert_eq!(
+            keys(trie.auto_complete_ranked("the", 4)),
+            vec!["the", "they", "there", "these"]
+        );
+        assert_eq!(
+            trie.auto_complete_ranked("the", 1),
+            vec![("the".to_string(), &500)]
+        );
+        assert_eq!(trie.auto_complete_ranked("the", 100).len(), 6);
+        assert!(trie.auto_complete_ranked("the", 0).is_empty());
+        assert!(trie.auto_complete_ranked("x", 5).is_empty());
+
+        // A custom score can use the key as well as the value
+        let by_value_per_char =
+            trie.auto_complete_ranked_by("the", 2, |key, value| *value / key.len() as u32);
+        assert_eq!(keys(by_value_per_char), vec!["the", "they"]);
+    }
+
+    #[test]
+    fn test_top_k_by_value() {
+        let trie: Trie<u32> = [
+            ("the", 500),
+            ("there", 90),
+            ("these", 90),
+            ("then", 90),
+            ("they", 300),
+            ("cat", 1000),
+        ]
+        .into_iter()
+        .collect();
+
+        let keys = |results: Vec<(String, &u32)>| -> Vec<String> {
+            results.into_iter().map(|(key, _)| key).collect()
+        };
+
+        assert_eq!(
+            trie.top_k_by_value(2),
+            vec![("cat".to_string(), &1000), ("the".to_string(), &500)]
+        );
+        // Three keys tie at 90; the lexicographically smallest ones are kept
+        assert_eq!(
+            keys(trie.top_k_by_value(5)),
+            vec!["cat", "the", "they", "then", "there"]
+        );
+        // Asking for more than there is returns everything, ranked
+        assert_eq!(
+            keys(trie.top_k_by_value(100)),
+            vec!["cat", "the", "they", "then", "there", "these"]
+        );
+        assert_eq!(
+            keys(trie.top_k_by_value(usize::MAX)),
+            vec!["cat", "the", "they", "then", "there", "these"]
+        );
+        assert_eq!(
+            keys(trie.top_k_by_value_with_prefix("the", usize::MAX)),
+            vec!["the", "they", "then", "there", "these"]
+        );
+        assert!(trie.top_k_by_value(0).is_empty());
+        assert!(Trie::<u32>::new().top_k_by_value(3).is_empty());
+
+        assert_eq!(
+            keys(trie.top_k_by_value_with_prefix("the", 3)),
+            vec!["the", "they", "then"]
+        );
+        assert!(trie.top_k_by_value_with_prefix("x", 3).is_empty());
+
+        // Same selection as the full sort behind auto_complete_ranked
+        for k in 0..8 {
+            assert_eq!(
+                trie.top_k_by_value_with_prefix("th", k),
+                trie.auto_complete_ranked("th", k)
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_inverted_index() {
+        let docs = [
+            ("intro.md", "the trie stores the keys"),
+            ("empty.md", "   "),
+            ("usage.md", "insert keys then\tsearch\nthe trie"),
+            ("faq.md", "why a trie"),
+        ];
+        let index = Trie::build_inverted_index(&docs);
+
+        // "the" appears twice in the first document but is listed once
+        assert_eq!(index.get("the"), Some(&vec![0, 2]));
+        assert_eq!(index.get("trie"), Some(&vec![0, 2, 3]));
+        assert_eq!(index.get("keys"), Some(&vec![0, 2]));
+        assert_eq!(index.get("search"), Some(&vec![2]));
+        assert_eq!(index.get("tri"), None);
+        assert_eq!(index.get(""), None);
+        assert_eq!(docs[index.get("why").unwrap()[0]].0, "faq.md");
+        assert_eq!(index.len(), 9);
+
+        // Prefix queries reach every token that starts with the prefix
+        let tokens: Vec<String> = index.prefix_iter("th").map(|(token, _)| token).collect();
+        assert_eq!(tokens, vec!["the", "then"]);
+
+        assert!(Trie::build_inverted_index(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_multi_trie_accumulates_values() {
+        let mut trie = MultiTrie::new();
+        assert!(trie.is_empty());
+        trie.insert("cat", 1);
+        trie.insert("car", 2);
+        trie.insert("cat", 3);
+        trie.insert("cat", 1);
+        trie.insert("", 0);
+
+        assert_eq!(trie.len(), 3);
+        assert_eq!(trie.get_all("cat"), [1, 3, 1]);
+        assert_eq!(trie.get_first("cat"), Some(&1));
+        assert_eq!(trie.get_last("cat"), Some(&1));
+        assert_eq!(trie.get_all("ca"), [] as [i32; 0]);
+        assert_eq!(trie.get_first("dog"), None);
+
+        assert_eq!(
+            trie.completions_with_all_values("ca", 10),
+            vec![
+                ("car".to_string(), &[2][..]),
+                ("cat".to_string(), &[1, 3, 1][..])
+            ]
+        );
+        assert_eq!(trie.completions_with_all_values("", 1).len(), 1);
+        assert!(trie.completions_with_all_values("ca", 0).is_empty());
+
+        assert_eq!(trie.remove_all("cat"), vec![1, 3, 1]);
+        assert!(trie.remove_all("cat").is_empty());
+        assert_eq!(trie.remove_all(""), vec![0]);
+        assert_eq!(trie.len(), 1);
+        assert_eq!(trie.into_inner().get("car"), Some(&vec![2]));
+    }
+
+    #[test]
+    fn test_soundex_codes() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Rubin"), "R150");
+        // "h" and "w" do not separate consonants with the same digit
+        assert_eq!(soundex("Ashcraft"), "A261");
+        // A consonant with the first letter's digit is not repeated
+        assert_eq!(soundex("Pfister"), "P236");
+        // A vowel does separate them
+        assert_eq!(soundex("Tymczak"), "T522");
+        assert_eq!(soundex("lee"), "L000");
+        assert_eq!(soundex("o'Hara"), "O600");
+        assert_eq!(soundex("123"), "");
+    }
+
+    #[test]
+    fn test_phonetic_trie_soundalike_search() {
+        let mut trie = PhoneticTrie::new();
+        assert!(trie.is_empty());
+        trie.insert("colour", 1);
+        trie.insert("color", 2);
+        trie.insert("banana", 3);
+        trie.insert("color", 4);
+        assert_eq!(trie.len(), 4);
+
+        // Spelling variants find each other's values
+        let color: Vec<(String, &i32)> = vec![
+            ("colour".to_string(), &1),
+            ("color".to_string(), &2),
+            ("color".to_string(), &4),
+        ];
+        assert_eq!(trie.soundalike_search("color"), color);
+        assert_eq!(trie.soundalike_search("COLOUR"), color);
+        // Soundex keeps the first letter as written, so "k" is not "c"
+        assert!(trie.soundalike_search("kolor").is_empty());
+
+        // Dissimilar words do not match
+        assert!(trie.soundalike_search("trie").is_empty());
+        assert_eq!(
+            trie.soundalike_search("bananna"),
+            vec![("banana".to_string(), &3)]
+        );
+    }
+
+    #[test]
+    fn test_aho_corasick_finds_overlapping_matches() {
+        let trie = trie! { "he" => 1, "she" => 2, "his" => 3, "hers" => 4 };
+        let automaton = trie.compute_aho_corasick();
+        assert_eq!(automaton.len(), 4);
+        let found: Vec<(&str, usize, usize, i32)> = automaton
+            .search("ushers")
+            .iter()
+            .map(|m| (m.matched, m.start, m.end(), *m.value))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("she", 1, 4, 2), ("he", 2, 4, 1), ("hers", 2, 6, 4)]
+        );
+        assert!(automaton.search("").is_empty());
+        assert!(automaton.search("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_aho_corasick_matches_naive_search() {
+        let patterns = [
+            "a", "ab", "bab", "bc", "bca", "c", "caa", "ça", "aça", "🦀🦀",
+        ];
+        let mut trie: Trie<usize> = patterns.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+        // The empty key is not a pattern.
+        trie.insert("", 99);
+        let automaton = trie.compute_aho_corasick();
+        assert_eq!(automaton.len(), patterns.len());
+
+        for text in ["abccab", "bcabcaabc", "aaaa", "aça ça🦀🦀🦀 bab", ""] {
+            let mut found: Vec<(usize, &str, usize)> = automaton
+                .search(text)
+                .iter()
+                .map(|m| (m.start, m.matched, *m.value))
+                .collect();
+            found.sort_unstable();
+            let mut expected = Vec::new();
+            for (start, _) in text.char_indices() {
+                for (i, pattern) in patterns.iter().enumerate() {
+                    if text[start..].starts_with(pattern) {
+                        expected.push((start, *pattern, i));
+                    }
+                }
+            }
+            expected.sort_unstable();
+            assert_eq!(found, expected, "{text}");
+        }
+
+        let empty = Trie::<u8>::new().compute_aho_corasick();
+        assert!(empty.is_empty());
+        assert!(empty.search("anything").is_empty());
+    }
+
+    #[test]
+    fn test_frozen_trie_matches_trie() {
+        let words = [
+            "", "a", "app", "apple", "apply", "apt", "banana", "band", "ça", "çava", "🦀",
+        ];
+        let trie: Trie<usize> = words.iter().enumerate().map(|(i, w)| (*w, i)).collect();
+        let frozen = trie.clone().freeze();
+
+        assert_eq!(frozen.len(), trie.len());
+        assert!(frozen.iter().eq(trie.iter()));
+        assert!((&frozen).into_iter().map(|(key, _)| key).eq(trie.keys()));
+        for query in [
+            "",
+            "a",
+            "ap",
+            "app",
+            "apple",
+            "applesauce",
+            "ban",
+            "ç",
+            "x",
+            "🦀",
+        ] {
+            assert_eq!(frozen.get(query), trie.get(query), "{query}");
+            assert_eq!(
+                frozen.prefix_search(query),
+                trie.prefix_search(query),
+                "{query}"
+            );
+            assert_eq!(
+                frozen.auto_complete(query, usize::MAX),
+                trie.auto_complete(query, usize::MAX),
+                "{query}"
+            );
+            assert_eq!(frozen.auto_complete(query, 2), trie.auto_complete(query, 2));
+        }
+        assert!(frozen.auto_complete("a", 0).is_empty());
+
+        let mut thawed = frozen.thaw();
+        assert_eq!(thawed, trie);
+        thawed.insert("apricot", 99);
+        assert!(thawed.delete("apple"));
+        assert_eq!(
+            thawed.auto_complete("ap", 10),
+            vec!["app", "apply", "apricot", "apt"]
+        );
+
+        let empty = Trie::<u8>::new().freeze();
+        assert!(empty.is_empty());
+        assert_eq!(empty.iter().count(), 0);
+        assert_eq!(empty.prefix_search(""), Trie::<u8>::new().prefix_search(""));
+        assert!(empty.thaw().is_empty());
+    }
+
+    #[test]
+    fn test_walk_continue_visits_every_node() {
+        let trie: Trie<i32> = [("", 0), ("car", 1), ("cat", 2), ("do", 3)]
+            .into_iter()
+            .collect();
+
+        let mut visited = Vec::new();
+        trie.walk("", |key, value| {
+            visited.push((key.to_string(), value.copied()));
+            WalkControl::Continue
+        });
+        assert_eq!(
+            visited,
+            vec![
+                ("".to_string(), Some(0)),
+                ("c".to_string(), None),
+                ("ca".to_string(), None),
+                ("car".to_string(), Some(1)),
+                ("cat".to_string(), Some(2)),
+                ("d".to_string(), None),
+                ("do".to_string(), Some(3)),
+            ]
+        );
+
+        let mut count = 0;
+        trie.walk("ca", |_, _| {
+            count += 1;
+            WalkControl::Continue
+        });
+        assert_eq!(count, 3);
+
+        trie.walk("x", |_, _| panic!("nothing starts with x"));
+    }
+
+    #[test]
+    fn test_walk_skip_subtree_and_stop() {
+        let trie: Trie<i32> = [("car", 1), ("card", 2), ("cat", 3), ("dog", 4)]
+            .into_iter()
+            .collect();
+
+        // Skipping below "car" hides "card" but the walk carries on to "cat"
+        let mut visited = Vec::new();
+        trie.walk("", |key, _| {
+            visited.push(key.to_string());
+            if key == "car" {
+                WalkControl::SkipSubtree
+            } else {
+                WalkControl::Continue
+            }
+        });
+        assert!(!visited.contains(&"card".to_string()));
+        assert!(visited.contains(&"cat".to_string()));
+        assert!(visited.contains(&"dog".to_string()));
+
+        // Stop halts right at the first stored key
+        let mut visited = Vec::new();
+        trie.walk("", |key, value| {
+            visited.push(key.to_string());
+            if value.is_some() {
+                WalkControl::Stop
+            } else {
+                WalkControl::Continue
+            }
+        });
+        assert_eq!(visited, vec!["", "c", "ca", "car"]);
+    }
+
+    #[test]
+    fn test_walk_mut_updates_values() {
+        let mut trie: Trie<i32> = [("car", 1), ("card", 2), ("cat", 3)].into_iter().collect();
+
+        trie.walk_mut("car", |_, value| {
+            if let Some(value) = value {
+                *value += 100;
+            }
+            WalkControl::Continue
+        });
+        assert_eq!(trie.get("car"), Some(&101));
+        assert_eq!(trie.get("card"), Some(&102));
+        assert_eq!(trie.get("cat"), Some(&3));
+
+        let mut seen = Vec::new();
+        trie.walk_mut("", |key, value| {
+            if let Some(value) = value {
+                seen.push(key.to_string());
+                *value = 0;
+                return WalkControl::Stop;
+            }
+            WalkControl::Continue
+        });
+        assert_eq!(seen, vec!["car"]);
+        assert_eq!(trie.get("car"), Some(&0));
+        assert_eq!(trie.get("card"), Some(&102));
+    }
+
+    #[test]
+    fn test_node_count() {
+        let mut trie = Trie::new();
+        assert_eq!(trie.node_count(), 1);
+
+        trie.insert("hello", 1);
+        assert_eq!(trie.node_count(), 6);
+
+        // Shared prefixes add only the nodes past the shared part
+        trie.insert("help", 2);
+        assert_eq!(trie.node_count(), 7);
+
+        assert!(trie.delete("help"));
+        assert!(trie.delete("hello"));
+        assert_eq!(trie.node_count(), 1);
+    }
+
+    #[test]
+    fn test_memory_estimate_grows_with_nodes() {
+        let mut trie = Trie::new();
+        let empty = trie.memory_estimate();
+        assert_eq!(empty, std::mem::size_of::<Trie<u64>>());
+
+        trie.insert("hello", 1u64);
+        let one_word = trie.memory_estimate();
+        // At least one map slot per non-root node
+        let slot = std::mem::size_of::<(char, u64)>();
+        assert!(one_word >= empty + 5 * slot);
+
+        trie.insert("world", 2);
+        assert!(trie.memory_estimate() > one_word);
+    }
+
+    #[test]
+    fn test_trie_builder() {
+        let trie = TrieBuilder::<i32>::new()
+            .insert("hello", 1)
+            .insert("world", 2)
+            .insert("hello", 3)
+            .build();
+        assert_eq!(trie.len(), 2);
+        assert_eq!(trie.get("hello"), Some(&3));
+        assert_eq!(trie.get("world"), Some(&2));
+
+        let folded = TrieBuilder::new()
+            .case_insensitive(true)
+            .root_capacity(26)
+            .insert("Hello", 1)
+            .insert_all([("HELLO", 2), ("World", 3)])
+            .build();
+        assert_eq!(folded.len(), 2);
+        assert_eq!(folded.get("hello"), Some(&2));
+        assert_eq!(folded.get("Hello"), None);
+        assert_eq!(folded.auto_complete("", 10), vec!["hello", "world"]);
+
+        let empty: Trie<i32> = TrieBuilder::default().build();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_trie_builder_request_chain() {
+        let trie = TrieBuilder::<i32>::new()
+            .case_insensitive(true)
+            .sorted_children(true)
+            .insert("hello", 1)
+            .insert("world", 2)
+            .build();
+        assert!(trie.sorted_children());
+        assert_eq!(trie.len(), 2);
+        assert_eq!(trie.get("hello"), Some(&1));
+        assert_eq!(trie.values().collect::<Vec<_>>(), vec![&1, &2]);
+    }
+
+    #[test]
+    fn test_trie_builder_sorted_children() {
+        let words = [
+            "delta", "alpha", "", "charlie", "al", "bravo", "Écho", "alphabet",
+        ];
+        let mut trie = TrieBuilder::new()
+            .sorted_children(true)
+            .insert_all(words.iter().map(|w| (*w, w.len())))
+            .build();
+        let expected: Vec<(String, usize)> = trie.iter().map(|(k, v)| (k, *v)).collect();
+        let keys: Vec<String> = expected.iter().map(|(k, _)| k.clone()).collect();
+
+        let values: Vec<usize> = trie.values().copied().collect();
+        assert_eq!(values, expected.iter().map(|(_, v)| *v).collect::<Vec<_>>());
+        let mut_keys: Vec<String> = trie.iter_mut().map(|(k, _)| k).collect();
+        assert_eq!(mut_keys, keys);
+        assert_eq!(trie.values_mut().count(), words.len());
+        assert_eq!(trie.clone().into_iter().collect::<Vec<_>>(), expected);
+
+        // Inserting later keeps the order, and so do clones and subtries.
+        trie.insert("aardvark", 0);
+        assert_eq!(
+            trie.clone().into_iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            trie.keys().collect::<Vec<_>>()
+        );
+        let sub = trie.subtrie("al").unwrap();
+        assert!(sub.sorted_children());
+        assert_eq!(sub.values().copied().collect::<Vec<_>>(), vec![2, 5, 8]);
+
+        let drained: Vec<String> = trie.drain().map(|(k, _)| k).collect();
+        let mut sorted = drained.clone();
+        sorted.sort();
+        assert_eq!(drained, sorted);
+        assert!(trie.is_empty());
+    }
+
+    #[test]
+    fn test_trie_builder_hash_ordered_children() {
+        let words = ["delta", "alpha", "charlie", "bravo"];
+        let trie = TrieBuilder::new()
+            .sorted_children(false)
+            .insert_all(words.map(|key| (key, key.len())))
+            .build();
+        assert!(!trie.sorted_children());
+        assert_eq!(trie.len(), 4);
+        // The order is unspecified, but every entry is still visited once.
+        let mut values: Vec<usize> = trie.values().copied().collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![5, 5, 5, 7]);
+        let mut entries: Vec<(String, usize)> = trie.into_iter().collect();
+        entries.sort();
+        assert_eq!(entries[0], ("alpha".to_string(), 5));
+        assert_eq!(entries.len(), 4);
+    }
+
+    #[test]
+    fn test_trie_macro_forms() {
+        let empty: Trie<i32> = trie! {};
+        assert!(empty.is_empty());
+
+        let scores = trie! { "apple" => 1, "app" => 2, "apple" => 3, };
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores.get("apple"), Some(&3));
+        assert_eq!(scores.get("app"), Some(&2));
+
+        let words = trie!["cat", "car", "cat"];
+        let words: Trie<()> = words;
+        assert_eq!(words.len(), 2);
+        assert_eq!(words.get("car"), Some(&()));
+        assert_eq!(words.auto_complete("ca", 10), vec!["car", "cat"]);
+    }
+
+    #[test]
+    fn test_trie_set_basic_operations() {
+        let mut set = TrieSet::from_words(&["cat", "car", "dog"]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains("car"));
+        assert!(!set.contains("ca"));
+
+        set.insert_word("car");
+        assert_eq!(set.len(), 3);
+        set.insert_word("cart");
+        assert_eq!(set.words_with_prefix("car"), vec!["car", "cart"]);
+
+        assert!(set.remove_word("car"));
+        assert!(!set.remove_word("car"));
+        assert!(set.contains("cart"));
+        assert_eq!(set.words_with_prefix("ca"), vec!["cart", "cat"]);
+        assert!(set.words_with_prefix("x").is_empty());
+    }
+
+    #[test]
+    fn test_trie_set_algebra() {
+        let a = TrieSet::from_words(&["apple", "app", "banana"]);
+        let b = TrieSet::from_words(&["app", "banana", "cherry"]);
+
+        let union = a.union(&b);
+        assert_eq!(
+            union.iter().collect::<Vec<_>>(),
+            vec!["app", "apple", "banana", "cherry"]
+        );
+        assert_eq!(&a | &b, union);
+
+        let both = a.intersection(&b);
+        assert_eq!(both, TrieSet::from_words(&["app", "banana"]));
+        assert_eq!(&a & &b, both);
+        assert_eq!(b.intersection(&a), both);
+
+        let only_a = a.difference(&b);
+        assert_eq!(only_a, TrieSet::from_words(&["apple"]));
+        assert_eq!(&a - &b, only_a);
+        // "apple" must survive even though its prefix "app" is removed.
+        assert!(!only_a.contains("app"));
+
+        assert!(both.is_subset(&a));
+        assert!(both.is_subset(&b));
+        assert!(!a.is_subset(&b));
+        assert!(TrieSet::new().is_subset(&a));
+        assert!(a.is_subset(&union));
+
+        let mut c = a.clone();
+        c |= &b;
+        assert_eq!(c, union);
+        c &= &a;
+        assert_eq!(c, a);
+        c -= &b;
+        assert_eq!(c, only_a);
+        assert_eq!(c.as_trie().node_count(), 6);
+    }
+
+    #[test]
+    fn test_delete_very_long_key() {
+        let long_key = "a".repeat(100_000);
+        let mut trie = trie! { "aaa" => 1, long_key.as_str() => 2 };
+        assert_eq!(trie.node_count(), 100_001);
+
+        assert!(trie.delete(&long_key));
+        assert!(!trie.delete(&long_key));
+        assert_eq!(trie.len(), 1);
+        assert_eq!(trie.get("aaa"), Some(&1));
+        // Only the root and the nodes for "aaa" are left.
+        assert_eq!(trie.node_count(), 4);
+
+        let mut trie = trie! { long_key.as_str() => 1 };
+        assert_eq!(trie.pop(&long_key), Some(1));
+        assert_eq!(trie.node_count(), 1);
+        assert!(trie.is_empty());
+    }
+
+    #[test]
+    fn test_auto_complete_deep_chain() {
+        let key = "x".repeat(1_000);
+        let trie = trie! { key.as_str() => 1 };
+        assert_eq!(trie.node_count(), 1_001);
+        assert_eq!(trie.auto_complete("", 10), vec![key.clone()]);
+        assert_eq!(trie.auto_complete("xxx", 10), vec![key.clone()]);
+        assert_eq!(trie.auto_complete_with_values("x", 1), vec![(key, &1)]);
+    }
+
+    #[test]
+    fn test_from_sorted_input() {
+        let mut words = vec!["a", "an", "and", "ant", "apple", "b", "banana", "band", ""];
+        words.sort_unstable();
+        let trie = Trie::from_sorted_input(words.iter().map(|w| (w, w.len())));
+
+        let mut expected = Trie::new();
+        expected.add_word_list(&words, |w| w.len());
+        assert_eq!(trie, expected);
+        assert_eq!(trie.len(), words.len());
+        assert_eq!(trie.node_count(), expected.node_count());
+        assert_eq!(trie.get(""), Some(&0));
+        assert_eq!(trie.auto_complete("an", 10), vec!["an", "and", "ant"]);
+    }
+
+    #[test]
+    fn test_from_sorted_input_unsorted_and_duplicates() {
+        let trie =
+            Trie::from_sorted_input(vec![("ab", 1), ("b", 2), ("ac", 3), ("a", 4), ("ab", 5)]);
+        assert_eq!(trie, trie! { "ab" => 5, "b" => 2, "ac" => 3, "a" => 4 });
+        assert_eq!(trie.len(), 4);
+
+        let empty: Trie<i32> = Trie::from_sorted_input(Vec::<(String, i32)>::new());
+        assert!(empty.is_empty());
+        assert_eq!(empty.node_count(), 1);
+    }
+
+    #[test]
+    fn test_from_sorted_input_reopens_deep_branches() {
+        // Each out of order key comes back to a branch closed two or more
+        // levels below where it rejoins the previous key's path.
+        let trie = Trie::from_sorted_input([("abc", 1), ("b", 2), ("abd", 3)]);
+        assert_eq!(trie, trie! { "abc" => 1, "b" => 2, "abd" => 3 });
+        assert_eq!(trie.len(), 3);
+
+        let trie = Trie::from_sorted_input([("abc", 1), ("b", 2), ("abc", 3)]);
+        assert_eq!(trie, trie! { "abc" => 3, "b" => 2 });
+        assert_eq!(trie.len(), 2);
+
+        let items = [
+            ("wxyz", 1),
+            ("wxy", 2),
+            ("a", 3),
+            ("wxyzq", 4),
+            ("wx", 5),
+            ("wxya", 6),
+        ];
+        let expected: Trie<i32> = items.into_iter().collect();
+        let trie = Trie::from_sorted_input(items);
+        assert_eq!(trie, expected);
+        assert_eq!(trie.len(), expected.len());
+        assert_eq!(trie.node_count(), expected.node_count());
+    }
+
+    #[test]
+    fn test_get_longest_match() {
+        let mut trie = trie! { "/api" => 1, "/api/users" => 2, "/api/users/admin" => 3 };
+        assert_eq!(trie.get_longest_match("/api/users/42"), Some(&2));
+        assert_eq!(trie.get_longest_match("/api/users/admin/x"), Some(&3));
+        assert_eq!(trie.get_longest_match("/api/user"), Some(&1));
+        assert_eq!(trie.get_longest_match("/api"), Some(&1));
+        assert_eq!(trie.get_longest_match("/ap"), None);
+        assert_eq!(trie.get_longest_match(""), None);
+
+        for query in ["/api/users/42", "/ap", "/api/usersx", ""] {
+            assert_eq!(
+                trie.get_longest_match(query),
+                trie.longest_prefix_match(query).map(|(_, value)| value)
+            );
+        }
+
+        trie.insert("", 0);
+        assert_eq!(trie.get_longest_match("/static"), Some(&0));
+    }
+
+    #[test]
+    fn test_reverse_iteration() {
+        let trie = trie! {
+            "" => 0, "a" => 1, "ab" => 2, "abc" => 3, "abd" => 4, "b" => 5, "ba" => 6, "c" => 7,
+        };
+        let forward: Vec<String> = trie.keys().collect();
+        let backward: Vec<String> = trie.keys().rev().collect();
+        assert_eq!(backward, forward.iter().cloned().rev().collect::<Vec<_>>());
+        assert_eq!(backward, vec!["c", "ba", "b", "abd", "abc", "ab", "a", ""]);
+
+        let entries: Vec<(String, &i32)> = trie.iter().collect();
+        let mut reversed: Vec<(String, &i32)> = trie.iter().rev().collect();
+        reversed.reverse();
+        assert_eq!(entries, reversed);
+        assert_eq!(
+            trie.prefix_iter("ab").next_back(),
+            Some(("abd".to_string(), &4))
+        );
+        assert_eq!(
+            trie.auto_complete_iter("ab").rev().collect::<Vec<_>>(),
+            vec!["abd", "abc", "ab"]
+        );
+        assert_eq!(Trie::<i32>::new().keys().next_back(), None);
+    }
+
+    #[test]
+    fn test_interleaved_double_ended_iteration() {
+        let trie = trie! {
+            "" => 0, "a" => 1, "ab" => 2, "abc" => 3, "abd" => 4, "b" => 5, "ba" => 6, "c" => 7,
+        };
+        let expected: Vec<String> = trie.keys().collect();
+        // Every pattern of front/back calls must see each key exactly once.
+        for pattern in 0u32..(1 << expected.len()) {
+            let mut keys = trie.keys();
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for step in 0..expected.len() + 2 {
+                let item = if pattern & (1 << (step % expected.len())) == 0 {
+                    keys.next().map(|key| front.push(key))
+                } else {
+                    keys.next_back().map(|key| back.push(key))
+                };
+                if item.is_none() {
+                    break;
+                }
+            }
+            assert_eq!(keys.next(), None);
+            assert_eq!(keys.next_back(), None);
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, expected);
+        }
+    }
+
+    #[test]
+    fn test_trie_set_operations() {
+        // Every trie stores a key's length, so results can be compared exactly.
+        let make = |words: &[&str]| Trie::from_iter(words.iter().map(|w| (*w, w.len())));
+        let a = make(&["apple", "app", "banana", "cherry"]);
+        let b = make(&["app", "banana", "date", "ap"]);
+        let c = make(&["banana", "cherry", "date", "elder"]);
+
+        let union = a.union(&b);
+        assert_eq!(
+            union.keys().collect::<Vec<_>>(),
+            vec!["ap", "app", "apple", "banana", "cherry", "date"]
+        );
+        assert_eq!(&a | &b, union);
+        assert_eq!(a.union(&b), b.union(&a));
+        assert!(union.len() <= a.len() + b.len());
+
+        assert_eq!(&a & &b, make(&["app", "banana"]));
+        assert_eq!(a.intersection(&b), b.intersection(&a));
+        assert_eq!(&a - &b, make(&["apple", "cherry"]));
+        assert_eq!(&b - &a, make(&["date", "ap"]));
+
+        assert_eq!(&(&a | &b) | &c, &a | &(&b | &c));
+        assert_eq!(&(&a & &b) & &c, &a & &(&b & &c));
+        // Difference is not associative; repeated differences remove the union.
+        assert_eq!(&(&a - &b) - &c, &a - &(&b | &c));
+
+        assert_eq!(&a & &(&b | &c), &(&a & &b) | &(&a & &c));
+        assert_eq!(&a | &(&b & &c), &(&a | &b) & &(&a | &c));
+        assert_eq!(&a - &(&b & &c), &(&a - &b) | &(&a - &c));
+
+        assert_eq!(&a | &Trie::new(), a);
+        assert!((&a & &Trie::<()>::new()).is_empty());
+        assert!((&a - &a).is_empty());
+    }
+
+    #[test]
+    fn test_set_operations_keep_self_values() {
+        let scores = trie! { "cat" => 1, "dog" => 2 };
+        let other = trie! { "cat" => 10, "eel" => 3 };
+        assert_eq!(
+            scores.union(&other),
+            trie! { "cat" => 1, "dog" => 2, "eel" => 3 }
+        );
+        assert_eq!(
+            other.union(&scores),
+            trie! { "cat" => 10, "dog" => 2, "eel" => 3 }
+        );
+
+        let flags = trie! { "cat" => true, "cow" => false };
+        assert_eq!(&scores & &flags, trie! { "cat" => 1 });
+        assert_eq!(&scores - &flags, trie! { "dog" => 2 });
+    }
+
+    #[test]
+    fn test_contains_key_as_prefix_of() {
+        let mut trie = trie! { "app" => 1, "banana" => 2 };
+        assert!(trie.contains_key_as_prefix_of("applications"));
+        assert!(trie.contains_key_as_prefix_of("app"));
+        assert!(!trie.contains_key_as_prefix_of("ap"));
+        assert!(!trie.contains_key_as_prefix_of("ban"));
+        assert!(!trie.contains_key_as_prefix_of("bandana"));
+        assert!(!trie.contains_key_as_prefix_of(""));
+
+        trie.insert("", 0);
+        assert!(trie.contains_key_as_prefix_of("anything"));
+        assert!(!Trie::<()>::new().contains_key_as_prefix_of("app"));
+    }
+
+    #[test]
+    fn test_update_with_prefix() {
+        let mut trie = trie! {
+            "config.db.port" => 1,
+            "config.db.host" => 2,
+            "config" => 3,
+            "configure" => 4,
+            "other" => 5,
+        };
+        let mut seen = Vec::new();
+        let updated = trie.update_with_prefix("config.", |key, value| {
+            seen.push(key.to_string());
+            *value += 100;
+        });
+        assert_eq!(updated, 2);
+        seen.sort();
+        assert_eq!(seen, vec!["config.db.host", "config.db.port"]);
+        assert_eq!(trie.get("config.db.port"), Some(&101));
+        assert_eq!(trie.get("config.db.host"), Some(&102));
+        assert_eq!(trie.get("config"), Some(&3));
+        assert_eq!(trie.get("configure"), Some(&4));
+
+        // The prefix itself counts when it is a stored key.
+        assert_eq!(trie.update_with_prefix("config", |_, value| *value = 0), 4);
+        assert_eq!(trie.get("other"), Some(&5));
+        assert_eq!(trie.update_with_prefix("", |_, value| *value += 1), 5);
+        assert_eq!(trie.values().sum::<i32>(), 10);
+        assert_eq!(trie.update_with_prefix("missing", |_, _| unreachable!()), 0);
+    }
+
+    #[test]
+    fn test_common_prefix_of_all_keys() {
+        assert_eq!(Trie::<()>::new().common_prefix_of_all_keys(), "");
+
+        let mut trie = trie! { "application" => 1 };
+        assert_eq!(trie.common_prefix_of_all_keys(), "application");
+        trie.insert("application", 2);
+        assert_eq!(trie.common_prefix_of_all_keys(), "application");
+        trie.insert("applications", 3);
+        assert_eq!(trie.common_prefix_of_all_keys(), "application");
+        trie.insert("app", 4);
+        trie.insert("apple", 5);
+        assert_eq!(trie.common_prefix_of_all_keys(), "app");
+
+        let trie = trie! { "com.example.host" => 1, "com.example.port" => 2, "com.examples" => 3 };
+        assert_eq!(trie.common_prefix_of_all_keys(), "com.example");
+
+        let trie = trie! { "apple" => 1, "banana" => 2 };
+        assert_eq!(trie.common_prefix_of_all_keys(), "");
+        let trie = trie! { "" => 0, "apple" => 1 };
+        assert_eq!(trie.common_prefix_of_all_keys(), "");
+    }
+
+    #[test]
+    fn test_key_length_statistics() {
+        let empty: Trie<()> = Trie::new();
+        assert!(empty.key_length_histogram().is_empty());
+        assert_eq!(empty.max_key_length(), 0);
+        assert_eq!(empty.min_key_length(), 0);
+        assert_eq!(empty.avg_key_length(), 0.0);
+
+        let trie = trie! { "a" => 1, "ab" => 2, "cd" => 3, "éclair" => 4, "pineapple" => 5 };
+        let histogram = trie.key_length_histogram();
+        assert_eq!(
+            histogram.into_iter().collect::<Vec<_>>(),
+            vec![(1, 1), (2, 2), (6, 1), (9, 1)]
+        );
+        assert_eq!(
+            trie.key_length_histogram().values().sum::<usize>(),
+            trie.len()
+        );
+        assert_eq!(trie.max_key_length(), 9);
+        assert_eq!(trie.min_key_length(), 1);
+        assert_eq!(trie.avg_key_length(), 4.0);
+
+        let words: Vec<String> = (0..500).map(|i| format!("w{}", i * 7)).collect();
+        let mut trie = Trie::new();
+        trie.add_word_list(&words, |word| word.len());
+        assert_eq!(
+            trie.key_length_histogram().values().sum::<usize>(),
+            trie.len()
+        );
+        let mut with_empty = trie! { "" => 0 };
+        assert_eq!(with_empty.min_key_length(), 0);
+        with_empty.insert("xy", 2);
+        assert_eq!(with_empty.avg_key_length(), 1.0);
+    }
+
+    #[test]
+    fn test_depth_height_and_branching_factor() {
+        let empty: Trie<()> = Trie::new();
+        assert_eq!(empty.height(), 0);
+        assert_eq!(empty.average_key_length(), 0.0);
+        assert_eq!(empty.depth(""), None);
+        assert_eq!(empty.branching_factor_at(""), Some(0));
+        assert_eq!(empty.branching_factor_at("a"), None);
+
+        let single = trie!["word"];
+        assert_eq!(single.height(), 4);
+        assert_eq!(single.depth("word"), Some(4));
+        assert_eq!(single.depth("wor"), None);
+        assert_eq!(single.average_key_length(), 4.0);
+        assert_eq!(single.branching_factor_at("wo"), Some(1));
+        assert_eq!(single.branching_factor_at("word"), Some(0));
+
+        let letters = trie!["a", "b", "c"];
+        assert_eq!(letters.height(), 1);
+        assert_eq!(letters.depth("b"), Some(1));
+        assert_eq!(letters.branching_factor_at(""), Some(3));
+        assert_eq!(letters.average_key_length(), 1.0);
+
+        let mut trie = trie! { "a" => 1, "ab" => 2, "cd" => 3, "éclair" => 4, "pineapple" => 5 };
+        assert_eq!(trie.height(), trie.max_key_length());
+        assert_eq!(trie.average_key_length(), trie.avg_key_length());
+        assert_eq!(trie.depth("éclair"), Some(6));
+        assert!(trie.delete("pineapple"));
+        assert_eq!(trie.height(), 6);
+        assert_eq!(trie.branching_factor_at(""), Some(3));
+    }
+
     #[test]
-    fn test_new_trie_is_empty() {
-        let trie: Trie<i32> = Trie::new();
-        assert_eq!(trie.get("anything"), None);
-        assert!(!trie.prefix_search("anything"));
-        assert!(trie.auto_complete("", 10).is_empty());
+    fn test_frequency_trie() {
+        let mut counts = FrequencyTrie::new();
+        assert!(counts.is_empty());
+        assert_eq!(counts.frequency("the"), 0);
+
+        counts.count("the");
+        counts.count_all("the cat sat on the car near the card".split(' '));
+        counts.count_all(vec![String::from("car"), String::from("cat")]);
+        assert_eq!(counts.len(), 7);
+        assert_eq!(counts.frequency("the"), 4);
+        assert_eq!(counts.frequency("car"), 2);
+        assert_eq!(counts.frequency("ca"), 0);
+
+        assert_eq!(
+            counts.top_k_completions("ca", 10),
+            vec![
+                ("car".to_string(), 2),
+                ("cat".to_string(), 2),
+                ("card".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            counts.top_k_completions("", 1),
+            vec![("the".to_string(), 4)]
+        );
+        assert!(counts.top_k_completions("ca", 0).is_empty());
+        assert!(counts.top_k_completions("x", 3).is_empty());
+        assert_eq!(counts.as_trie().values().sum::<usize>(), 12);
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_default_constructor() {
-        let trie: Trie<i32> = Trie::default();
-        assert_eq!(trie.get("test"), None);
+    fn test_dawg_matches_trie() {
+        let stems = ["walk", "talk", "jump", "play", "stalk", "p"];
+        let endings = ["", "s", "ed", "ing", "er", "ers"];
+        let mut words = Vec::new();
+        for stem in stems {
+            for ending in endings {
+                words.push(format!("{stem}{ending}"));
+            }
+        }
+        words.push("pa".to_string());
+        let mut trie: Trie<()> = words.iter().collect();
+        trie.insert("", ());
+        let dawg = trie.clone().into_dawg();
+
+        assert_eq!(dawg.len(), trie.len());
+        assert!(dawg.node_count() * 3 < trie.node_count());
+        for word in &words {
+            assert!(dawg.contains(word), "{word}");
+        }
+        for query in ["", "wal", "walke", "walkings", "stal", "x", "pin", "ped"] {
+            assert_eq!(dawg.contains(query), trie.get(query).is_some(), "{query}");
+            assert_eq!(
+                dawg.prefix_search(query),
+                trie.prefix_search(query),
+                "{query}"
+            );
+            assert_eq!(
+                dawg.auto_complete(query, usize::MAX),
+                trie.auto_complete(query, usize::MAX)
+            );
+            assert_eq!(dawg.auto_complete(query, 3), trie.auto_complete(query, 3));
+        }
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_insert_and_get_single_word() {
+    fn test_dawg_keeps_values_apart() {
+        // "cats" and "dogs" end in the same letters but not the same values, so
+        // their last nodes must not be merged.
+        let trie = trie! { "cats" => 1, "dogs" => 2, "rats" => 1 };
+        let trie_nodes = trie.node_count();
+        let dawg = trie.into_dawg();
+        assert_eq!(dawg.get("cats"), Some(&1));
+        assert_eq!(dawg.get("dogs"), Some(&2));
+        assert_eq!(dawg.get("rats"), Some(&1));
+        assert_eq!(dawg.get("cat"), None);
+        assert!(dawg.node_count() < trie_nodes);
+
+        let empty = Trie::<u8>::new().into_dawg();
+        assert!(empty.is_empty());
+        assert_eq!(empty.node_count(), 1);
+        assert!(empty.auto_complete("", 10).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_save_and_load_file() {
+        let words: Vec<String> = (0..10_000).map(|i| format!("word{i}")).collect();
         let mut trie = Trie::new();
-        trie.insert("hello", &42);
+        trie.add_word_list(&words, |word| word.len() as u32);
 
-        assert_eq!(trie.get("hello"), Some(&42));
-        assert_eq!(trie.get("hell"), None);
-        assert_eq!(trie.get("hello_world"), None);
+        let file = tempfile::NamedTempFile::new().unwrap();
+        trie.save_to_file(file.path()).unwrap();
+        let loaded = Trie::<u32>::load_from_file(file.path()).unwrap();
+        assert_eq!(loaded, trie);
+        assert_eq!(loaded.len(), 10_000);
+
+        // Saving again replaces the previous contents.
+        let small = trie! { "only" => 1u32 };
+        small.save_to_file(file.path()).unwrap();
+        assert_eq!(Trie::<u32>::load_from_file(file.path()).unwrap(), small);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_load_file_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = Trie::<u32>::load_from_file(dir.path().join("missing.trh")).unwrap_err();
+        assert_eq!(missing.kind(), std::io::ErrorKind::NotFound);
+
+        let decode_error = |bytes: &[u8]| {
+            let file = tempfile::NamedTempFile::new().unwrap();
+            std::fs::write(file.path(), bytes).unwrap();
+            let err = Trie::<u32>::load_from_file(file.path()).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+            err.into_inner()
+                .unwrap()
+                .downcast::<DeserializeError>()
+                .map(|err| *err)
+                .unwrap()
+        };
+        let bytes = trie! { "apple" => 1u32 }.to_bytes();
+        assert_eq!(decode_error(&bytes[..2]), DeserializeError::UnexpectedEof);
+        assert_eq!(
+            decode_error(&bytes[..bytes.len() - 1]),
+            DeserializeError::UnexpectedEof
+        );
+        assert_eq!(decode_error(b"ZIP\x01\x00"), DeserializeError::BadMagic);
+        assert_eq!(
+            decode_error(b"TRH\x09\x00"),
+            DeserializeError::UnsupportedVersion(9)
+        );
+
+        // A directory cannot be written over as a file.
+        let err = Trie::<u32>::new().save_to_file(dir.path()).unwrap_err();
+        assert_ne!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_insert_from_reader() {
+        let input = "apple\n  banana \n\n   \ncherry\r\napple\nbanana split\n";
+        let mut trie = trie! { "cherry" => 0 };
+        let added = trie
+            .insert_from_reader(std::io::Cursor::new(input.as_bytes().to_vec()), |word| {
+                word.len()
+            })
+            .unwrap();
+        assert_eq!(added, 3);
+        assert_eq!(trie.len(), 4);
+        assert_eq!(trie.get("banana"), Some(&6));
+        assert_eq!(trie.get("cherry"), Some(&6));
+        assert_eq!(trie.get("banana split"), Some(&12));
         assert_eq!(trie.get(""), None);
+
+        let invalid = std::io::Cursor::new(vec![b'o', b'k', b'\n', 0xff, b'\n']);
+        let err = trie.insert_from_reader(invalid, |_| 0).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(trie.get("ok"), Some(&0));
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_insert_and_get_multiple_words() {
-        let mut trie = Trie::new();
-        trie.insert("cat", &1);
-        trie.insert("car", &2);
-        trie.insert("card", &3);
-        trie.insert("care", &4);
-        trie.insert("careful", &5);
+    fn test_export_to_writer_round_trip() {
+        let trie = trie! { "pear" => 1, "apple" => 2, "app" => 3, "über" => 4 };
+        let mut out = std::io::Cursor::new(Vec::new());
+        trie.export_to_writer(&mut out).unwrap();
+        let text = String::from_utf8(out.get_ref().clone()).unwrap();
+        assert_eq!(text, "app\napple\npear\nüber\n");
 
-        assert_eq!(trie.get("cat"), Some(&1));
-        assert_eq!(trie.get("car"), Some(&2));
-        assert_eq!(trie.get("card"), Some(&3));
-        assert_eq!(trie.get("care"), Some(&4));
-        assert_eq!(trie.get("careful"), Some(&5));
+        out.set_position(0);
+        let mut restored = Trie::new();
+        assert_eq!(restored.insert_from_reader(out, |_| ()).unwrap(), 4);
+        assert_eq!(
+            restored.keys().collect::<Vec<_>>(),
+            trie.keys().collect::<Vec<_>>()
+        );
 
-        // Test partial matches don't exist
-        assert_eq!(trie.get("ca"), None);
-        assert_eq!(trie.get("careless"), None);
+        let mut empty = Vec::new();
+        Trie::<()>::new().export_to_writer(&mut empty).unwrap();
+        assert!(empty.is_empty());
     }
 
     #[test]
-    fn test_update_existing_key() {
-        let mut trie = Trie::new();
-        trie.insert("test", &100);
-        assert_eq!(trie.get("test"), Some(&100));
+    fn test_diff_and_apply() {
+        let old = trie! { "app" => 1, "apple" => 2, "banana" => 3, "cherry" => 4, "" => 0 };
+        let new = trie! { "app" => 1, "apple" => 20, "apricot" => 5, "cherry" => 4, "date" => 6 };
 
-        // Update the same key
-        trie.insert("test", &200);
-        assert_eq!(trie.get("test"), Some(&200));
+        assert!(old.diff(&old).is_empty());
+        let diff = old.diff(&new);
+        assert_eq!(diff.len(), 5);
+        assert_eq!(
+            diff.added,
+            vec![("apricot".to_string(), 5), ("date".to_string(), 6)]
+        );
+        assert_eq!(diff.removed, vec!["".to_string(), "banana".to_string()]);
+        assert_eq!(diff.modified, vec![("apple".to_string(), 2, 20)]);
+
+        let mut a = old.clone();
+        a.diff(&new).apply_to(&mut a);
+        assert_eq!(a, new);
+        let mut b = old.clone();
+        b.apply_diff(old.diff(&new));
+        assert_eq!(b, new);
+
+        // The reverse diff undoes it.
+        b.apply_diff(new.diff(&old));
+        assert_eq!(b, old);
+
+        let empty = Trie::new();
+        assert_eq!(empty.diff(&old).added.len(), old.len());
+        assert_eq!(old.diff(&empty).removed.len(), old.len());
+    }
+
+    #[test]
+    fn test_apply_diff_to_drifted_trie() {
+        let diff = trie! { "a" => 1, "b" => 2 }.diff(&trie! { "b" => 3, "c" => 4 });
+        // "a" is already gone and "c" is already present: both are ignored or
+        // overwritten rather than treated as errors.
+        let mut drifted = trie! { "b" => 9, "c" => 0, "z" => 7 };
+        drifted.apply_diff(diff.clone());
+        assert_eq!(drifted, trie! { "b" => 3, "c" => 4, "z" => 7 });
+        drifted.apply_diff(diff);
+        assert_eq!(drifted, trie! { "b" => 3, "c" => 4, "z" => 7 });
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let mut trie = trie! { "apple" => 1, "banana" => 2 };
+        let saved = trie.snapshot();
+        trie.insert("cherry", 3);
+        trie.delete("apple");
+        *trie.get_mut("banana").unwrap() = 20;
+
+        let again = saved.clone();
+        trie.restore(saved);
+        assert_eq!(trie, trie! { "apple" => 1, "banana" => 2 });
+        assert_eq!(trie.len(), 2);
+
+        // A snapshot can be restored more than once through its clones.
+        trie.insert("date", 4);
+        trie.restore(again);
+        assert_eq!(trie.get("date"), None);
+    }
+
+    #[test]
+    fn test_transaction_commits_or_rolls_back() {
+        let mut trie = trie! { "apple" => 1 };
+
+        let result: Result<usize, String> = trie.transaction(|t| {
+            t.insert("banana", 2);
+            t.insert("cherry", 3);
+            Ok(t.len())
+        });
+        assert_eq!(result, Ok(3));
+        assert_eq!(trie.len(), 3);
+        assert_eq!(trie.get("cherry"), Some(&3));
+
+        let before = trie.clone();
+        let result: Result<(), String> = trie.transaction(|t| {
+            t.insert("date", 4);
+            t.delete("apple");
+            for (key, value) in [("elder", 5), ("", -1)] {
+                if value < 0 {
+                    return Err(format!("negative value for {key:?}"));
+                }
+                t.insert(key, value);
+            }
+            Ok(())
+        });
+        assert_eq!(result, Err("negative value for \"\"".to_string()));
+        assert_eq!(trie, before);
+        assert_eq!(trie.len(), 3);
+        assert_eq!(trie.get("elder"), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_timed_trie_expiry() {
+        let start = Instant::now();
+        let mut cache = TimedTrie::default();
+        cache.insert_with_ttl_at("short", 1, Duration::from_millis(30), start);
+        cache.insert_with_ttl_at("long", 2, Duration::from_secs(3600), start);
+        assert_eq!(cache.get_at("short", start), Some(&1));
+        assert_eq!(cache.get_at("long", start), Some(&2));
+        assert_eq!(cache.get_at("missing", start), None);
+
+        let later = start + Duration::from_millis(50);
+        assert_eq!(cache.get_at("short", later), None);
+        assert_eq!(cache.get_at("long", later), Some(&2));
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(cache.purge_expired_at(later), 1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.purge_expired_at(later), 0);
+        assert!(cache.remove("long"));
+        assert!(cache.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_timed_trie_with_explicit_clock() {
+        let mut cache = TimedTrie::new();
+        cache.insert_with_ttl("a", "x", Duration::from_secs(10));
+        cache.insert_with_ttl("ab", "y", Duration::from_secs(20));
+        cache.insert_with_ttl("abc", "z", Duration::from_secs(30));
+        let start = cache.expires_at("a").unwrap() - Duration::from_secs(10);
+
+        let later = start + Duration::from_secs(15);
+        assert_eq!(cache.get_at("a", later), None);
+        assert_eq!(cache.get_at("ab", later), Some(&"y"));
+        // An entry is expired from its expiry instant onwards.
+        assert_eq!(cache.get_at("ab", cache.expires_at("ab").unwrap()), None);
+
+        assert_eq!(cache.purge_expired_at(later), 1);
+        assert_eq!(cache.get_at("abc", later), Some(&"z"));
+        assert_eq!(cache.purge_expired_at(start + Duration::from_secs(60)), 2);
+        assert!(cache.is_empty());
+
+        // Re-inserting renews the expiry; an expired old value is not returned.
+        let mut counts = TimedTrie::new();
+        assert_eq!(counts.insert_with_ttl("k", 1, Duration::ZERO), None);
+        assert_eq!(
+            counts.insert_with_ttl("k", 2, Duration::from_secs(60)),
+            None
+        );
+        assert_eq!(
+            counts.insert_with_ttl("k", 3, Duration::from_secs(60)),
+            Some(2)
+        );
+        assert_eq!(counts.get("k"), Some(&3));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_round_trip() {
+        use crate::ffi::*;
+        use std::ffi::{CStr, CString};
+
+        let key = |s: &str| CString::new(s).unwrap();
+        let trie = trie_new();
+        unsafe {
+            trie_insert(trie, key("cat").as_ptr(), 1);
+            trie_insert(trie, key("car").as_ptr(), 2);
+            trie_insert(trie, key("dog").as_ptr(), 3);
+
+            let mut value = 0;
+            assert!(trie_get(trie, key("car").as_ptr(), &mut value));
+            assert_eq!(value, 2);
+            assert!(!trie_get(trie, key("ca").as_ptr(), &mut value));
+            assert!(trie_prefix_search(trie, key("ca").as_ptr()));
+            assert!(trie_delete(trie, key("dog").as_ptr()));
+            assert!(!trie_prefix_search(trie, key("d").as_ptr()));
+
+            let mut out = [std::ptr::null_mut(); 4];
+            let mut len = 0;
+            trie_auto_complete(trie, key("c").as_ptr(), 4, out.as_mut_ptr(), &mut len);
+            let words: Vec<String> = out[..len]
+                .iter()
+                .map(|&s| CStr::from_ptr(s).to_str().unwrap().to_string())
+                .collect();
+            assert_eq!(words, vec!["car", "cat"]);
+            out[..len].iter().for_each(|&s| trie_string_free(s));
+
+            // Null pointers are treated as missing rather than dereferenced
+            assert!(!trie_get(trie, std::ptr::null(), &mut value));
+            assert!(!trie_delete(std::ptr::null_mut(), key("cat").as_ptr()));
+            trie_free(trie);
+        }
+    }
+
+    #[test]
+    fn test_from_word_list_maps_words_to_indices() {
+        let trie: Trie<usize> = vec!["apple", "banana", "cherry"].into();
+        assert_eq!(trie.len(), 3);
+        assert_eq!(trie.get("apple"), Some(&0));
+        assert_eq!(trie.get("cherry"), Some(&2));
+
+        let owned = vec![String::from("apple"), String::from("banana")];
+        assert_eq!(Trie::from(owned).get("banana"), Some(&1));
+
+        let words: &[&str] = &["x", "y", "x"];
+        let trie = Trie::from(words);
+        assert_eq!(trie.len(), 2);
+        assert_eq!(trie.get("x"), Some(&2));
+        assert_eq!(trie.get("y"), Some(&1));
+
+        assert!(Trie::from(Vec::<&str>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_text_format_round_trip() {
+        let trie = trie! { "banana" => 2, "apple" => 1, "" => 0, "café" => 3 };
+        let text = trie.to_string();
+        assert_eq!(text, "=0\napple=1\nbanana=2\ncafé=3\n");
+        assert_eq!(Trie::<i32>::try_from(text.as_str()), Ok(trie));
+        assert_eq!(Trie::<i32>::new().to_string(), "");
+
+        let config = "# settings\n\nurl=http://x?a=b\nname=\nname=second\r\n#off=1\n";
+        let parsed = Trie::<String>::try_from(config).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed.get("url").map(String::as_str), Some("http://x?a=b"));
+        assert_eq!(parsed.get("name").map(String::as_str), Some("second"));
+        assert_eq!(
+            Trie::<String>::try_from(parsed.to_string().as_str()),
+            Ok(parsed)
+        );
+
+        let empty_value = Trie::<String>::try_from("key=").unwrap();
+        assert_eq!(empty_value.get("key").map(String::as_str), Some(""));
+    }
+
+    #[test]
+    fn test_text_format_skips_whitespace_only_lines() {
+        let config = "a=1\n   \n\t\nb=2\n \r\n";
+        let parsed = Trie::<u8>::try_from(config).unwrap();
+        assert_eq!(parsed, trie! { "a" => 1, "b" => 2 });
+    }
+
+    #[test]
+    fn test_text_format_errors() {
+        let err = Trie::<u8>::try_from("a=1\n# note\nb=300\nc=3").unwrap_err();
+        assert_eq!(err.line_number, 3);
+        assert_eq!(err.line, "b=300");
+        assert!(matches!(err.error, LineError::InvalidValue(_)));
+        assert!(
+            err.to_string()
+                .starts_with("line 3 (\"b=300\"): invalid value")
+        );
+        assert!(std::error::Error::source(&err).is_some());
+
+        assert_eq!(
+            Trie::<u8>::try_from("a=1\nno separator"),
+            Err(TryFromTrieError {
+                line_number: 2,
+                line: "no separator".to_string(),
+                error: LineError::MissingSeparator,
+            })
+        );
+        assert!(Trie::<u8>::try_from("a=").is_err());
+    }
+
+    #[test]
+    fn test_equal_tries_hash_equally() {
+        use std::collections::HashSet;
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = trie! { "apple" => 1, "app" => 2, "banana" => 3 };
+        let b = trie! { "banana" => 3, "apple" => 1, "app" => 2 };
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(hash_of(&a), hash_of(&a.clone()));
+
+        let mut changed_value = b.clone();
+        changed_value.insert("app", 5);
+        assert_ne!(hash_of(&a), hash_of(&changed_value));
+        let mut extra_key = b.clone();
+        extra_key.insert("apples", 1);
+        assert_ne!(hash_of(&a), hash_of(&extra_key));
+        // Keys are hashed with a terminator, so the split between them matters
+        assert_ne!(hash_of(&trie!["ab", "c"]), hash_of(&trie!["a", "bc"]));
+
+        let mut memo = HashMap::new();
+        memo.insert(a, "cached");
+        assert_eq!(memo.get(&b), Some(&"cached"));
+
+        let sets: HashSet<TrieSet> = [
+            TrieSet::from_words(&["x", "y"]),
+            TrieSet::from_words(&["y", "x"]),
+            TrieSet::from_words(&["x"]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(sets.len(), 2);
+    }
+
+    #[test]
+    fn test_tries_are_ordered_by_sorted_entries() {
+        use std::cmp::Ordering;
+        use std::collections::BTreeSet;
+
+        let a = trie! { "apple" => 1, "banana" => 2 };
+        let b = trie! { "banana" => 2, "apple" => 1 };
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+
+        // The first differing key decides, not the number of keys
+        let smaller_first_key = trie! { "aardvark" => 9, "zebra" => 9, "zoo" => 9 };
+        assert_eq!(smaller_first_key.cmp(&a), Ordering::Less);
+        assert!(trie! { "apple" => 1 } < a);
+
+        let same_keys_bigger_value = trie! { "apple" => 1, "banana" => 3 };
+        assert!(a < same_keys_bigger_value);
+
+        let empty = Trie::new();
+        assert!(empty < a);
+        assert!(empty < trie! { "" => 0 });
+        assert_eq!(empty.cmp(&Trie::new()), Ordering::Equal);
+
+        let floats = trie! { "x" => f64::NAN };
+        assert_eq!(floats.partial_cmp(&floats.clone()), None);
+
+        let sorted: Vec<Trie<i32>> = BTreeSet::from([a.clone(), same_keys_bigger_value, b, empty])
+            .into_iter()
+            .collect();
+        assert_eq!(sorted.len(), 3);
+        assert!(sorted.is_sorted());
+        assert_eq!(sorted[1], a);
+
+        assert!(TrieSet::from_words(&["a", "b"]) < TrieSet::from_words(&["b"]));
+    }
+
+    #[test]
+    fn test_trie_ref_views_a_subtree() {
+        let trie = trie! { "car" => 1, "cart" => 2, "cat" => 3, "dog" => 4 };
+        let whole = trie.as_ref();
+        assert_eq!(whole.get("cat"), Some(&3));
+        assert!(whole.prefix_search("do"));
+        assert_eq!(whole.iter().count(), trie.len());
+
+        let view = trie.node_at("ca").unwrap();
+        assert_eq!(view.get("r"), Some(&1));
+        assert_eq!(view.get(""), None);
+        assert!(view.prefix_search("rt"));
+        assert!(!view.prefix_search("d"));
+        assert_eq!(view.auto_complete("", 10), vec!["r", "rt", "t"]);
+        assert_eq!(view.auto_complete("r", 1), vec!["r"]);
+        assert!(view.auto_complete("x", 10).is_empty());
+        let entries: Vec<(String, &i32)> = view.into_iter().collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("r".to_string(), &1),
+                ("rt".to_string(), &2),
+                ("t".to_string(), &3)
+            ]
+        );
+        assert!(trie.node_at("cow").is_none());
+        assert_eq!(trie.node_at("dog").unwrap().get(""), Some(&4));
+    }
+
+    #[test]
+    fn test_cursor_navigation() {
+        let trie = trie! { "car" => 1, "cart" => 2, "cat" => 3 };
+        let mut cursor = trie.as_ref().cursor();
+        assert_eq!(cursor.current_key(), "");
+        assert!(!cursor.backtrack());
+        assert!(!cursor.advance('x'));
+        assert_eq!(cursor.depth(), 0);
+
+        assert!(cursor.advance('c'));
+        assert!(cursor.advance('a'));
+        assert_eq!(cursor.child_chars().collect::<Vec<_>>(), vec!['r', 't']);
+        assert_eq!(cursor.value(), None);
+        assert!(!cursor.advance('b'));
+        assert_eq!(cursor.current_key(), "ca");
+
+        assert!(cursor.advance('r'));
+        assert_eq!(cursor.value(), Some(&1));
+        let mut deeper = cursor.clone();
+        assert!(deeper.advance('t'));
+        assert_eq!((deeper.current_key(), deeper.value()), ("cart", Some(&2)));
+        assert_eq!(deeper.child_chars().count(), 0);
+        assert_eq!(cursor.current_key(), "car");
+
+        assert!(cursor.backtrack());
+        assert!(cursor.advance('t'));
+        assert_eq!((cursor.current_key(), cursor.depth()), ("cat", 3));
+        assert_eq!(cursor.view().get(""), Some(&3));
+
+        // A cursor from a view starts with an empty key at the view's node
+        let mut from_view = trie.node_at("car").unwrap().cursor();
+        assert_eq!(from_view.value(), Some(&1));
+        assert!(from_view.advance('t'));
+        assert_eq!(from_view.current_key(), "t");
+    }
+
+    #[test]
+    fn test_cursor_supports_external_walks() {
+        // A depth-first walk written only against the Cursor API visits the
+        // same keys, in the same order, as the Trie's own iterator
+        fn walk(cursor: &mut Cursor<'_, i32>, keys: &mut Vec<(String, i32)>) {
+            if let Some(&value) = cursor.value() {
+                keys.push((cursor.current_key().to_string(), value));
+            }
+            for c in cursor.child_chars() {
+                assert!(cursor.advance(c));
+                walk(cursor, keys);
+                assert!(cursor.backtrack());
+            }
+        }
+
+        let trie = trie! { "" => 0, "car" => 1, "cart" => 2, "cat" => 3, "dog" => 4 };
+        let mut cursor = trie.cursor();
+        let mut keys = Vec::new();
+        walk(&mut cursor, &mut keys);
+
+        let expected: Vec<(String, i32)> = trie.iter().map(|(key, &value)| (key, value)).collect();
+        assert_eq!(keys, expected);
+        // Every advance was undone, so the cursor is back at the root
+        assert_eq!((cursor.current_key(), cursor.depth()), ("", 0));
+    }
+
+    #[test]
+    fn test_prefix_match_results() {
+        let trie = trie! { "app" => 1, "apple" => 2, "banana" => 3 };
+        assert_eq!(trie.prefix_match(""), PrefixMatchResult::PrefixOfStoredKey);
+        assert_eq!(
+            trie.prefix_match("ap"),
+            PrefixMatchResult::PrefixOfStoredKey
+        );
+        assert_eq!(trie.prefix_match("app"), PrefixMatchResult::ExactAndPrefix);
+        assert_eq!(
+            trie.prefix_match("apple"),
+            PrefixMatchResult::ExactStoredKey
+        );
+        assert_eq!(
+            trie.prefix_match("banana"),
+            PrefixMatchResult::ExactStoredKey
+        );
+        assert_eq!(trie.prefix_match("apples"), PrefixMatchResult::NoMatch);
+        assert_eq!(trie.prefix_match("c"), PrefixMatchResult::NoMatch);
+
+        let empty: Trie<i32> = Trie::new();
+        assert_eq!(empty.prefix_match(""), PrefixMatchResult::NoMatch);
+        // prefix_search keeps its original answer for the empty prefix.
+        assert!(empty.prefix_search(""));
+        assert_eq!(
+            trie! { "" => 0 }.prefix_match(""),
+            PrefixMatchResult::ExactStoredKey
+        );
+
+        for prefix in ["", "a", "app", "apple", "apples", "b", "z"] {
+            assert_eq!(
+                trie.prefix_search(prefix),
+                trie.prefix_match(prefix) != PrefixMatchResult::NoMatch
+            );
+        }
+    }
+
+    #[test]
+    fn test_auto_complete_with_spans() {
+        let trie = trie! { "café" => 1, "cafés" => 2, "car" => 3 };
+        let results = trie.auto_complete_with_spans("caf", 10);
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert_eq!(result.matched_char_range, 0..3);
+            assert_eq!(result.matched(), "caf");
+            assert_eq!(trie.get(&result.key), Some(result.value));
+        }
+        assert_eq!(results[1].key, "cafés");
+
+        // Ranges count characters, so a multi-byte prefix still slices cleanly
+        let accented = trie.auto_complete_with_spans("café", 10);
+        assert_eq!(accented[0].matched_char_range, 0..4);
+        assert_eq!(accented[1].matched(), "café");
+        let chars: String = accented[1]
+            .key
+            .chars()
+            .skip(accented[1].matched_char_range.end)
+            .collect();
+        assert_eq!(chars, "s");
+
+        let everything = trie.auto_complete_with_spans("", 2);
+        assert_eq!(everything.len(), 2);
+        assert_eq!(everything[0].matched(), "");
+        assert!(trie.auto_complete_with_spans("x", 10).is_empty());
+        assert_eq!(
+            trie.auto_complete_with_spans("ca", 10)
+                .into_iter()
+                .map(|result| result.key)
+                .collect::<Vec<_>>(),
+            trie.auto_complete("ca", 10)
+        );
+    }
+
+    #[test]
+    fn test_suffix_trie_substring_search() {
+        let mut names = SuffixTrie::new();
+        assert!(!names.contains_substring(""));
+        assert_eq!(names.insert("hello", 1), None);
+        names.insert("yellow", 2);
+        names.insert("help", 3);
+        assert_eq!(names.insert("hello", 4), Some(1));
+        assert_eq!(names.len(), 3);
+        assert_eq!(names.get("hello"), Some(&4));
+        assert_eq!(names.get("ello"), None);
+
+        // Prefix, infix and suffix patterns
+        assert_eq!(names.keys_containing("hel"), vec!["hello", "help"]);
+        assert_eq!(names.keys_containing("ell"), vec!["hello", "yellow"]);
+        assert_eq!(names.keys_containing("low"), vec!["yellow"]);
+        assert_eq!(names.keys_containing("l"), vec!["hello", "help", "yellow"]);
+        assert!(names.contains_substring("lp"));
+        assert!(!names.contains_substring("lol"));
+        assert!(names.keys_containing("xyz").is_empty());
+        assert_eq!(names.keys_containing("").len(), 3);
+
+        assert_eq!(names.remove("hello"), Some(4));
+        assert_eq!(names.remove("hello"), None);
+        assert_eq!(names.keys_containing("ell"), vec!["yellow"]);
+        assert!(!names.contains_substring("hell"));
+        assert_eq!(names.as_trie().len(), 2);
+
+        let mut with_empty = SuffixTrie::new();
+        with_empty.insert("", 0);
+        with_empty.insert("naïve", 1);
+        assert_eq!(with_empty.keys_containing(""), vec!["", "naïve"]);
+        assert_eq!(with_empty.keys_containing("ïv"), vec!["naïve"]);
+        assert_eq!(with_empty.remove(""), Some(0));
+        assert_eq!(with_empty.keys_containing(""), vec!["naïve"]);
+    }
+
+    #[test]
+    #[cfg(feature = "smallvec-children")]
+    fn test_small_map_stays_sorted_past_inline_capacity() {
+        use crate::small_map::{INLINE_CHILDREN, SmallMap};
+
+        let mut map: SmallMap<char, usize> = SmallMap::new();
+        let keys = ['m', 'c', 'x', 'a', 'q', 'f', 'z'];
+        assert!(keys.len() > INLINE_CHILDREN);
+        for (index, &key) in keys.iter().enumerate() {
+            assert_eq!(map.insert(key, index), None);
+        }
+        assert_eq!(map.insert('q', 40), Some(4));
+        *map.entry('b').or_insert_with(|| 0) += 7;
+        *map.entry('m').or_insert_with(|| 0) += 10;
+
+        let entries: Vec<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(
+            entries,
+            vec![
+                ('a', 3),
+                ('b', 7),
+                ('c', 1),
+                ('f', 5),
+                ('m', 10),
+                ('q', 40),
+                ('x', 2),
+                ('z', 6)
+            ]
+        );
+        assert_eq!(map.remove(&'c'), Some(1));
+        assert_eq!(map.remove(&'c'), None);
+        assert_eq!(map.get(&'f'), Some(&5));
+        assert_eq!(map.get(&'d'), None);
+        assert_eq!(map.len(), 7);
+        let keys: String = map.into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, "abfmqxz");
+    }
+
+    #[test]
+    fn test_with_hasher_matches_default_hasher() {
+        let words = ["apple", "app", "application", "banana", "band", "", "日本"];
+        let mut default_trie = Trie::new();
+        let mut ahash_trie = Trie::with_hasher(ahash::RandomState::new());
+        for (index, word) in words.iter().enumerate() {
+            assert_eq!(
+                default_trie.insert(word, index),
+                ahash_trie.insert(word, index)
+            );
+        }
+
+        assert_eq!(
+            default_trie.iter().collect::<Vec<_>>(),
+            ahash_trie.iter().collect::<Vec<_>>()
+        );
+        for query in ["app", "ban", "x", ""] {
+            assert_eq!(
+                default_trie.auto_complete(query, 10),
+                ahash_trie.auto_complete(query, 10)
+            );
+            assert_eq!(
+                default_trie.prefix_search(query),
+                ahash_trie.prefix_search(query)
+            );
+        }
+        *ahash_trie.entry("band").or_insert(0) += 10;
+        *default_trie.entry("band").or_insert(0) += 10;
+        assert_eq!(ahash_trie.delete("app"), default_trie.delete("app"));
+        assert_eq!(ahash_trie.delete_prefix("ban"), 2);
+        assert_eq!(default_trie.delete_prefix("ban"), 2);
+        assert_eq!(ahash_trie.len(), default_trie.len());
+        assert_eq!(
+            ahash_trie.into_iter().collect::<BTreeMap<_, _>>(),
+            default_trie.into_iter().collect::<BTreeMap<_, _>>()
+        );
+    }
+
+    #[test]
+    fn test_with_hasher_results_keep_the_hasher() {
+        type FixedState = core::hash::BuildHasherDefault<std::collections::hash_map::DefaultHasher>;
+
+        let mut trie: Trie<i32, FixedState> = Trie::default();
+        trie.extend([("cat", 1), ("car", 2), ("dog", 3)]);
+        let other: Trie<i32, FixedState> = [("car", 0)].into_iter().collect();
+
+        let cars: Trie<i32, FixedState> = &trie & &other;
+        assert_eq!(cars.keys().collect::<Vec<_>>(), vec!["car"]);
+        let rest = trie.difference(&other);
+        assert_eq!(rest.keys().collect::<Vec<_>>(), vec!["cat", "dog"]);
+        let mut copy = trie.subtrie("ca").unwrap();
+        copy.insert("nary", 4);
+        assert_eq!(copy.as_ref().get("nary"), Some(&4));
+        assert_eq!(trie.drain().count(), 3);
+        assert!(trie.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_regex_match_anchored() {
+        let trie: Trie<i32> = [("abcd12", 1), ("abcd123", 2), ("ab12", 3), ("xabcd12", 4)]
+            .into_iter()
+            .collect();
+
+        let keys = |pattern| -> Vec<String> {
+            trie.regex_match(pattern)
+                .unwrap()
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect()
+        };
+        assert_eq!(
+            trie.regex_match(r"^[a-z]{4}\d{2}$").unwrap(),
+            vec![("abcd12".to_string(), &1)]
+        );
+        assert_eq!(keys(r"^abcd"), vec!["abcd12", "abcd123"]);
+        assert_eq!(
+            keys(r"\d{2}$"),
+            vec!["ab12", "abcd12", "abcd123", "xabcd12"]
+        );
+        assert_eq!(keys(r"3$"), vec!["abcd123"]);
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_regex_match_unanchored_and_empty() {
+        let mut trie: Trie<i32> = [("cat", 1), ("concat", 2), ("dog", 3), ("café", 4)]
+            .into_iter()
+            .collect();
+        trie.insert("", 0);
+
+        let keys = |pattern| -> Vec<String> {
+            trie.regex_match(pattern)
+                .unwrap()
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect()
+        };
+        // Unanchored patterns match anywhere in a key
+        assert_eq!(keys("cat"), vec!["cat", "concat"]);
+        assert_eq!(keys("é$"), vec!["café"]);
+        // The empty pattern matches every key, including the empty one
+        assert_eq!(keys(""), vec!["", "café", "cat", "concat", "dog"]);
+        assert_eq!(keys("^$"), vec![""]);
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_regex_match_no_matches_and_invalid_pattern() {
+        let trie: Trie<i32> = [("cat", 1), ("dog", 2)].into_iter().collect();
+        assert!(trie.regex_match(r"^\d+$").unwrap().is_empty());
+        assert!(trie.regex_match("bird").unwrap().is_empty());
+        assert!(Trie::<i32>::new().regex_match(".*").unwrap().is_empty());
+        assert!(trie.regex_match("(unclosed").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_router_trie_extracts_params() {
+        let mut router = RouterTrie::new();
+        assert_eq!(router.register("/api/users/:id/posts/:post", 1), None);
+        assert_eq!(router.register("/api/users/:user", 2), None);
+        assert_eq!(router.len(), 2);
+
+        let (handler, params) = router.route("/api/users/42/posts/7").unwrap();
+        assert_eq!(*handler, 1);
+        assert_eq!(params, HashMap::from([("id", "42"), ("post", "7")]));
+
+        // Routes name the same position independently
+        let (handler, params) = router.route("/api/users/ada").unwrap();
+        assert_eq!(*handler, 2);
+        assert_eq!(params, HashMap::from([("user", "ada")]));
+
+        // Same shape with other names replaces the route
+        assert_eq!(router.register("api/users/:name/", 3), Some(2));
+        assert_eq!(router.route("/api/users/ada/").unwrap().1["name"], "ada");
+        assert_eq!(router.len(), 2);
+
+        assert!(router.route("/api/users").is_none());
+        assert!(router.route("/api/users/42/posts").is_none());
+        assert!(router.route("/api/teams/42").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_router_trie_specificity_and_backtracking() {
+        let mut router = RouterTrie::new();
+        router.register("/files/**", "catch_all");
+        router.register("/files/:name", "param");
+        router.register("/files/readme", "literal");
+        router.register("/files/:dir/raw", "raw");
+        router.register("/files/docs/index", "docs_index");
+
+        assert_eq!(router.route("/files/readme").unwrap().0, &"literal");
+        assert_eq!(router.route("/files/notes").unwrap().0, &"param");
+        // "docs" matches a literal, but nothing under it ends in "raw", so the
+        // parameter gets its turn
+        let (handler, params) = router.route("/files/docs/raw").unwrap();
+        assert_eq!((*handler, params["dir"]), ("raw", "docs"));
+
+        let (handler, params) = router.route("/files/a/b/c/").unwrap();
+        assert_eq!((*handler, params["**"]), ("catch_all", "a/b/c"));
+        // The catch-all also matches nothing at all
+        assert_eq!(router.route("/files").unwrap().1["**"], "");
+        assert!(router.route("/other").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(expected = "`**` must be the last segment")]
+    fn test_router_trie_rejects_inner_catch_all() {
+        let mut router = RouterTrie::new();
+        router.register("/static/**/raw", ());
     }
 
     #[test]
     fn test_empty_string_insertion() {
         let mut trie = Trie::new();
-        trie.insert("", &42);
+        trie.insert("", 42);
         assert_eq!(trie.get(""), Some(&42));
         assert_eq!(trie.get("a"), None);
     }
 
     #[test]
     fn test_single_character_words() {
-        let mut trie = Trie::new();
-        trie.insert("a", &1);
-        trie.insert("I", &2);
-        trie.insert("x", &3);
+        let trie = trie! { "a" => 1, "I" => 2, "x" => 3 };
 
         assert_eq!(trie.get("a"), Some(&1));
         assert_eq!(trie.get("I"), Some(&2));
@@ -83,10 +4570,7 @@ mod tests {
 
     #[test]
     fn test_prefix_search() {
-        let mut trie = Trie::new();
-        trie.insert("hello", &1);
-        trie.insert("help", &2);
-        trie.insert("hero", &3);
+        let trie = trie! { "hello" => 1, "help" => 2, "hero" => 3 };
 
         assert!(trie.prefix_search("he"));
         assert!(trie.prefix_search("hel"));
@@ -105,41 +4589,40 @@ mod tests {
 
     #[test]
     fn test_auto_complete_basic() {
-        let mut trie = Trie::new();
-        trie.insert("cat", &1);
-        trie.insert("car", &2);
-        trie.insert("card", &3);
-        trie.insert("care", &4);
-        trie.insert("careful", &5);
-        trie.insert("dog", &6);
+        let trie = trie! {
+            "cat" => 1,
+            "car" => 2,
+            "card" => 3,
+            "care" => 4,
+            "careful" => 5,
+            "dog" => 6,
+        };
 
-        let mut results = trie.auto_complete("car", 10);
-        results.sort(); // Sort for predictable testing
+        let results = trie.sorted_auto_complete("car", 10);
 
         // Now that the bug is fixed, "car" itself should be included
-        let mut expected = vec![
+        let expected = vec![
             "car".to_string(),
             "card".to_string(),
             "care".to_string(),
             "careful".to_string(),
         ];
-        expected.sort();
         assert_eq!(results, expected);
     }
 
     #[test]
     fn test_auto_complete_max_results() {
-        let mut trie = Trie::new();
-        trie.insert("test", &1);
-        trie.insert("testing", &2);
-        trie.insert("tester", &3);
-        trie.insert("tests", &4);
-        trie.insert("testimony", &5);
+        let trie = trie! {
+            "test" => 1,
+            "testing" => 2,
+            "tester" => 3,
+            "tests" => 4,
+            "testimony" => 5,
+        };
 
         // Test with max_results = 2
         let results = trie.auto_complete("test", 2);
-        assert_eq!(results.len(), 2);
-        assert!(results.contains(&"test".to_string()));
+        assert_eq!(results, vec!["test", "tester"]);
 
         // Test with max_results = 0
         let results = trie.auto_complete("test", 0);
@@ -152,9 +4635,7 @@ mod tests {
 
     #[test]
     fn test_auto_complete_no_matches() {
-        let mut trie = Trie::new();
-        trie.insert("hello", &1);
-        trie.insert("world", &2);
+        let trie = trie! { "hello" => 1, "world" => 2 };
 
         let results = trie.auto_complete("xyz", 10);
         assert!(results.is_empty());
@@ -162,9 +4643,7 @@ mod tests {
 
     #[test]
     fn test_auto_complete_single_result() {
-        let mut trie = Trie::new();
-        trie.insert("unique", &1);
-        trie.insert("cat", &2);
+        let trie = trie! { "unique" => 1, "cat" => 2 };
 
         let results = trie.auto_complete("uni", 10);
         assert_eq!(results, vec!["unique".to_string()]);
@@ -172,23 +4651,17 @@ mod tests {
 
     #[test]
     fn test_auto_complete_prefix_is_word() {
-        let mut trie = Trie::new();
-        trie.insert("go", &1);
-        trie.insert("gone", &2);
-        trie.insert("going", &3);
-        trie.insert("good", &4);
+        let trie = trie! { "go" => 1, "gone" => 2, "going" => 3, "good" => 4 };
 
         // Test that when the prefix itself is a complete word, it's included
-        let mut results = trie.auto_complete("go", 10);
-        results.sort();
+        let results = trie.sorted_auto_complete("go", 10);
 
-        let mut expected = vec![
+        let expected = vec![
             "go".to_string(),
-            "gone".to_string(),
             "going".to_string(),
+            "gone".to_string(),
             "good".to_string(),
         ];
-        expected.sort();
         assert_eq!(results, expected);
 
         // Test another case where prefix exactly matches a word
@@ -198,30 +4671,24 @@ mod tests {
 
     #[test]
     fn test_auto_complete_empty_prefix() {
-        let mut trie = Trie::new();
-        trie.insert("a", &1);
-        trie.insert("b", &2);
+        let trie = trie! { "a" => 1, "b" => 2 };
 
-        let mut results = trie.auto_complete("", 10);
-        results.sort();
+        let results = trie.sorted_auto_complete("", 10);
 
-        let mut expected = vec!["a".to_string(), "b".to_string()];
-        expected.sort();
+        let expected = vec!["a".to_string(), "b".to_string()];
         assert_eq!(results, expected);
     }
 
     #[test]
     fn test_auto_complete_empty_prefix_with_root_value() {
         let mut trie = Trie::new();
-        trie.insert("", &42); // Empty string as a word
-        trie.insert("a", &1);
-        trie.insert("b", &2);
+        trie.insert("", 42); // Empty string as a word
+        trie.insert("a", 1);
+        trie.insert("b", 2);
 
-        let mut results = trie.auto_complete("", 10);
-        results.sort();
+        let results = trie.sorted_auto_complete("", 10);
 
-        let mut expected = vec!["".to_string(), "a".to_string(), "b".to_string()];
-        expected.sort();
+        let expected = vec!["".to_string(), "a".to_string(), "b".to_string()];
         assert_eq!(results, expected);
     }
 
@@ -230,24 +4697,21 @@ mod tests {
         let mut trie = Trie::new();
         // Insert many words with same prefix
         for i in 0..20 {
-            trie.insert(&format!("test{}", i), &i);
+            trie.insert(&format!("test{}", i), i);
         }
 
         let results = trie.auto_complete("test", 5);
-        assert_eq!(results.len(), 5);
 
-        // Should include some of the words, but not all 20
-        for result in &results {
-            assert!(result.starts_with("test"));
-        }
+        // The first five in lexicographic order, not all 20
+        assert_eq!(
+            results,
+            vec!["test0", "test1", "test10", "test11", "test12"]
+        );
     }
 
     #[test]
     fn test_delete_leaf_word() {
-        let mut trie = Trie::new();
-        trie.insert("cat", &1);
-        trie.insert("car", &2);
-        trie.insert("card", &3);
+        let mut trie = trie! { "cat" => 1, "car" => 2, "card" => 3 };
 
         assert!(trie.delete("card"));
         assert_eq!(trie.get("card"), None);
@@ -257,10 +4721,7 @@ mod tests {
 
     #[test]
     fn test_delete_word_with_children() {
-        let mut trie = Trie::new();
-        trie.insert("car", &1);
-        trie.insert("card", &2);
-        trie.insert("care", &3);
+        let mut trie = trie! { "car" => 1, "card" => 2, "care" => 3 };
 
         assert!(trie.delete("car"));
         assert_eq!(trie.get("car"), None);
@@ -274,7 +4735,7 @@ mod tests {
     #[test]
     fn test_delete_nonexistent_word() {
         let mut trie = Trie::new();
-        trie.insert("hello", &1);
+        trie.insert("hello", 1);
 
         assert!(!trie.delete("world"));
         assert!(!trie.delete("hell"));
@@ -284,9 +4745,7 @@ mod tests {
 
     #[test]
     fn test_delete_empty_string() {
-        let mut trie = Trie::new();
-        trie.insert("", &42);
-        trie.insert("hello", &1);
+        let mut trie = trie! { "" => 42, "hello" => 1 };
 
         // Current implementation returns false for empty string deletion
         // This is a design choice - empty string deletion is not allowed
@@ -299,9 +4758,7 @@ mod tests {
 
     #[test]
     fn test_delete_cleans_up_unused_nodes() {
-        let mut trie = Trie::new();
-        trie.insert("car", &1);
-        trie.insert("card", &2);
+        let mut trie = trie! { "car" => 1, "card" => 2 };
 
         // Delete "card" - should clean up 'd' node
         assert!(trie.delete("card"));
@@ -375,38 +4832,31 @@ mod tests {
     fn test_different_value_types() {
         // Test with String values
         let mut string_trie = Trie::new();
-        string_trie.insert("key", &"value".to_string());
+        string_trie.insert("key", "value".to_string());
         assert_eq!(string_trie.get("key"), Some(&"value".to_string()));
 
         // Test with boolean values
-        let mut bool_trie = Trie::new();
-        bool_trie.insert("true_key", &true);
-        bool_trie.insert("false_key", &false);
+        let bool_trie = trie! { "true_key" => true, "false_key" => false };
         assert_eq!(bool_trie.get("true_key"), Some(&true));
         assert_eq!(bool_trie.get("false_key"), Some(&false));
 
         // Test with tuple values
         let mut tuple_trie = Trie::new();
-        tuple_trie.insert("point", &(10, 20));
+        tuple_trie.insert("point", (10, 20));
         assert_eq!(tuple_trie.get("point"), Some(&(10, 20)));
 
         // Test with Option values
-        let mut option_trie = Trie::new();
-        option_trie.insert("some", &Some(42));
-        option_trie.insert("none", &None);
+        let option_trie = trie! { "some" => Some(42), "none" => None };
         assert_eq!(option_trie.get("some"), Some(&Some(42)));
         assert_eq!(option_trie.get("none"), Some(&None));
     }
 
     #[test]
     fn test_unicode_support() {
-        let mut trie = Trie::new();
-        trie.insert("café", &1);
-        trie.insert("naïve", &2);
-        trie.insert("résumé", &3);
-        trie.insert("🦀", &4); // Rust crab emoji
-        trie.insert("你好", &5); // Chinese characters
-        trie.insert("مرحبا", &6); // Arabic
+        let mut trie = trie! { "café" => 1, "naïve" => 2, "résumé" => 3 };
+        trie.insert("🦀", 4); // Rust crab emoji
+        trie.insert("你好", 5); // Chinese characters
+        trie.insert("مرحبا", 6); // Arabic
 
         assert_eq!(trie.get("café"), Some(&1));
         assert_eq!(trie.get("naïve"), Some(&2));
@@ -430,10 +4880,7 @@ mod tests {
 
     #[test]
     fn test_case_sensitivity() {
-        let mut trie = Trie::new();
-        trie.insert("Hello", &1);
-        trie.insert("hello", &2);
-        trie.insert("HELLO", &3);
+        let trie = trie! { "Hello" => 1, "hello" => 2, "HELLO" => 3 };
 
         assert_eq!(trie.get("Hello"), Some(&1));
         assert_eq!(trie.get("hello"), Some(&2));
@@ -451,8 +4898,8 @@ mod tests {
         let long_word = "supercalifragilisticexpialidocious";
         let very_long_word = "pneumonoultramicroscopicsilicovolcanoconiosisverylongword";
 
-        trie.insert(long_word, &42);
-        trie.insert(very_long_word, &99);
+        trie.insert(long_word, 42);
+        trie.insert(very_long_word, 99);
 
         assert_eq!(trie.get(long_word), Some(&42));
         assert_eq!(trie.get(very_long_word), Some(&99));
@@ -468,12 +4915,7 @@ mod tests {
 
     #[test]
     fn test_overlapping_words() {
-        let mut trie = Trie::new();
-        trie.insert("test", &1);
-        trie.insert("testing", &2);
-        trie.insert("tester", &3);
-        trie.insert("te", &4);
-        trie.insert("t", &5);
+        let trie = trie! { "test" => 1, "testing" => 2, "tester" => 3, "te" => 4, "t" => 5 };
 
         assert_eq!(trie.get("t"), Some(&5));
         assert_eq!(trie.get("te"), Some(&4));
@@ -481,22 +4923,19 @@ mod tests {
         assert_eq!(trie.get("tester"), Some(&3));
         assert_eq!(trie.get("testing"), Some(&2));
 
-        let mut results = trie.auto_complete("test", 10);
-        results.sort();
+        let results = trie.sorted_auto_complete("test", 10);
 
         // Now that the bug is fixed, "test" itself should be included
-        let mut expected = vec![
+        let expected = vec![
             "test".to_string(),
             "tester".to_string(),
             "testing".to_string(),
         ];
-        expected.sort();
         assert_eq!(results, expected);
 
         // Test with limited results
         let results_limited = trie.auto_complete("test", 2);
-        assert_eq!(results_limited.len(), 2);
-        assert!(results_limited.contains(&"test".to_string()));
+        assert_eq!(results_limited, vec!["test", "tester"]);
     }
 
     #[test]
@@ -506,7 +4945,7 @@ mod tests {
         // Insert many words
         for i in 0..1000 {
             let word = format!("word{:04}", i);
-            trie.insert(&word, &i);
+            trie.insert(&word, i);
         }
 
         // Verify all insertions
@@ -529,6 +4968,14 @@ mod tests {
 
         let results_all = trie.auto_complete("word", 2000);
         assert_eq!(results_all.len(), 1000); // Should return all 1000 words
+        assert_eq!(trie.len(), 1000);
+
+        // Deleting every word brings the count back to zero
+        for i in 0..1000 {
+            assert!(trie.delete(&format!("word{:04}", i)));
+        }
+        assert_eq!(trie.len(), 0);
+        assert!(trie.is_empty());
     }
 
     #[test]
@@ -552,6 +4999,30 @@ mod tests {
         // Test autocomplete
         let results = trie.auto_complete("item_", 50);
         assert_eq!(results.len(), 50);
+        assert_eq!(trie.len(), 500);
+    }
+
+    #[test]
+    fn test_len_counts_unique_keys() {
+        let mut trie = trie! { "car" => 1, "card" => 2 };
+        assert_eq!(trie.len(), 2);
+
+        // Updating an existing key does not change the count
+        trie.insert("car", 3);
+        assert_eq!(trie.len(), 2);
+
+        // add_word_list only counts words that were not already present
+        trie.add_word_list(&["car", "cart", "care"], |_| 0);
+        assert_eq!(trie.len(), 4);
+
+        // Failed deletes leave the count untouched
+        assert!(!trie.delete("ca"));
+        assert!(!trie.delete("zebra"));
+        assert_eq!(trie.len(), 4);
+
+        assert!(trie.delete("card"));
+        assert_eq!(trie.len(), 3);
+        assert!(!trie.is_empty());
     }
 
     #[test]
@@ -561,7 +5032,7 @@ mod tests {
 
         // Insert all words
         for (i, word) in words.iter().enumerate() {
-            trie.insert(word, &i);
+            trie.insert(word, i);
         }
 
         // Verify all exist
@@ -596,7 +5067,7 @@ mod tests {
         ];
 
         for (i, word) in words.iter().enumerate() {
-            trie.insert(word, &i);
+            trie.insert(word, i);
         }
 
         // Test that all words exist
@@ -654,7 +5125,7 @@ mod tests {
         ];
 
         for (i, word) in special_words.iter().enumerate() {
-            trie.insert(word, &i);
+            trie.insert(word, i);
         }
 
         // Verify all special character words work
@@ -677,11 +5148,11 @@ mod tests {
         let mut trie = Trie::new();
 
         // Test with numeric strings
-        trie.insert("123", &123);
-        trie.insert("456", &456);
-        trie.insert("12345", &12345);
-        trie.insert("0", &0);
-        trie.insert("007", &7);
+        trie.insert("123", 123);
+        trie.insert("456", 456);
+        trie.insert("12345", 12345);
+        trie.insert("0", 0);
+        trie.insert("007", 7);
 
         assert_eq!(trie.get("123"), Some(&123));
         assert_eq!(trie.get("456"), Some(&456));
@@ -701,9 +5172,7 @@ mod tests {
 
     #[test]
     fn test_empty_results_scenarios() {
-        let mut trie = Trie::new();
-        trie.insert("apple", &1);
-        trie.insert("banana", &2);
+        let mut trie = trie! { "apple" => 1, "banana" => 2 };
 
         // Test various scenarios that should return empty results
         assert!(trie.auto_complete("xyz", 10).is_empty());
@@ -715,4 +5184,90 @@ mod tests {
         let results = trie.auto_complete("appl", 10);
         assert!(results.is_empty());
     }
+
+    // Invariants checked over arbitrary keys and values. The empty key is a
+    // valid key but cannot be deleted, so the delete properties skip it.
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+        use std::collections::BTreeMap;
+
+        fn key() -> impl Strategy<Value = String> {
+            prop::string::string_regex("[a-z]{0,20}").unwrap()
+        }
+
+        fn pairs() -> impl Strategy<Value = Vec<(String, i32)>> {
+            prop::collection::vec((key(), any::<i32>()), 0..50)
+        }
+
+        proptest! {
+            #[test]
+            fn insert_then_get_returns_the_value(pairs in pairs(), k in key(), v in any::<i32>()) {
+                let mut trie: Trie<i32> = pairs.into_iter().collect();
+                trie.insert(&k, v);
+                prop_assert_eq!(trie.get(&k), Some(&v));
+            }
+
+            #[test]
+            fn delete_then_get_returns_none(pairs in pairs(), k in key(), v in any::<i32>()) {
+                prop_assume!(!k.is_empty());
+                let mut trie: Trie<i32> = pairs.into_iter().collect();
+                trie.insert(&k, v);
+                prop_assert!(trie.delete(&k));
+                prop_assert_eq!(trie.get(&k), None);
+            }
+
+            #[test]
+            fn auto_complete_results_start_with_prefix(
+                pairs in pairs(),
+                prefix in prop::string::string_regex("[a-z]{0,3}").unwrap(),
+                max in 0usize..20,
+            ) {
+                let trie: Trie<i32> = pairs.into_iter().collect();
+                let results = trie.auto_complete(&prefix, max);
+                prop_assert!(results.len() <= max);
+                for word in results {
+                    prop_assert!(word.starts_with(&prefix));
+                }
+            }
+
+            #[test]
+            fn len_counts_unique_keys_inserted_minus_deleted(
+                pairs in pairs(),
+                deletions in prop::collection::vec(key(), 0..30),
+            ) {
+                let mut trie = Trie::new();
+                let mut model = BTreeMap::new();
+                for (k, v) in pairs {
+                    trie.insert(&k, v);
+                    model.insert(k, v);
+                }
+                for k in deletions {
+                    let expected = !k.is_empty() && model.remove(&k).is_some();
+                    prop_assert_eq!(trie.delete(&k), expected);
+                }
+                prop_assert_eq!(trie.len(), model.len());
+            }
+
+            #[test]
+            fn insertion_order_does_not_matter(
+                entries in prop::collection::btree_map(key(), any::<i32>(), 0..50),
+                seed in any::<u64>(),
+            ) {
+                let forward: Trie<i32> = entries.clone().into_iter().collect();
+                let mut shuffled: Vec<_> = entries.into_iter().collect();
+                // A Fisher-Yates shuffle driven by the seed, so proptest can
+                // shrink failures down to a small seed.
+                let mut state = seed | 1;
+                for i in (1..shuffled.len()).rev() {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    shuffled.swap(i, (state % (i as u64 + 1)) as usize);
+                }
+                let reordered: Trie<i32> = shuffled.into_iter().collect();
+                prop_assert_eq!(forward, reordered);
+            }
+        }
+    }
 }
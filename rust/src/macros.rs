@@ -0,0 +1,34 @@
+/// Builds a `Trie` inline.
+///
+/// With `key => value` pairs it inserts each pair in order, so a repeated key
+/// keeps its last value. With bare keys it builds a `Trie<()>` word set. The
+/// value type is inferred from the values.
+///
+/// Example:
+/// ```Rust
+/// let scores = trie! { "apple" => 1, "banana" => 2 };
+/// assert_eq!(scores.get("banana"), Some(&2));
+///
+/// let words = trie!["cat", "car"];
+/// assert!(words.prefix_search("ca"));
+/// ```
+#[macro_export]
+macro_rules! trie {
+    () => {
+        $crate::Trie::new()
+    };
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut trie = $crate::Trie::new();
+        $(
+            trie.insert($key, $value);
+        )+
+        trie
+    }};
+    ($($key:expr),+ $(,)?) => {{
+        let mut trie: $crate::Trie<()> = $crate::Trie::new();
+        $(
+            trie.insert($key, ());
+        )+
+        trie
+    }};
+}
@@ -0,0 +1,27 @@
+//! The map type behind each node's children: a `HashMap` for O(1) lookups with
+//! the `std` feature, or an `alloc` `BTreeMap` without it, which also makes
+//! iteration order deterministic. The `smallvec-children` feature replaces
+//! either with a `SmallMap`, which keeps a node's first few children inline.
+
+#[cfg(feature = "smallvec-children")]
+pub(crate) use crate::small_map::{self as child_map, SmallMap as ChildMap};
+#[cfg(not(any(feature = "std", feature = "smallvec-children")))]
+pub(crate) use alloc::collections::{BTreeMap as ChildMap, btree_map as child_map};
+#[cfg(all(feature = "std", not(feature = "smallvec-children")))]
+pub(crate) use std::collections::{HashMap as ChildMap, hash_map as child_map};
+
+/// Whether the children map in this build keeps its entries in `char` order,
+/// as the `BTreeMap` and `SmallMap` do and the `HashMap` does not.
+pub(crate) const SORTED_CHILDREN: bool =
+    cfg!(any(not(feature = "std"), feature = "smallvec-children"));
+
+/// The hasher a `Trie` uses unless it is built with `Trie::with_hasher`: the
+/// standard library's `RandomState`, as for a `HashMap`.
+#[cfg(feature = "std")]
+pub type DefaultHashBuilder = std::hash::RandomState;
+
+/// Without `std` the children live in a `BTreeMap` and are never hashed, so
+/// this only fills in the hasher parameter of `Trie`.
+#[cfg(not(feature = "std"))]
+#[allow(deprecated)]
+pub type DefaultHashBuilder = core::hash::BuildHasherDefault<core::hash::SipHasher>;
@@ -0,0 +1,112 @@
+use crate::trie::Trie;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A Trie where each key holds a list of values, for several document ids per
+/// term, several definitions per word or several timestamps per event.
+///
+/// Inserting appends to the key's list instead of replacing it, and the
+/// values of a key stay in the order they were inserted.
+///
+/// Example:
+/// ```Rust
+/// let mut definitions = MultiTrie::new();
+/// definitions.insert("bank", "edge of a river");
+/// definitions.insert("bank", "place that holds money");
+/// assert_eq!(definitions.get_all("bank"), ["edge of a river", "place that holds money"]);
+/// assert_eq!(definitions.get_last("bank"), Some(&"place that holds money"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiTrie<TValue> {
+    // Lists are never left empty: a key without values is removed.
+    inner: Trie<Vec<TValue>>,
+}
+
+impl<TValue> MultiTrie<TValue> {
+    /// Initializes a new, empty MultiTrie.
+    pub fn new() -> Self {
+        MultiTrie { inner: Trie::new() }
+    }
+
+    /// Returns the number of distinct keys stored.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns true if no keys are stored.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Appends `value` to the values of `key`, keeping any already stored.
+    pub fn insert(&mut self, key: &str, value: TValue) {
+        self.inner.entry(key).or_default().push(value);
+    }
+
+    /// Returns every value stored under `key` in insertion order, or an empty
+    /// slice if there are none.
+    pub fn get_all(&self, key: &str) -> &[TValue] {
+        self.inner.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the first value inserted under `key`.
+    pub fn get_first(&self, key: &str) -> Option<&TValue> {
+        self.get_all(key).first()
+    }
+
+    /// Returns the last value inserted under `key`.
+    pub fn get_last(&self, key: &str) -> Option<&TValue> {
+        self.get_all(key).last()
+    }
+
+    /// Removes `key` and returns all of its values, in insertion order. Unlike
+    /// `Trie::pop`, this also removes the empty key.
+    pub fn remove_all(&mut self, key: &str) -> Vec<TValue> {
+        let values = if key.is_empty() {
+            self.inner.take_root_value()
+        } else {
+            self.inner.pop(key)
+        };
+        values.unwrap_or_default()
+    }
+
+    /// Returns up to `max_results` keys that start with `prefix`, each with all
+    /// of its values, in lexicographic key order.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut index = MultiTrie::new();
+    /// index.insert("cat", 1);
+    /// index.insert("car", 2);
+    /// index.insert("cat", 3);
+    /// let results = index.completions_with_all_values("ca", 10);
+    /// assert_eq!(results, vec![("car".to_string(), &[2][..]), ("cat".to_string(), &[1, 3][..])]);
+    /// ```
+    pub fn completions_with_all_values(
+        &self,
+        prefix: &str,
+        max_results: usize,
+    ) -> Vec<(String, &[TValue])> {
+        self.inner
+            .prefix_iter(prefix)
+            .take(max_results)
+            .map(|(key, values)| (key, values.as_slice()))
+            .collect()
+    }
+
+    /// Returns a reference to the inner Trie of value lists.
+    pub fn as_trie(&self) -> &Trie<Vec<TValue>> {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the inner Trie of value lists.
+    pub fn into_inner(self) -> Trie<Vec<TValue>> {
+        self.inner
+    }
+}
+
+impl<TValue> Default for MultiTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,211 @@
+//! Rayon support: parallel construction and insertion, and parallel iterators
+//! over a Trie's entries.
+//!
+//! Keys with different first characters never share a node, so the subtrees
+//! under the root's children are independent. Every parallel operation here
+//! hands one such subtree to each task.
+
+use crate::iter::{IntoIter, PrefixIter, Values};
+use crate::map::DefaultHashBuilder;
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+use core::hash::BuildHasher;
+use rayon::iter::plumbing::UnindexedConsumer;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+impl<TValue: Send + Sync + Clone> Trie<TValue> {
+    /// Builds a Trie from `items` using all of rayon's worker threads. Produces
+    /// the same Trie as inserting the items in order, so when a key repeats the
+    /// last value wins.
+    ///
+    /// Items are partitioned by the first character of their key. Keys with
+    /// different first characters never share a node, so each partition is
+    /// built independently and the partial tries are merged by hanging each one
+    /// under the root, with no conflicts to resolve.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let items = vec![("apple", 1), ("banana", 2), ("avocado", 3)];
+    /// let trie = Trie::par_build(&items);
+    /// assert_eq!(trie.get("avocado"), Some(&3));
+    /// ```
+    pub fn par_build<S: AsRef<str> + Sync>(items: &[(S, TValue)]) -> Trie<TValue> {
+        let mut trie = Trie::new();
+        trie.par_insert_all(items);
+        trie
+    }
+
+    /// Inserts `items` using all of rayon's worker threads, with the same
+    /// result as inserting them in order: a repeated key ends up with its last
+    /// value.
+    ///
+    /// As in `par_build`, items are partitioned by the first character of
+    /// their key, and each partition is inserted into the existing branch
+    /// under that character on its own task. The values are cloned out of
+    /// `items`, also in parallel, before the Trie is touched, so if cloning a
+    /// value panics, the panic is propagated and the Trie is left unchanged.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// trie.par_insert_all(&[("apple", 10), ("banana", 2)]);
+    /// assert_eq!(trie.get("apple"), Some(&10));
+    /// assert_eq!(trie.len(), 2);
+    /// ```
+    pub fn par_insert_all<S: AsRef<str> + Sync>(&mut self, items: &[(S, TValue)]) {
+        let mut root_value = None;
+        let mut partitions: HashMap<char, Vec<&(S, TValue)>> = HashMap::new();
+        for item in items {
+            match item.0.as_ref().chars().next() {
+                Some(first) => partitions.entry(first).or_default().push(item),
+                None => root_value = Some(&item.1),
+            }
+        }
+
+        let root_value = root_value.cloned();
+        let partitions: Vec<_> = partitions
+            .into_par_iter()
+            .map(|(first, items)| {
+                let entries: Vec<_> = items
+                    .into_iter()
+                    .map(|(key, value)| (key.as_ref(), value.clone()))
+                    .collect();
+                (first, entries)
+            })
+            .collect();
+
+        // From here on no user code runs until the Trie is whole again: the
+        // values each task replaces are handed back rather than dropped.
+        let mut word_count = self.len();
+        let sorted_children = self.sorted_children();
+        let mut root = core::mem::take(self).into_root();
+        let work: Vec<_> = partitions
+            .into_iter()
+            .map(|(first, entries)| {
+                let branch = root.remove_child(first).unwrap_or_default();
+                (first, branch, entries)
+            })
+            .collect();
+
+        // Each task returns its branch with the number of keys it added.
+        let branches: Vec<_> = work
+            .into_par_iter()
+            .map(|(first, mut branch, entries)| {
+                let mut added = 0;
+                let mut replaced = Vec::new();
+                for (key, value) in entries {
+                    let mut node = &mut branch;
+                    for c in key.chars().skip(1) {
+                        node = node.add_child(c);
+                    }
+                    match node.set_value(value) {
+                        Some(old) => replaced.push(old),
+                        None => added += 1,
+                    }
+                }
+                (first, branch, added, replaced)
+            })
+            .collect();
+
+        let mut replaced = Vec::with_capacity(branches.len() + 1);
+        for (first, branch, added, old) in branches {
+            root.set_child(first, branch);
+            word_count += added;
+            replaced.push(old);
+        }
+        if let Some(value) = root_value {
+            match root.set_value(value) {
+                Some(old) => replaced.push(vec![old]),
+                None => word_count += 1,
+            }
+        }
+        *self = Trie::from_root(root, word_count);
+        self.set_sorted_children(sorted_children);
+    }
+}
+
+/// A parallel iterator over the (key, value) pairs of a Trie, created by
+/// `par_iter` from rayon's prelude. Each subtree under the root is walked on
+/// its own task; collecting the entries keeps them in key order.
+///
+/// Example:
+/// ```Rust
+/// use rayon::prelude::*;
+/// let trie = trie! { "the" => 500, "cat" => 3, "then" => 120 };
+/// assert_eq!(trie.par_iter().filter(|(_, count)| **count > 100).count(), 2);
+/// ```
+pub struct ParIter<'a, TValue, H = DefaultHashBuilder> {
+    root_value: Option<&'a TValue>,
+    // The root's children in ascending order.
+    branches: Vec<(char, &'a TrieNode<TValue, H>)>,
+}
+
+impl<'a, TValue: Sync, H: Sync> ParallelIterator for ParIter<'a, TValue, H> {
+    type Item = (String, &'a TValue);
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.root_value
+            .map(|value| (String::new(), value))
+            .into_par_iter()
+            .chain(
+                self.branches
+                    .into_par_iter()
+                    .flat_map_iter(|(c, node)| PrefixIter::new(node, c.to_string())),
+            )
+            .drive_unindexed(consumer)
+    }
+}
+
+impl<'a, TValue: Sync, H: BuildHasher + Clone + Sync> IntoParallelIterator for &'a Trie<TValue, H> {
+    type Item = (String, &'a TValue);
+    type Iter = ParIter<'a, TValue, H>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        let root = self.root();
+        ParIter {
+            root_value: root.get_value(),
+            branches: root
+                .children_iter_sorted()
+                .map(|(&c, node)| (c, node))
+                .collect(),
+        }
+    }
+}
+
+/// An owning parallel iterator over the (key, value) pairs of a Trie, created
+/// by `into_par_iter`. Values are moved out without cloning. Like
+/// `Trie::into_iter`, it yields the entries in no particular order.
+pub struct IntoParIter<TValue, H = DefaultHashBuilder> {
+    root_value: Option<TValue>,
+    branches: Vec<(char, TrieNode<TValue, H>)>,
+}
+
+impl<TValue: Send, H: Send> ParallelIterator for IntoParIter<TValue, H> {
+    type Item = (String, TValue);
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.root_value
+            .map(|value| (String::new(), value))
+            .into_par_iter()
+            .chain(self.branches.into_par_iter().flat_map_iter(|(c, node)| {
+                let word_count = Values::new(&node).count();
+                IntoIter::with_prefix(node, c.to_string(), word_count, false)
+            }))
+            .drive_unindexed(consumer)
+    }
+}
+
+impl<TValue: Send, H: BuildHasher + Clone + Send> IntoParallelIterator for Trie<TValue, H> {
+    type Item = (String, TValue);
+    type Iter = IntoParIter<TValue, H>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        let (root_value, children) = self.into_root().into_parts();
+        IntoParIter {
+            root_value,
+            branches: children.collect(),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::multi::MultiTrie;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A Trie that finds words by how they sound, using Soundex codes.
+///
+/// Every word is stored under its Soundex code (see `soundex`), so "colour"
+/// and "color", or "Robert" and "Rupert", land in the same list and a search
+/// for one returns them all. Soundex was designed for English surnames: it
+/// only looks at ASCII letters and keeps at most four sounds, so it finds
+/// spelling variants but also matches some unrelated words.
+///
+/// Example:
+/// ```Rust
+/// let mut names = PhoneticTrie::new();
+/// names.insert("Robert", 1);
+/// names.insert("Rupert", 2);
+/// names.insert("Alice", 3);
+/// let matches = names.soundalike_search("Robbert");
+/// assert_eq!(matches, vec![("Robert".to_string(), &1), ("Rupert".to_string(), &2)]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PhoneticTrie<TValue> {
+    // Each code maps to the (word, value) pairs stored under it, in insertion
+    // order.
+    codes: MultiTrie<(String, TValue)>,
+    word_count: usize,
+}
+
+impl<TValue> PhoneticTrie<TValue> {
+    /// Initializes a new, empty PhoneticTrie.
+    pub fn new() -> Self {
+        PhoneticTrie {
+            codes: MultiTrie::new(),
+            word_count: 0,
+        }
+    }
+
+    /// Returns the number of (word, value) pairs stored.
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns true if nothing is stored.
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
+    /// Stores `value` for `word` under the word's Soundex code. Pairs are
+    /// only ever added, so inserting a word twice keeps both values.
+    pub fn insert(&mut self, word: &str, value: TValue) {
+        self.codes
+            .insert(&soundex(word), (String::from(word), value));
+        self.word_count += 1;
+    }
+
+    /// Returns every stored word that sounds like `query`, that is, has the
+    /// same Soundex code, with its value, in insertion order.
+    pub fn soundalike_search(&self, query: &str) -> Vec<(String, &TValue)> {
+        self.codes
+            .get_all(&soundex(query))
+            .iter()
+            .map(|(word, value)| (word.clone(), value))
+            .collect()
+    }
+}
+
+impl<TValue> Default for PhoneticTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the American Soundex code of `word`: its first letter, uppercased,
+/// followed by three digits for the consonant sounds after it, padded with
+/// zeros. "Robert" and "Rupert" are both "R163".
+///
+/// Only ASCII letters are considered, and case is ignored. Consonants with
+/// the same digit count once when they are adjacent or separated only by
+/// "h" or "w", while a vowel between them lets both count. Words without any
+/// ASCII letters get the empty code, so they all sound alike.
+pub fn soundex(word: &str) -> String {
+    let mut letters = word
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_lowercase());
+    let Some(first) = letters.next() else {
+        return String::new();
+    };
+
+    let mut code = String::with_capacity(4);
+    code.push(first.to_ascii_uppercase());
+    // The first letter's own digit still keeps a repeat right after it out.
+    let mut previous = soundex_digit(first);
+    for c in letters {
+        if code.len() == 4 {
+            break;
+        }
+        if c == 'h' || c == 'w' {
+            continue;
+        }
+        let digit = soundex_digit(c);
+        if let Some(d) = digit {
+            if digit != previous {
+                code.push(d);
+            }
+        }
+        previous = digit;
+    }
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+/// The Soundex digit of a lowercase ASCII consonant, or `None` for a vowel.
+fn soundex_digit(c: char) -> Option<char> {
+    match c {
+        'b' | 'f' | 'p' | 'v' => Some('1'),
+        'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+        'd' | 't' => Some('3'),
+        'l' => Some('4'),
+        'm' | 'n' => Some('5'),
+        'r' => Some('6'),
+        _ => None,
+    }
+}
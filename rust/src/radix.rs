@@ -0,0 +1,302 @@
+use crate::map::{ChildMap, child_map::Entry};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A radix (Patricia) trie: a Trie in which every chain of nodes with a single
+/// child and no value is merged into one node, whose edge carries a string
+/// label instead of a single character.
+///
+/// Keys with long unshared suffixes therefore cost one node instead of one per
+/// character. "unconditionally" on its own is a single node below the root,
+/// where `Trie` would need fifteen.
+#[derive(Clone)]
+pub struct RadixTrie<TValue> {
+    root: RadixNode<TValue>,
+    word_count: usize,
+}
+
+#[derive(Clone)]
+struct RadixNode<TValue> {
+    // The edge label leading into this node; empty only for the root.
+    label: String,
+    value: Option<TValue>,
+    // Children keyed by the first character of their label. Two children never
+    // share a first character, which is what keeps the labels unambiguous.
+    children: ChildMap<char, RadixNode<TValue>>,
+}
+
+impl<TValue> RadixNode<TValue> {
+    fn new(label: String, value: Option<TValue>) -> Self {
+        RadixNode {
+            label,
+            value,
+            children: ChildMap::new(),
+        }
+    }
+
+    /// Splits this node's label after `at` bytes. The node keeps the first part,
+    /// and a new child takes the rest along with the value and children.
+    fn split(&mut self, at: usize) {
+        let suffix = self.label.split_off(at);
+        let first = first_char(&suffix);
+        let lower = RadixNode {
+            label: suffix,
+            value: self.value.take(),
+            children: core::mem::take(&mut self.children),
+        };
+        self.children.insert(first, lower);
+    }
+
+    /// Absorbs this node's only child, for a node that no longer holds a value.
+    fn merge_with_only_child(&mut self) {
+        let child = core::mem::take(&mut self.children)
+            .into_values()
+            .next()
+            .expect("merge requires exactly one child");
+        self.label.push_str(&child.label);
+        self.value = child.value;
+        self.children = child.children;
+    }
+}
+
+impl<TValue> RadixTrie<TValue> {
+    /// Initializes a new, empty RadixTrie.
+    pub fn new() -> Self {
+        RadixTrie {
+            root: RadixNode::new(String::new(), None),
+            word_count: 0,
+        }
+    }
+
+    /// Returns the number of words stored in the RadixTrie. This is O(1).
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns true if the RadixTrie contains no words.
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
+    /// Inserts a key-value pair, returning the previous value if the key was
+    /// already stored. An edge whose label the key leaves partway through is
+    /// split in two at the point where they diverge.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = RadixTrie::new();
+    /// trie.insert("romane", 1);
+    /// trie.insert("romanus", 2); // splits "romane" into "roman" + "e"
+    /// assert_eq!(trie.get("romane"), Some(&1));
+    /// ```
+    pub fn insert(&mut self, key: &str, value: TValue) -> Option<TValue> {
+        let mut node = &mut self.root;
+        let mut rest = key;
+        while let Some(first) = rest.chars().next() {
+            let child = match node.children.entry(first) {
+                Entry::Vacant(slot) => {
+                    slot.insert(RadixNode::new(rest.to_string(), Some(value)));
+                    self.word_count += 1;
+                    return None;
+                }
+                Entry::Occupied(slot) => slot.into_mut(),
+            };
+
+            let common = common_prefix_len(&child.label, rest);
+            if common < child.label.len() {
+                child.split(common);
+            }
+            rest = &rest[common..];
+            node = child;
+        }
+
+        let previous = node.value.replace(value);
+        if previous.is_none() {
+            self.word_count += 1;
+        }
+        previous
+    }
+
+    /// Gets the value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&TValue> {
+        let mut node = &self.root;
+        let mut rest = key;
+        while let Some(first) = rest.chars().next() {
+            node = node.children.get(&first)?;
+            rest = rest.strip_prefix(node.label.as_str())?;
+        }
+        node.value.as_ref()
+    }
+
+    /// Gets a mutable reference to the value stored under `key`.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut TValue> {
+        let mut node = &mut self.root;
+        let mut rest = key;
+        while let Some(first) = rest.chars().next() {
+            node = node.children.get_mut(&first)?;
+            rest = rest.strip_prefix(node.label.as_str())?;
+        }
+        node.value.as_mut()
+    }
+
+    /// Deletes a key and its value. Returns true if the key was found. A node
+    /// left with no value and no children is removed, and one left with no
+    /// value and a single child is merged with that child, so the tree stays
+    /// fully compressed. As with `Trie`, the empty key cannot be removed this way.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = RadixTrie::new();
+    /// trie.insert("romane", 1);
+    /// trie.insert("romanus", 2);
+    /// assert!(trie.delete("romanus"));
+    /// assert_eq!(trie.node_count(), 2); // root + "romane"
+    /// ```
+    pub fn delete(&mut self, key: &str) -> bool {
+        if key.is_empty() {
+            return false;
+        }
+        let removed = Self::delete_recursively(&mut self.root, key).is_some();
+        if removed {
+            self.word_count -= 1;
+        }
+        removed
+    }
+
+    /// Recursive helper for `delete`. Removes the value for `rest` below `node`
+    /// and restores compression on the child it descended into.
+    fn delete_recursively(node: &mut RadixNode<TValue>, rest: &str) -> Option<TValue> {
+        let Some(first) = rest.chars().next() else {
+            return node.value.take();
+        };
+
+        let child = node.children.get_mut(&first)?;
+        let child_rest = rest.strip_prefix(child.label.as_str())?;
+        let removed = Self::delete_recursively(child, child_rest)?;
+        if child.value.is_none() {
+            match child.children.len() {
+                0 => {
+                    node.children.remove(&first);
+                }
+                1 => child.merge_with_only_child(),
+                _ => {}
+            }
+        }
+        Some(removed)
+    }
+
+    /// Returns true if any stored word starts with `prefix`, including a prefix
+    /// that ends partway through an edge label.
+    pub fn prefix_search(&self, prefix: &str) -> bool {
+        self.find_prefix(prefix).is_some()
+    }
+
+    /// Returns up to `max_results` words that start with `prefix`, in
+    /// lexicographic order.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = RadixTrie::new();
+    /// trie.insert("apricot", 1);
+    /// trie.insert("apple", 2);
+    /// trie.insert("app", 3);
+    /// assert_eq!(trie.auto_complete("ap", 2), vec!["app", "apple"]);
+    /// ```
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        let mut results = Vec::new();
+        if max_results == 0 {
+            return results;
+        }
+        if let Some((node, mut key)) = self.find_prefix(prefix) {
+            Self::collect_words_recursive(node, &mut key, &mut results, max_results);
+        }
+        results
+    }
+
+    fn collect_words_recursive(
+        node: &RadixNode<TValue>,
+        key: &mut String,
+        results: &mut Vec<String>,
+        max_results: usize,
+    ) {
+        if node.value.is_some() {
+            results.push(key.clone());
+        }
+        // Children have distinct first characters, so ordering by that
+        // character orders their whole subtrees.
+        let mut children: Vec<_> = node.children.iter().collect();
+        children.sort_unstable_by_key(|(c, _)| **c);
+        for (_, child) in children {
+            if results.len() >= max_results {
+                return;
+            }
+            let len = key.len();
+            key.push_str(&child.label);
+            Self::collect_words_recursive(child, key, results, max_results);
+            key.truncate(len);
+        }
+    }
+
+    /// Adds multiple words, using `value_generator` to compute each word's value.
+    pub fn add_word_list<T, F>(&mut self, items: &[T], value_generator: F)
+    where
+        T: AsRef<str>,
+        F: Fn(&T) -> TValue,
+    {
+        for item in items {
+            self.insert(item.as_ref(), value_generator(item));
+        }
+    }
+
+    /// Returns the number of nodes in the tree, including the root. Every node
+    /// other than the root either holds a value or has at least two children.
+    pub fn node_count(&self) -> usize {
+        let mut count = 0;
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            count += 1;
+            stack.extend(node.children.values());
+        }
+        count
+    }
+
+    /// Finds the node below which every key starts with `prefix`, along with
+    /// that node's full key. The key may extend past `prefix` when the prefix
+    /// ends partway through an edge label.
+    fn find_prefix(&self, prefix: &str) -> Option<(&RadixNode<TValue>, String)> {
+        let mut node = &self.root;
+        let mut rest = prefix;
+        let mut key = String::new();
+        while let Some(first) = rest.chars().next() {
+            node = node.children.get(&first)?;
+            key.push_str(&node.label);
+            match rest.strip_prefix(node.label.as_str()) {
+                Some(remaining) => rest = remaining,
+                None if node.label.starts_with(rest) => break,
+                None => return None,
+            }
+        }
+        Some((node, key))
+    }
+}
+
+/// Allows creating a new RadixTrie with `RadixTrie::default()`.
+impl<TValue> Default for RadixTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Length in bytes of the longest common prefix of `a` and `b`, always on a
+/// character boundary of both.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, x), y)| x != y)
+        .map_or(a.len().min(b.len()), |((i, _), _)| i)
+}
+
+fn first_char(label: &str) -> char {
+    label.chars().next().expect("edge labels are never empty")
+}
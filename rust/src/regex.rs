@@ -0,0 +1,90 @@
+use crate::iter::PrefixIter;
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use regex_automata::dfa::Automaton;
+use regex_automata::dfa::dense::{BuildError, DFA};
+use regex_automata::util::primitives::StateID;
+use regex_automata::util::start;
+
+impl<TValue, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Returns every stored key matching the regular expression `pattern`,
+    /// sorted by key. As with `Regex::is_match`, the pattern may match anywhere
+    /// in a key unless it is anchored with `^` and `$`.
+    ///
+    /// The pattern is compiled to a DFA that is stepped through the UTF-8 bytes
+    /// of each edge during the walk. A branch is skipped as soon as the DFA
+    /// reaches a dead state, and once a match has been seen every key below
+    /// is taken without running the DFA any further. Returns an error, boxed
+    /// as it is large, if the pattern is invalid or its DFA cannot be built.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("abcd12", 1);
+    /// trie.insert("abcd123", 2);
+    /// trie.insert("ab12", 3);
+    /// let matches = trie.regex_match(r"^[a-z]{4}\d{2}$").unwrap();
+    /// assert_eq!(matches, vec![("abcd12".to_string(), &1)]);
+    /// ```
+    pub fn regex_match(&self, pattern: &str) -> Result<Vec<(String, &TValue)>, Box<BuildError>> {
+        let dfa = DFA::new(pattern).map_err(Box::new)?;
+        let start = dfa
+            .start_state(&start::Config::new())
+            .expect("DFA supports unanchored searches");
+        let mut search = RegexSearch {
+            dfa: &dfa,
+            key: String::new(),
+            results: Vec::new(),
+        };
+        search.visit(self.root(), start);
+        Ok(search.results)
+    }
+}
+
+/// State for the walk behind `regex_match`.
+struct RegexSearch<'d, 'a, TValue> {
+    dfa: &'d DFA<Vec<u32>>,
+    key: String,
+    results: Vec<(String, &'a TValue)>,
+}
+
+impl<'a, TValue> RegexSearch<'_, 'a, TValue> {
+    /// Visits `node`, reached with the DFA in `state`, which is neither dead
+    /// nor a match. Children are visited in sorted order, so results come out
+    /// sorted.
+    fn visit<H: BuildHasher + Clone>(&mut self, node: &'a TrieNode<TValue, H>, state: StateID) {
+        if let Some(value) = node.get_value() {
+            if self.dfa.is_match_state(self.dfa.next_eoi_state(state)) {
+                self.results.push((self.key.clone(), value));
+            }
+        }
+
+        for (&c, child) in node.children_iter_sorted() {
+            let mut next = state;
+            let mut matched = false;
+            let mut buffer = [0; 4];
+            for &byte in c.encode_utf8(&mut buffer).as_bytes() {
+                next = self.dfa.next_state(next, byte);
+                // Match states lag one byte behind, so this match ended before
+                // `byte`. Every key below contains it, whatever follows.
+                if self.dfa.is_match_state(next) {
+                    matched = true;
+                    break;
+                }
+            }
+
+            self.key.push(c);
+            if matched {
+                self.results
+                    .extend(PrefixIter::new(child, self.key.clone()));
+            } else if !self.dfa.is_dead_state(next) {
+                self.visit(child, next);
+            }
+            self.key.pop();
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+/// The parameter name under which a `**` route captures the rest of the path.
+pub const CATCH_ALL: &str = "**";
+
+/// A Trie over URL path segments for HTTP routing.
+///
+/// Patterns are split on `/` into segments. A segment is a literal such as
+/// `users`, a named parameter such as `:id` that matches any one segment, or
+/// `**`, which may only come last and matches the rest of the path, including
+/// nothing. Empty segments are ignored in both patterns and paths, so
+/// `/users/` and `users` are the same route.
+///
+/// When several routes match a path, the most specific one wins: at each
+/// segment a literal beats `:param`, which beats `**`. A route that fails
+/// further down gives way to the next best choice at the segment where it
+/// was taken.
+///
+/// Example:
+/// ```Rust
+/// let mut router = RouterTrie::new();
+/// router.register("/api/users/:id/posts", "user_posts");
+/// router.register("/api/users/me/posts", "my_posts");
+/// router.register("/static/**", "files");
+///
+/// let (handler, params) = router.route("/api/users/42/posts").unwrap();
+/// assert_eq!((*handler, params["id"]), ("user_posts", "42"));
+/// assert_eq!(router.route("/api/users/me/posts").unwrap().0, &"my_posts");
+/// assert_eq!(router.route("/static/css/site.css").unwrap().1["**"], "css/site.css");
+/// ```
+#[derive(Clone, Debug)]
+pub struct RouterTrie<THandler> {
+    root: RouteNode<THandler>,
+    route_count: usize,
+}
+
+#[derive(Clone, Debug)]
+struct RouteNode<THandler> {
+    literals: HashMap<String, RouteNode<THandler>>,
+    // Every `:param` segment at this position shares one child; the names are
+    // kept with each route, so routes may name the same position differently.
+    param: Option<Box<RouteNode<THandler>>>,
+    // The route whose pattern ends at this node.
+    exact: Option<Route<THandler>>,
+    // The route whose pattern ends with `**` right after this node.
+    catch_all: Option<Route<THandler>>,
+}
+
+#[derive(Clone, Debug)]
+struct Route<THandler> {
+    // Names of the route's `:param` segments, in order.
+    params: Vec<String>,
+    handler: THandler,
+}
+
+impl<THandler> RouteNode<THandler> {
+    fn new() -> Self {
+        RouteNode {
+            literals: HashMap::new(),
+            param: None,
+            exact: None,
+            catch_all: None,
+        }
+    }
+
+    /// Finds the most specific route for `segments`, pushing the values of
+    /// the `:param` segments it passes onto `values`. Each segment comes with
+    /// its byte offset in `path`, so a catch-all can return the rest of it.
+    fn find<'a>(
+        &'a self,
+        path: &'a str,
+        segments: &[(usize, &'a str)],
+        values: &mut Vec<&'a str>,
+    ) -> Option<(&'a Route<THandler>, Option<&'a str>)> {
+        let Some((&(start, segment), rest)) = segments.split_first() else {
+            if let Some(route) = &self.exact {
+                return Some((route, None));
+            }
+            return self.catch_all.as_ref().map(|route| (route, Some("")));
+        };
+
+        if let Some(found) = self
+            .literals
+            .get(segment)
+            .and_then(|child| child.find(path, rest, values))
+        {
+            return Some(found);
+        }
+        if let Some(child) = &self.param {
+            values.push(segment);
+            if let Some(found) = child.find(path, rest, values) {
+                return Some(found);
+            }
+            values.pop();
+        }
+        let remainder = path[start..].trim_end_matches('/');
+        self.catch_all
+            .as_ref()
+            .map(|route| (route, Some(remainder)))
+    }
+}
+
+impl<THandler> RouterTrie<THandler> {
+    /// Initializes a new, empty RouterTrie.
+    pub fn new() -> Self {
+        RouterTrie {
+            root: RouteNode::new(),
+            route_count: 0,
+        }
+    }
+
+    /// Returns the number of registered routes.
+    pub fn len(&self) -> usize {
+        self.route_count
+    }
+
+    /// Returns true if no routes are registered.
+    pub fn is_empty(&self) -> bool {
+        self.route_count == 0
+    }
+
+    /// Registers `handler` for `pattern`, returning the handler it replaces
+    /// if a pattern of the same shape was already registered. Patterns that
+    /// differ only in their parameter names, such as `/users/:id` and
+    /// `/users/:name`, have the same shape.
+    ///
+    /// Panics if `**` is not the last segment, if a parameter has no name, or
+    /// if two parameters of the pattern share a name.
+    pub fn register(&mut self, pattern: &str, handler: THandler) -> Option<THandler> {
+        let mut node = &mut self.root;
+        let mut params: Vec<String> = Vec::new();
+        let mut catch_all = false;
+        let mut segments = pattern.split('/').filter(|s| !s.is_empty()).peekable();
+        while let Some(segment) = segments.next() {
+            if segment == CATCH_ALL {
+                assert!(
+                    segments.peek().is_none(),
+                    "`**` must be the last segment of a route, got {pattern:?}"
+                );
+                catch_all = true;
+                break;
+            }
+            node = match segment.strip_prefix(':') {
+                Some(name) => {
+                    assert!(!name.is_empty(), "unnamed parameter in route {pattern:?}");
+                    assert!(
+                        !params.iter().any(|param| param == name),
+                        "parameter {name:?} appears twice in route {pattern:?}"
+                    );
+                    params.push(name.to_string());
+                    node.param.get_or_insert_with(|| Box::new(RouteNode::new()))
+                }
+                None => node
+                    .literals
+                    .entry(segment.to_string())
+                    .or_insert_with(RouteNode::new),
+            };
+        }
+
+        let slot = if catch_all {
+            &mut node.catch_all
+        } else {
+            &mut node.exact
+        };
+        let previous = slot.replace(Route { params, handler });
+        if previous.is_none() {
+            self.route_count += 1;
+        }
+        previous.map(|route| route.handler)
+    }
+
+    /// Matches `path` against the registered routes and returns the handler
+    /// of the most specific one, along with the values of its parameters by
+    /// name. A `**` route captures the rest of the path, without leading or
+    /// trailing slashes, under the name `"**"`.
+    pub fn route<'a>(&'a self, path: &'a str) -> Option<(&'a THandler, HashMap<&'a str, &'a str>)> {
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for segment in path.split('/') {
+            if !segment.is_empty() {
+                segments.push((start, segment));
+            }
+            start += segment.len() + 1;
+        }
+
+        let mut values = Vec::new();
+        let (route, remainder) = self.root.find(path, &segments, &mut values)?;
+        let mut params: HashMap<&str, &str> = route
+            .params
+            .iter()
+            .map(String::as_str)
+            .zip(values)
+            .collect();
+        if let Some(remainder) = remainder {
+            params.insert(CATCH_ALL, remainder);
+        }
+        Some((&route.handler, params))
+    }
+}
+
+/// Allows creating a new RouterTrie with `RouterTrie::default()`.
+impl<THandler> Default for RouterTrie<THandler> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,46 @@
+use crate::trie::Trie;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Serializes the Trie as a flat map from key to value, in lexicographic key
+/// order. The node structure is deliberately left out so that the format stays
+/// stable however the internals change.
+impl<TValue: Serialize> Serialize for Trie<TValue> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Some formats (e.g. bincode) need the length up front, which the
+        // iterator cannot report on its own.
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self.prefix_iter("") {
+            map.serialize_entry(&key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Rebuilds a Trie from a flat key-to-value map by inserting each entry. If a key
+/// appears more than once, the last value wins.
+impl<'de, TValue: Deserialize<'de>> Deserialize<'de> for Trie<TValue> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(TrieVisitor(PhantomData))
+    }
+}
+
+struct TrieVisitor<TValue>(PhantomData<TValue>);
+
+impl<'de, TValue: Deserialize<'de>> Visitor<'de> for TrieVisitor<TValue> {
+    type Value = Trie<TValue>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map from string keys to values")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut trie = Trie::new();
+        while let Some((key, value)) = map.next_entry::<String, TValue>()? {
+            trie.insert(&key, value);
+        }
+        Ok(trie)
+    }
+}
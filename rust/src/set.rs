@@ -0,0 +1,179 @@
+use crate::iter::Keys;
+use crate::trie::Trie;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+
+/// A set of words backed by a `Trie<()>`, for dictionaries and stop-word lists
+/// where only membership matters.
+///
+/// The binary operators work on references like `HashSet`'s: `&a | &b`,
+/// `&a & &b` and `&a - &b` build new sets, while `a |= &b`, `a &= &b` and
+/// `a -= &b` update `a` in place.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TrieSet(Trie<()>);
+
+impl TrieSet {
+    /// Initializes a new, empty TrieSet.
+    pub fn new() -> Self {
+        TrieSet(Trie::new())
+    }
+
+    /// Builds a set containing each of `words`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let stop_words = TrieSet::from_words(&["a", "an", "the"]);
+    /// assert!(stop_words.contains("an"));
+    /// ```
+    pub fn from_words(words: &[&str]) -> Self {
+        words.iter().collect()
+    }
+
+    /// Returns the number of words in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the set holds no words.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns true if `word` is in the set.
+    pub fn contains(&self, word: &str) -> bool {
+        self.0.get(word).is_some()
+    }
+
+    /// Adds `word` to the set. Adding a word twice has no effect.
+    pub fn insert_word(&mut self, word: &str) {
+        self.0.insert(word, ());
+    }
+
+    /// Removes `word` from the set. Returns true if it was present.
+    pub fn remove_word(&mut self, word: &str) -> bool {
+        self.0.delete(word)
+    }
+
+    /// Returns every word starting with `prefix`, in lexicographic order.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let words = TrieSet::from_words(&["car", "cat", "dog"]);
+    /// assert_eq!(words.words_with_prefix("ca"), vec!["car", "cat"]);
+    /// ```
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.0.prefix_iter(prefix).map(|(word, _)| word).collect()
+    }
+
+    /// Returns an iterator over the words in lexicographic order.
+    pub fn iter(&self) -> Keys<'_, ()> {
+        self.0.keys()
+    }
+
+    /// Returns a new set with the words in either `self` or `other`.
+    pub fn union(&self, other: &TrieSet) -> TrieSet {
+        TrieSet(self.0.union(&other.0))
+    }
+
+    /// Returns a new set with the words in both `self` and `other`.
+    pub fn intersection(&self, other: &TrieSet) -> TrieSet {
+        TrieSet(self.0.intersection(&other.0))
+    }
+
+    /// Returns a new set with the words in `self` that are not in `other`.
+    pub fn difference(&self, other: &TrieSet) -> TrieSet {
+        TrieSet(self.0.difference(&other.0))
+    }
+
+    /// Returns true if every word in `self` is also in `other`.
+    pub fn is_subset(&self, other: &TrieSet) -> bool {
+        self.len() <= other.len() && self.iter().all(|word| other.contains(&word))
+    }
+
+    /// Returns a reference to the underlying `Trie<()>`.
+    pub fn as_trie(&self) -> &Trie<()> {
+        &self.0
+    }
+
+    /// Consumes the set and returns the underlying `Trie<()>`.
+    pub fn into_inner(self) -> Trie<()> {
+        self.0
+    }
+}
+
+impl From<Trie<()>> for TrieSet {
+    fn from(trie: Trie<()>) -> Self {
+        TrieSet(trie)
+    }
+}
+
+impl<'a> IntoIterator for &'a TrieSet {
+    type Item = String;
+    type IntoIter = Keys<'a, ()>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Same set of key types as the `Trie<()>` conversions, for the same reason.
+macro_rules! impl_set_conversions {
+    ($($key:ty),*) => {$(
+        impl<'a, 'b> Extend<$key> for TrieSet {
+            fn extend<I: IntoIterator<Item = $key>>(&mut self, iter: I) {
+                self.0.extend(iter);
+            }
+        }
+
+        impl<'a, 'b> FromIterator<$key> for TrieSet {
+            fn from_iter<I: IntoIterator<Item = $key>>(iter: I) -> Self {
+                TrieSet(iter.into_iter().collect())
+            }
+        }
+    )*};
+}
+
+impl_set_conversions!(&'a str, &'a &'b str, String, &'a String);
+
+impl BitOr<&TrieSet> for &TrieSet {
+    type Output = TrieSet;
+
+    fn bitor(self, other: &TrieSet) -> TrieSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd<&TrieSet> for &TrieSet {
+    type Output = TrieSet;
+
+    fn bitand(self, other: &TrieSet) -> TrieSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub<&TrieSet> for &TrieSet {
+    type Output = TrieSet;
+
+    fn sub(self, other: &TrieSet) -> TrieSet {
+        self.difference(other)
+    }
+}
+
+impl BitOrAssign<&TrieSet> for TrieSet {
+    fn bitor_assign(&mut self, other: &TrieSet) {
+        self.extend(other.iter());
+    }
+}
+
+impl BitAndAssign<&TrieSet> for TrieSet {
+    fn bitand_assign(&mut self, other: &TrieSet) {
+        self.0.retain(|word, _| other.contains(word));
+    }
+}
+
+impl SubAssign<&TrieSet> for TrieSet {
+    fn sub_assign(&mut self, other: &TrieSet) {
+        self.0.retain(|word, _| !other.contains(word));
+    }
+}
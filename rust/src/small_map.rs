@@ -0,0 +1,248 @@
+//! `SmallMap`, the children map used with the `smallvec-children` feature: a
+//! vector of `(key, child)` pairs kept sorted by key, which stores its first
+//! `N` entries inline and only spills to the heap beyond that.
+//!
+//! Most nodes of a word Trie have one to four children, where a binary search
+//! over a few inline keys beats hashing the key and probing a separate table.
+//! A node cannot hold its children inline inside itself, so each child is
+//! boxed: a node costs one allocation of its own instead of its parent's
+//! children map growing to make room for it.
+
+use alloc::boxed::Box;
+use core::iter::FusedIterator;
+use core::mem;
+use core::slice;
+use smallvec::SmallVec;
+
+/// How many children a node stores inline before its map spills to the heap.
+pub(crate) const INLINE_CHILDREN: usize = 4;
+
+/// A map kept as a sorted `SmallVec`, with room for `N` entries inline.
+#[derive(Clone)]
+pub struct SmallMap<K, V, const N: usize = INLINE_CHILDREN> {
+    entries: SmallVec<[(K, Box<V>); N]>,
+}
+
+impl<K: Ord, V, const N: usize> SmallMap<K, V, N> {
+    /// Creates an empty map. Nothing is allocated until it holds more than
+    /// `N` entries.
+    pub fn new() -> Self {
+        SmallMap {
+            entries: SmallVec::new(),
+        }
+    }
+
+    /// Creates an empty map with room for `capacity` entries, or `N` if
+    /// that is more.
+    pub fn with_capacity(capacity: usize) -> Self {
+        SmallMap {
+            entries: SmallVec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let index = self.search(key).ok()?;
+        Some(&self.entries[index].1)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.search(key).ok()?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(index) => Some(mem::replace(&mut self.entries[index].1, value)),
+            Err(index) => {
+                self.entries.insert(index, (key, Box::new(value)));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.search(key).ok()?;
+        Some(*self.entries.remove(index).1)
+    }
+
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N> {
+        match self.search(&key) {
+            Ok(index) => Entry::Occupied(OccupiedEntry {
+                value: &mut self.entries[index].1,
+            }),
+            Err(index) => Entry::Vacant(VacantEntry {
+                map: self,
+                key,
+                index,
+            }),
+        }
+    }
+
+    /// Iterates over the entries in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Iterates over the entries in ascending key order, with mutable access
+    /// to the values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            inner: self.entries.iter_mut(),
+        }
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn into_values(self) -> impl Iterator<Item = V> {
+        self.into_iter().map(|(_, value)| value)
+    }
+
+    fn search(&self, key: &K) -> Result<usize, usize> {
+        self.entries.binary_search_by(|(probe, _)| probe.cmp(key))
+    }
+}
+
+impl<K: Ord, V, const N: usize> Default for SmallMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize> IntoIterator for SmallMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.entries.into_iter(),
+        }
+    }
+}
+
+/// A view into a single entry of a `SmallMap`, as returned by `SmallMap::entry`.
+pub enum Entry<'a, K, V, const N: usize = INLINE_CHILDREN> {
+    Vacant(VacantEntry<'a, K, V, N>),
+    Occupied(OccupiedEntry<'a, V>),
+}
+
+impl<'a, K, V, const N: usize> Entry<'a, K, V, N> {
+    /// Returns the entry's value, inserting `default()` if it is vacant.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Vacant(slot) => slot.insert(default()),
+            Entry::Occupied(slot) => slot.into_mut(),
+        }
+    }
+}
+
+/// A missing key, remembering where it belongs in the sorted entries.
+pub struct VacantEntry<'a, K, V, const N: usize = INLINE_CHILDREN> {
+    map: &'a mut SmallMap<K, V, N>,
+    key: K,
+    index: usize,
+}
+
+impl<'a, K, V, const N: usize> VacantEntry<'a, K, V, N> {
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.map
+            .entries
+            .insert(self.index, (self.key, Box::new(value)));
+        &mut self.map.entries[self.index].1
+    }
+}
+
+/// A key that is already in the map.
+pub struct OccupiedEntry<'a, V> {
+    value: &'a mut V,
+}
+
+impl<'a, V> OccupiedEntry<'a, V> {
+    pub fn into_mut(self) -> &'a mut V {
+        self.value
+    }
+}
+
+/// Iterator over the entries of a `SmallMap`, in ascending key order.
+pub struct Iter<'a, K, V> {
+    inner: slice::Iter<'a, (K, Box<V>)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, value)| (key, &**value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+// Derived impls would needlessly require `K: Clone` and `V: Clone`.
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Iter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// Mutable iterator over the entries of a `SmallMap`, in ascending key order.
+pub struct IterMut<'a, K, V> {
+    inner: slice::IterMut<'a, (K, Box<V>)>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, value)| (&*key, &mut **value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for IterMut<'_, K, V> {}
+
+/// Owning iterator over the entries of a `SmallMap`, in ascending key order.
+pub struct IntoIter<K, V, const N: usize = INLINE_CHILDREN> {
+    inner: smallvec::IntoIter<[(K, Box<V>); N]>,
+}
+
+impl<K, V, const N: usize> Iterator for IntoIter<K, V, N> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, value)| (key, *value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {}
+
+impl<K, V, const N: usize> FusedIterator for IntoIter<K, V, N> {}
@@ -0,0 +1,64 @@
+use crate::trie::Trie;
+use alloc::boxed::Box;
+
+/// A saved copy of a Trie's contents, taken by `Trie::snapshot` and put back
+/// by `Trie::restore`.
+#[derive(Clone, Debug)]
+pub struct TrieSnapshot<TValue> {
+    trie: Box<Trie<TValue>>,
+}
+
+impl<TValue: Clone> Trie<TValue> {
+    /// Saves a copy of the current contents. This deep-copies the Trie, so it
+    /// costs as much as `clone`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// let saved = trie.snapshot();
+    /// trie.insert("banana", 2);
+    /// trie.restore(saved);
+    /// assert_eq!(trie.get("banana"), None);
+    /// ```
+    pub fn snapshot(&self) -> TrieSnapshot<TValue> {
+        TrieSnapshot {
+            trie: Box::new(self.clone()),
+        }
+    }
+
+    /// Runs `f` on the Trie and keeps its changes only if it returns `Ok`. On
+    /// `Err`, the Trie is put back exactly as it was before `f` ran and the
+    /// error is returned.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// let result: Result<(), &str> = trie.transaction(|t| {
+    ///     t.insert("banana", 2);
+    ///     Err("rejected")
+    /// });
+    /// assert_eq!(result, Err("rejected"));
+    /// assert_eq!(trie.len(), 1);
+    /// ```
+    pub fn transaction<F, R, E>(&mut self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut Trie<TValue>) -> Result<R, E>,
+    {
+        let saved = self.snapshot();
+        let result = f(self);
+        if result.is_err() {
+            self.restore(saved);
+        }
+        result
+    }
+}
+
+impl<TValue> Trie<TValue> {
+    /// Replaces the Trie's contents with a snapshot, discarding everything
+    /// changed since it was taken.
+    pub fn restore(&mut self, snapshot: TrieSnapshot<TValue>) {
+        *self = *snapshot.trie;
+    }
+}
@@ -0,0 +1,140 @@
+//! `TrieStats`, a summary of a Trie's shape gathered in one walk, for choosing
+//! between the Trie variants: high branching factors favour `AsciiTrie`, long
+//! unbranched paths compress well into a `RadixTrie`, and short keys are best
+//! left in a plain `Trie`.
+
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+use alloc::string::ToString;
+use alloc::{format, vec};
+use core::fmt;
+use core::hash::BuildHasher;
+
+/// Statistics about a Trie's keys and nodes, returned by `Trie::stats`. Key
+/// lengths are counted in characters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrieStats {
+    /// Number of stored keys, as `Trie::len`.
+    pub word_count: usize,
+    /// Number of nodes, including the root, as `Trie::node_count`.
+    pub node_count: usize,
+    /// Length of the longest key, or 0 if there are none.
+    pub max_key_length: usize,
+    /// Length of the shortest key, or 0 if there are none.
+    pub min_key_length: usize,
+    /// Mean key length, or 0.0 if there are no keys.
+    pub avg_key_length: f64,
+    /// Total length of all keys, as if they were stored separately.
+    pub total_chars: usize,
+    /// Mean number of children of the nodes that have any, or 0.0 if no node
+    /// does.
+    pub branching_factor_avg: f64,
+    /// Largest number of children of any node.
+    pub branching_factor_max: usize,
+    /// Bytes allocated for the nodes' children maps: `Trie::memory_estimate`
+    /// without the Trie itself.
+    pub estimated_heap_bytes: usize,
+}
+
+impl<TValue, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Gathers `TrieStats` in a single walk over the nodes.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "car" => 1, "cart" => 2, "dog" => 3 };
+    /// let stats = trie.stats();
+    /// assert_eq!(stats.word_count, 3);
+    /// assert_eq!(stats.total_chars, 10);
+    /// assert_eq!(stats.branching_factor_max, 2);
+    /// ```
+    pub fn stats(&self) -> TrieStats {
+        let slot_size = core::mem::size_of::<(char, TrieNode<TValue, H>)>();
+        let mut word_count = 0;
+        let mut node_count = 0;
+        let mut max_key_length = 0;
+        let mut min_key_length = usize::MAX;
+        let mut total_chars = 0;
+        let mut branching_nodes = 0;
+        let mut total_children = 0;
+        let mut branching_factor_max = 0;
+        let mut estimated_heap_bytes = 0;
+
+        let mut stack = vec![(self.root(), 0)];
+        while let Some((node, depth)) = stack.pop() {
+            node_count += 1;
+            if node.is_end_of_word() {
+                word_count += 1;
+                total_chars += depth;
+                max_key_length = max_key_length.max(depth);
+                min_key_length = min_key_length.min(depth);
+            }
+            let children = node.child_count();
+            if children > 0 {
+                branching_nodes += 1;
+                total_children += children;
+                branching_factor_max = branching_factor_max.max(children);
+            }
+            estimated_heap_bytes += node.children_capacity() * slot_size;
+            stack.extend(node.children_iter().map(|(_, child)| (child, depth + 1)));
+        }
+
+        TrieStats {
+            word_count,
+            node_count,
+            max_key_length,
+            min_key_length: if word_count == 0 { 0 } else { min_key_length },
+            avg_key_length: mean(total_chars, word_count),
+            total_chars,
+            branching_factor_avg: mean(total_children, branching_nodes),
+            branching_factor_max,
+            estimated_heap_bytes,
+        }
+    }
+
+    /// Prints the Trie's `stats` as a table to standard output.
+    #[cfg(feature = "std")]
+    pub fn print_stats(&self) {
+        println!("{}", self.stats());
+    }
+}
+
+fn mean(total: usize, count: usize) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        total as f64 / count as f64
+    }
+}
+
+/// Formats the statistics as a two-column table, one statistic per line.
+impl fmt::Display for TrieStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = [
+            ("words", self.word_count.to_string()),
+            ("nodes", self.node_count.to_string()),
+            ("total characters", self.total_chars.to_string()),
+            ("min key length", self.min_key_length.to_string()),
+            ("avg key length", format!("{:.2}", self.avg_key_length)),
+            ("max key length", self.max_key_length.to_string()),
+            (
+                "avg branching factor",
+                format!("{:.2}", self.branching_factor_avg),
+            ),
+            (
+                "max branching factor",
+                self.branching_factor_max.to_string(),
+            ),
+            (
+                "estimated heap bytes",
+                self.estimated_heap_bytes.to_string(),
+            ),
+        ];
+        for (i, (label, value)) in rows.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{label:<24}{value:>12}")?;
+        }
+        Ok(())
+    }
+}
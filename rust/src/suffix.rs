@@ -0,0 +1,127 @@
+use crate::trie::{PrefixMatchResult, Trie};
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A Trie of every suffix of every key, for substring search.
+///
+/// Inserting "hello" stores "hello", "ello", "llo", "lo" and "o", so any
+/// substring of a key is a prefix of one of its suffixes and can be found in
+/// O(m) for a pattern of m characters. The price is memory: a key of n
+/// characters adds up to n(n+1)/2 nodes, so this suits short keys such as
+/// names or product codes rather than documents.
+///
+/// Example:
+/// ```Rust
+/// let mut names = SuffixTrie::new();
+/// names.insert("Johnson", 1);
+/// names.insert("Jones", 2);
+/// assert!(names.contains_substring("ohn"));
+/// assert_eq!(names.keys_containing("on"), vec!["Johnson", "Jones"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SuffixTrie<TValue> {
+    entries: Trie<TValue>,
+    // Each suffix, including the empty one, maps to the keys that end with it.
+    suffixes: Trie<BTreeSet<String>>,
+}
+
+impl<TValue> SuffixTrie<TValue> {
+    /// Initializes a new, empty SuffixTrie.
+    pub fn new() -> Self {
+        SuffixTrie {
+            entries: Trie::new(),
+            suffixes: Trie::new(),
+        }
+    }
+
+    /// Returns the number of keys stored. Suffixes are not counted.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no keys are stored.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Stores `value` under `key` and indexes every suffix of `key`. Returns
+    /// the previous value if the key was already stored.
+    pub fn insert(&mut self, key: &str, value: TValue) -> Option<TValue> {
+        let previous = self.entries.insert(key, value);
+        if previous.is_none() {
+            for start in suffix_starts(key) {
+                self.suffixes
+                    .entry(&key[start..])
+                    .or_default()
+                    .insert(String::from(key));
+            }
+        }
+        previous
+    }
+
+    /// Gets the value stored under `key`. Only whole keys match, not suffixes.
+    pub fn get(&self, key: &str) -> Option<&TValue> {
+        self.entries.get(key)
+    }
+
+    /// Removes `key` and its suffixes, returning its value if it was stored.
+    pub fn remove(&mut self, key: &str) -> Option<TValue> {
+        let value = pop_any(&mut self.entries, key)?;
+        for start in suffix_starts(key) {
+            let suffix = &key[start..];
+            let now_unused = self.suffixes.get_mut(suffix).is_some_and(|keys| {
+                keys.remove(key);
+                keys.is_empty()
+            });
+            if now_unused {
+                pop_any(&mut self.suffixes, suffix);
+            }
+        }
+        Some(value)
+    }
+
+    /// Returns true if `pattern` occurs anywhere in a stored key.
+    pub fn contains_substring(&self, pattern: &str) -> bool {
+        // Unlike `prefix_search`, this answers false for "" with no keys.
+        self.suffixes.prefix_match(pattern) != PrefixMatchResult::NoMatch
+    }
+
+    /// Returns every stored key that contains `pattern`, in lexicographic
+    /// order and without duplicates.
+    pub fn keys_containing(&self, pattern: &str) -> Vec<String> {
+        let mut keys = BTreeSet::new();
+        for (_, owners) in self.suffixes.prefix_iter(pattern) {
+            keys.extend(owners);
+        }
+        keys.into_iter().cloned().collect()
+    }
+
+    /// Returns a reference to the Trie of whole keys.
+    pub fn as_trie(&self) -> &Trie<TValue> {
+        &self.entries
+    }
+}
+
+/// Like `Trie::pop`, but also removes the empty key.
+fn pop_any<TValue>(trie: &mut Trie<TValue>, key: &str) -> Option<TValue> {
+    if key.is_empty() {
+        trie.take_root_value()
+    } else {
+        trie.pop(key)
+    }
+}
+
+/// Returns the byte offset of every suffix of `key`, including the empty one
+/// at the end, so that the empty pattern matches every key.
+fn suffix_starts(key: &str) -> impl Iterator<Item = usize> + '_ {
+    key.char_indices()
+        .map(|(start, _)| start)
+        .chain(core::iter::once(key.len()))
+}
+
+impl<TValue> Default for SuffixTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,103 @@
+use crate::trie::Trie;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// A Trie that can be shared and updated across threads.
+///
+/// Every method takes `&self` and acquires the lock it needs internally: a
+/// read lock for lookups, so any number of threads can query at once, and a
+/// write lock for updates. Cloning a SyncTrie is O(1) and gives another handle
+/// to the same Trie; use `snapshot` for an independent copy.
+pub struct SyncTrie<TValue> {
+    inner: Arc<RwLock<Trie<TValue>>>,
+}
+
+impl<TValue> SyncTrie<TValue> {
+    /// Initializes a new, empty SyncTrie.
+    pub fn new() -> Self {
+        Self::from(Trie::new())
+    }
+
+    /// Returns the number of words currently stored.
+    pub fn len(&self) -> usize {
+        self.inner.read().len()
+    }
+
+    /// Returns true if no words are currently stored.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().is_empty()
+    }
+
+    /// Inserts a key-value pair, returning the previous value if the key was
+    /// already stored.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = SyncTrie::new();
+    /// let handle = trie.clone();
+    /// std::thread::spawn(move || handle.insert("apple", 1)).join().unwrap();
+    /// assert_eq!(trie.get("apple"), Some(1));
+    /// ```
+    pub fn insert(&self, key: &str, value: TValue) -> Option<TValue> {
+        self.inner.write().insert(key, value)
+    }
+
+    /// Returns a clone of the value stored under `key`. The value is cloned so
+    /// the read lock is released before the caller gets to use it.
+    pub fn get(&self, key: &str) -> Option<TValue>
+    where
+        TValue: Clone,
+    {
+        self.inner.read().get(key).cloned()
+    }
+
+    /// Deletes a key and its value. Returns true if the key was found.
+    pub fn delete(&self, key: &str) -> bool {
+        self.inner.write().delete(key)
+    }
+
+    /// Returns true if any stored word starts with `prefix`.
+    pub fn prefix_search(&self, prefix: &str) -> bool {
+        self.inner.read().prefix_search(prefix)
+    }
+
+    /// Returns up to `max_results` words that start with `prefix`, in
+    /// lexicographic order.
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        self.inner.read().auto_complete(prefix, max_results)
+    }
+
+    /// Returns an independent deep copy of the current contents, taken under a
+    /// read lock so it reflects a single consistent state.
+    pub fn snapshot(&self) -> Trie<TValue>
+    where
+        TValue: Clone,
+    {
+        self.inner.read().clone()
+    }
+}
+
+/// Cloning a SyncTrie is O(1): the new handle shares the same Trie.
+impl<TValue> Clone for SyncTrie<TValue> {
+    fn clone(&self) -> Self {
+        SyncTrie {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+/// Allows creating a new SyncTrie with `SyncTrie::default()`.
+impl<TValue> Default for SyncTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps an existing Trie so it can be shared across threads.
+impl<TValue> From<Trie<TValue>> for SyncTrie<TValue> {
+    fn from(trie: Trie<TValue>) -> Self {
+        SyncTrie {
+            inner: Arc::new(RwLock::new(trie)),
+        }
+    }
+}
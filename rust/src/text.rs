@@ -0,0 +1,105 @@
+//! A line-based `key=value` text format for Tries, read by `Trie::try_from`
+//! and written by the Trie's `Display` impl.
+//!
+//! Each line holds one entry, split at its first `=`: everything before is the
+//! key and everything after is the value, so values may contain `=` but keys
+//! may not. Blank lines, including ones of only whitespace, and lines starting
+//! with `#` are skipped.
+
+use crate::trie::Trie;
+use alloc::string::{String, ToString};
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
+
+/// Why a line was rejected by `Trie::try_from`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineError<E> {
+    /// The line has no `=` between key and value.
+    MissingSeparator,
+    /// The value did not parse as the Trie's value type.
+    InvalidValue(E),
+}
+
+/// The line that stopped `Trie::try_from` from parsing its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryFromTrieError<E> {
+    /// The 1-based number of the line.
+    pub line_number: usize,
+    /// The line itself, without its line ending.
+    pub line: String,
+    /// What was wrong with it.
+    pub error: LineError<E>,
+}
+
+impl<E: fmt::Display> fmt::Display for TryFromTrieError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} ({:?}): ", self.line_number, self.line)?;
+        match &self.error {
+            LineError::MissingSeparator => write!(f, "expected `key=value`"),
+            LineError::InvalidValue(err) => write!(f, "invalid value: {err}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for TryFromTrieError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.error {
+            LineError::MissingSeparator => None,
+            LineError::InvalidValue(err) => Some(err),
+        }
+    }
+}
+
+/// Parses `key=value` lines, one entry per line. A key given twice keeps its
+/// last value.
+///
+/// Example:
+/// ```Rust
+/// let config = "# limits\nmax_users=100\nmax_rooms=8\n";
+/// let trie = Trie::<u32>::try_from(config)?;
+/// assert_eq!(trie.get("max_users"), Some(&100));
+/// ```
+impl<TValue: FromStr> TryFrom<&str> for Trie<TValue> {
+    type Error = TryFromTrieError<TValue::Err>;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        let mut trie = Trie::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fail = |error| TryFromTrieError {
+                line_number: index + 1,
+                line: line.to_string(),
+                error,
+            };
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| fail(LineError::MissingSeparator))?;
+            let value = value
+                .parse()
+                .map_err(|err| fail(LineError::InvalidValue(err)))?;
+            trie.insert(key, value);
+        }
+        Ok(trie)
+    }
+}
+
+/// Writes one `key=value` line per entry, in lexicographic key order, in the
+/// format `Trie::try_from` reads. Keys containing `=` or a line break, or
+/// starting with `#`, will not read back unchanged.
+///
+/// Example:
+/// ```Rust
+/// let trie = trie! { "b" => 2, "a" => 1 };
+/// assert_eq!(trie.to_string(), "a=1\nb=2\n");
+/// ```
+impl<TValue: fmt::Display> fmt::Display for Trie<TValue> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, value) in self.iter() {
+            writeln!(f, "{key}={value}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::trie::Trie;
+use std::time::{Duration, Instant};
+
+/// A Trie whose entries expire a fixed time after they are inserted, for DNS
+/// caches, response caches keyed by URL and rate-limiter tables.
+///
+/// Expired entries are hidden from `get` straight away but keep their memory
+/// until `purge_expired` removes them, so `len` counts them until then.
+///
+/// Example:
+/// ```Rust
+/// let mut cache = TimedTrie::new();
+/// cache.insert_with_ttl("example.com", "93.184.216.34", Duration::from_secs(300));
+/// assert_eq!(cache.get("example.com"), Some(&"93.184.216.34"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TimedTrie<TValue> {
+    inner: Trie<(TValue, Instant)>,
+}
+
+impl<TValue> TimedTrie<TValue> {
+    /// Initializes a new, empty TimedTrie.
+    pub fn new() -> Self {
+        TimedTrie { inner: Trie::new() }
+    }
+
+    /// Returns the number of stored entries, including expired ones that have
+    /// not been purged yet.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns true if no entries are stored, expired or not.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Stores `value` under `key` until `ttl` from now, replacing any previous
+    /// entry and its expiry. Returns the previous value if it had not expired.
+    ///
+    /// Panics if `ttl` is too large to add to the current time, as
+    /// `Instant + Duration` does.
+    pub fn insert_with_ttl(&mut self, key: &str, value: TValue, ttl: Duration) -> Option<TValue> {
+        self.insert_with_ttl_at(key, value, ttl, Instant::now())
+    }
+
+    /// Stores `value` under `key` until `ttl` after `now`, as `insert_with_ttl`
+    /// does with the current time.
+    pub fn insert_with_ttl_at(
+        &mut self,
+        key: &str,
+        value: TValue,
+        ttl: Duration,
+        now: Instant,
+    ) -> Option<TValue> {
+        self.inner
+            .insert(key, (value, now + ttl))
+            .and_then(|(old, expiry)| (now < expiry).then_some(old))
+    }
+
+    /// Gets the value stored under `key`, or `None` if there is none or it has
+    /// expired.
+    pub fn get(&self, key: &str) -> Option<&TValue> {
+        self.get_at(key, Instant::now())
+    }
+
+    /// Gets the value stored under `key` as of `now`, which lets callers check
+    /// many keys against one clock reading.
+    pub fn get_at(&self, key: &str, now: Instant) -> Option<&TValue> {
+        match self.inner.get(key) {
+            Some((value, expiry)) if now < *expiry => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns when the entry under `key` expires, even if it already has.
+    pub fn expires_at(&self, key: &str) -> Option<Instant> {
+        self.inner.get(key).map(|&(_, expiry)| expiry)
+    }
+
+    /// Removes the entry under `key`, expired or not. Returns true if there was one.
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.inner.delete(key)
+    }
+
+    /// Removes every expired entry and returns how many were removed.
+    pub fn purge_expired(&mut self) -> usize {
+        self.purge_expired_at(Instant::now())
+    }
+
+    /// Removes every entry that has expired as of `now`.
+    pub fn purge_expired_at(&mut self, now: Instant) -> usize {
+        self.inner.retain(|_, (_, expiry)| now < *expiry)
+    }
+}
+
+/// Allows creating a new TimedTrie with `TimedTrie::default()`.
+impl<TValue> Default for TimedTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,33 +1,218 @@
-use crate::trie_node::TrieNode;
-pub struct Trie<TValue: Clone> {
-    root: TrieNode<TValue>,
+use crate::entry::{Entry, OccupiedEntry, VacantEntry};
+use crate::iter::{
+    AutoCompleteIter, IntoIter, Keys, PrefixIter, RangeIter, TrieDrain, TrieIter, TrieIterMut,
+    Values, ValuesMut,
+};
+use crate::map::DefaultHashBuilder;
+use crate::trie_node::{SortedChildIter, TrieNode};
+use alloc::collections::{BTreeMap, BinaryHeap};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::cmp::{Ordering, Reverse};
+use core::fmt;
+use core::hash::{BuildHasher, Hash, Hasher};
+use core::ops::{Add, BitAnd, BitOr, Index, Range, Sub};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, Write};
+/// Cloning a Trie deep-copies every node, so the copy is fully independent.
+///
+/// `H` builds the hasher for the `HashMap` of children in each node. It
+/// defaults to the standard library's `RandomState`; `Trie::with_hasher`
+/// swaps in a faster one.
+#[derive(Clone)]
+pub struct Trie<TValue, H = DefaultHashBuilder> {
+    root: TrieNode<TValue, H>,
+    /// Number of words currently stored, kept in sync by `insert` and `delete`.
+    word_count: usize,
+    /// Whether walks that would follow the children maps' own order visit
+    /// children in `char` order instead, as set by
+    /// `TrieBuilder::sorted_children`.
+    sorted_children: bool,
 }
 
-impl<TValue: Clone> Trie<TValue> {
+/// How a string relates to the keys of a Trie, as reported by
+/// `Trie::prefix_match`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixMatchResult {
+    /// No stored key starts with the string.
+    NoMatch,
+    /// Longer keys start with the string, but it is not a key itself.
+    PrefixOfStoredKey,
+    /// The string is a stored key, and no longer key starts with it.
+    ExactStoredKey,
+    /// The string is a stored key, and longer keys start with it too.
+    ExactAndPrefix,
+}
+
+/// One completion from `Trie::auto_complete_with_spans`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionResult<'a, TValue> {
+    /// The completed key.
+    pub key: String,
+    /// The value stored under `key`.
+    pub value: &'a TValue,
+    /// The part of `key` that matched the query, in characters rather than
+    /// bytes. For prefix completion this is always the leading
+    /// `prefix.chars().count()` characters.
+    pub matched_char_range: Range<usize>,
+}
+
+impl<TValue> CompletionResult<'_, TValue> {
+    /// Returns the part of `key` covered by `matched_char_range`.
+    pub fn matched(&self) -> &str {
+        let byte_offset = |chars: usize| {
+            self.key
+                .char_indices()
+                .nth(chars)
+                .map_or(self.key.len(), |(offset, _)| offset)
+        };
+        let range = &self.matched_char_range;
+        &self.key[byte_offset(range.start)..byte_offset(range.end)]
+    }
+}
+
+impl<TValue> Trie<TValue> {
     /// Initializes a new, empty Trie.
     pub fn new() -> Self {
         Trie {
             root: TrieNode::new(),
+            word_count: 0,
+            sorted_children: false,
+        }
+    }
+
+    /// Builds a Trie from key-value pairs given in sorted key order, such as a
+    /// dictionary file read line by line.
+    ///
+    /// The nodes on the previous key's path are kept open on a stack. Each new
+    /// key only rewinds the stack to the prefix it shares with the previous key
+    /// and descends from there, and a node is attached to its parent once, when
+    /// the stack leaves it. That touches every character once instead of hashing
+    /// each character of each key as `insert` does. Unsorted input still builds
+    /// the right Trie, just without the speedup. A repeated key keeps its last
+    /// value.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = Trie::from_sorted_input([("car", 1), ("card", 2), ("care", 3)]);
+    /// assert_eq!(trie.len(), 3);
+    /// assert_eq!(trie.get("card"), Some(&2));
+    /// ```
+    pub fn from_sorted_input<I, S>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (S, TValue)>,
+        S: AsRef<str>,
+    {
+        Self::build_sorted(TrieNode::new(), iter)
+    }
+}
+
+impl<TValue, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Initializes a new, empty Trie whose nodes hash their children with
+    /// clones of `hasher`, e.g. `ahash::RandomState` for faster lookups of
+    /// short `char` keys than the default SipHash.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::with_hasher(ahash::RandomState::new());
+    /// trie.insert("apple", 1);
+    /// assert_eq!(trie.get("apple"), Some(&1));
+    /// ```
+    pub fn with_hasher(hasher: H) -> Self {
+        Trie {
+            root: TrieNode::with_hasher(hasher),
+            word_count: 0,
+            sorted_children: false,
         }
     }
 
+    /// Returns the number of words stored in the Trie. This is O(1).
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// trie.insert("apple", 2);
+    /// assert_eq!(trie.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns true if the Trie contains no words.
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
     /// Inserts a key-value pair into the Trie.
     /// value is used to mark the end of the string or can contain a value if using the trie as a /// key value pair.
-    ///  If the key already exists,
-    /// its value is updated.
+    /// If the key already exists, its value is updated and the old value is returned.
     ///
     /// Example:
     /// ```Rust
     /// let mut trie = Trie::new();
-    /// trie.insert("apple", 1);
+    /// assert_eq!(trie.insert("apple", 1), None);
+    /// assert_eq!(trie.insert("apple", 2), Some(1));
+    /// assert_eq!(trie.get("apple"), Some(&2));
+    /// ```
+    pub fn insert(&mut self, key: &str, value: TValue) -> Option<TValue> {
+        let mut current_node = &mut self.root;
+        for c in key.chars() {
+            current_node = current_node.add_child(c);
+        }
+        let previous = current_node.set_value(value);
+        // Only a genuinely new key grows the count; an update leaves it unchanged.
+        if previous.is_none() {
+            self.word_count += 1;
+        }
+        previous
+    }
+
+    /// Inserts a key-value pair only if the key is not already stored. On success,
+    /// returns a mutable reference to the inserted value; if the key exists, the
+    /// Trie is left untouched and the value is handed back as `Err`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// assert_eq!(trie.try_insert("apple", 1), Ok(&mut 1));
+    /// assert_eq!(trie.try_insert("apple", 2), Err(2));
     /// assert_eq!(trie.get("apple"), Some(&1));
     /// ```
-    pub fn insert(&mut self, key: &str, value: &TValue) {
+    pub fn try_insert(&mut self, key: &str, value: TValue) -> Result<&mut TValue, TValue> {
+        // If the key is stored its whole path exists, so walking with `add_child`
+        // cannot create any nodes on the failure path.
         let mut current_node = &mut self.root;
         for c in key.chars() {
             current_node = current_node.add_child(c);
         }
-        current_node.set_value(value.clone());
+        if current_node.is_end_of_word() {
+            return Err(value);
+        }
+        self.word_count += 1;
+        current_node.set_value(value);
+        Ok(current_node.get_value_mut().expect("value was just set"))
+    }
+
+    /// Updates the value of a key that is already stored, returning the old
+    /// value. If the key is absent the Trie is left untouched and `None` is
+    /// returned, so no new keys can be added this way. This is the
+    /// counterpart of `try_insert`, which only adds absent keys.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// assert_eq!(trie.replace("apple", 2), Some(1));
+    /// assert_eq!(trie.replace("banana", 3), None);
+    /// assert_eq!(trie.get("banana"), None);
+    /// ```
+    pub fn replace(&mut self, key: &str, value: TValue) -> Option<TValue> {
+        self.get_mut(key)
+            .map(|current| core::mem::replace(current, value))
     }
 
     /// Searches for a key and returns a reference to its value if it exists.
@@ -51,6 +236,141 @@ impl<TValue: Clone> Trie<TValue> {
         current_node.get_value()
     }
 
+    /// Returns a reference to the value for `key`, panicking with `msg` if the
+    /// key is not stored.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// assert_eq!(trie.get_or_panic("apple", "apple must be loaded"), &1);
+    /// ```
+    pub fn get_or_panic(&self, key: &str, msg: &str) -> &TValue {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("{msg}"),
+        }
+    }
+
+    /// Looks up several keys at once, returning a copy of each value in the
+    /// order of `keys`.
+    ///
+    /// The keys are visited in sorted order, and each lookup starts from the
+    /// node where the previous key's path and its own part ways rather than
+    /// from the root. Batches whose keys share long prefixes, such as URL paths
+    /// under one host, skip most of the walk.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "/api/users" => 1, "/api/posts" => 2 };
+    /// let values = trie.batch_get(&["/api/posts", "/api/tags", "/api/users"]);
+    /// assert_eq!(values, vec![Some(2), None, Some(1)]);
+    /// ```
+    pub fn batch_get(&self, keys: &[&str]) -> Vec<Option<TValue>>
+    where
+        TValue: Clone,
+    {
+        let mut order: Vec<usize> = (0..keys.len()).collect();
+        order.sort_unstable_by_key(|&i| keys[i]);
+
+        let mut results = vec![None; keys.len()];
+        // `path[n]` is the node reached after the first n characters of
+        // `previous`, for as far as that key's path exists.
+        let mut path = vec![&self.root];
+        let mut previous = "";
+        for i in order {
+            let key = keys[i];
+            let shared = previous
+                .chars()
+                .zip(key.chars())
+                .take_while(|(a, b)| a == b)
+                .count();
+            path.truncate(shared + 1);
+            previous = key;
+
+            let mut found = true;
+            for c in key.chars().skip(path.len() - 1) {
+                let node = path[path.len() - 1];
+                match node.get_child(c) {
+                    Some(child) => path.push(child),
+                    None => {
+                        found = false;
+                        break;
+                    }
+                }
+            }
+            if found {
+                results[i] = path[path.len() - 1].get_value().cloned();
+            }
+        }
+        results
+    }
+
+    /// Searches for a key and returns a mutable reference to its value if it exists.
+    /// This allows updating a value in place without re-inserting it.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// *trie.get_mut("apple").unwrap() += 1;
+    /// assert_eq!(trie.get("apple"), Some(&2));
+    /// ```
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut TValue> {
+        self.find_node_mut(key)?.get_value_mut()
+    }
+
+    /// Gets the entry for the given key, allowing in-place insert-or-update logic.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// for word in ["apple", "banana", "apple"] {
+    ///     trie.entry(word).and_modify(|count| *count += 1).or_insert(1);
+    /// }
+    /// assert_eq!(trie.get("apple"), Some(&2));
+    /// ```
+    pub fn entry(&mut self, key: &str) -> Entry<'_, TValue, H> {
+        let chars: Vec<char> = key.chars().collect();
+
+        // Find how much of the key's path already exists, remembering the deepest
+        // node that has to survive if the key is later removed through the entry.
+        let mut depth = 0;
+        let mut anchor_depth = 0;
+        let mut node = &self.root;
+        for &c in &chars {
+            let Some(child) = node.get_child(c) else {
+                break;
+            };
+            if node.is_end_of_word() || node.child_count() > 1 {
+                anchor_depth = depth;
+            }
+            node = child;
+            depth += 1;
+        }
+
+        let occupied = depth == chars.len() && node.is_end_of_word();
+        if occupied && node.has_children() {
+            anchor_depth = depth;
+        }
+
+        // Walk back down mutably, only as far as the entry needs to hold on to.
+        let stop = if occupied { anchor_depth } else { depth };
+        let mut current_node = &mut self.root;
+        for &c in &chars[..stop] {
+            current_node = current_node
+                .get_child_mut(c)
+                .expect("path was found by the first pass");
+        }
+
+        let tail = chars[stop..].to_vec();
+        if occupied {
+            Entry::Occupied(OccupiedEntry::new(current_node, tail, &mut self.word_count))
+        } else {
+            Entry::Vacant(VacantEntry::new(current_node, tail, &mut self.word_count))
+        }
+    }
+
     /// Deletes a key and its associated value from the Trie.
     /// Returns true if the key was found and deleted, false otherwise.
     ///
@@ -62,56 +382,223 @@ impl<TValue: Clone> Trie<TValue> {
     /// assert_eq!(trie.get("apple"), None);
     /// ```
     pub fn delete(&mut self, key: &str) -> bool {
+        self.pop(key).is_some()
+    }
+
+    /// Deletes a key from the Trie and returns its value, or `None` if the key
+    /// was not found. Like `delete`, the empty key cannot be removed this way.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// assert_eq!(trie.pop("apple"), Some(1));
+    /// assert_eq!(trie.pop("apple"), None);
+    /// ```
+    pub fn pop(&mut self, key: &str) -> Option<TValue> {
         if key.is_empty() {
-            return false;
+            return None;
         }
-        // We collect the chars to easily pass slices during recursion.
         let chars: Vec<char> = key.chars().collect();
-        let mut deleted = false;
-        Self::delete_recursively(&mut self.root, &chars, &mut deleted);
-        deleted
-    }
-
-    /// Recursive helper to delete a key. Returns true if the calling node
-    /// should remove the child node from its children map (i.e., prune the branch).
-    fn delete_recursively(
-        current_node: &mut TrieNode<TValue>,
-        key_slice: &[char],
-        deleted: &mut bool,
-    ) -> bool {
-        if key_slice.is_empty() {
-            // We have reached the node corresponding to the key.
-            if current_node.is_end_of_word() {
-                current_node.clear_value();
-                *deleted = true;
-                // Return true if this node has no children, so the parent can remove it.
-                return !current_node.has_children();
+
+        // First pass: make sure the key is stored, and find the deepest node
+        // above it that has to survive because it is the root, holds a word or
+        // leads to other words. The nodes below it exist only for this key.
+        let mut anchor_depth = 0;
+        let mut node = &self.root;
+        for (depth, &c) in chars.iter().enumerate() {
+            if depth > 0 && (node.is_end_of_word() || node.child_count() > 1) {
+                anchor_depth = depth;
             }
-            // Key doesn't actually exist as a word in the trie.
-            return false;
+            node = node.get_child(c)?;
+        }
+        if !node.is_end_of_word() {
+            return None;
         }
 
-        let c = key_slice[0];
-        let should_delete_child = if let Some(child_node) = current_node.get_child_mut(c) {
-            // Recurse with the rest of the key
-            Self::delete_recursively(child_node, &key_slice[1..], deleted)
+        // Second pass: if other words continue past the key, only its value goes.
+        // Otherwise the branch below the anchor is cut off and taken apart one
+        // node at a time, so neither pass nor the drop recurses per character.
+        let removed = if node.has_children() {
+            self.find_node_mut(key)
+                .expect("path was found by the first pass")
+                .clear_value()
         } else {
-            // The path for the key doesn't exist.
-            return false;
+            let mut anchor = &mut self.root;
+            for &c in &chars[..anchor_depth] {
+                anchor = anchor
+                    .get_child_mut(c)
+                    .expect("path was found by the first pass");
+            }
+            let mut detached = anchor
+                .remove_child(chars[anchor_depth])
+                .expect("path was found by the first pass");
+            for &c in &chars[anchor_depth + 1..] {
+                detached = detached
+                    .remove_child(c)
+                    .expect("path was found by the first pass");
+            }
+            detached.clear_value()
         };
+        self.word_count -= 1;
+        removed
+    }
+
+    /// Deletes every word that starts with the given prefix, including the prefix
+    /// itself, and returns how many words were removed. An empty prefix clears
+    /// the whole Trie.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("app", 1);
+    /// trie.insert("apple", 2);
+    /// trie.insert("banana", 3);
+    /// assert_eq!(trie.delete_prefix("app"), 2);
+    /// assert_eq!(trie.prefix_search("app"), false);
+    /// assert_eq!(trie.get("banana"), Some(&3));
+    /// ```
+    pub fn delete_prefix(&mut self, prefix: &str) -> usize {
+        let chars: Vec<char> = prefix.chars().collect();
+        if chars.is_empty() {
+            let removed = self.word_count;
+            self.root = self.root.empty_like();
+            self.word_count = 0;
+            return removed;
+        }
+
+        // Find the deepest node above the prefix that has to survive because it
+        // holds a word or leads to other words. Everything below it on the
+        // prefix's path goes away with the subtrie.
+        let mut anchor_depth = 0;
+        let mut node = &self.root;
+        for (depth, &c) in chars.iter().enumerate() {
+            if depth > 0 && (node.is_end_of_word() || node.child_count() > 1) {
+                anchor_depth = depth;
+            }
+            match node.get_child(c) {
+                Some(child) => node = child,
+                None => return 0,
+            }
+        }
+
+        let mut anchor = &mut self.root;
+        for &c in &chars[..anchor_depth] {
+            anchor = anchor
+                .get_child_mut(c)
+                .expect("path was found by the first pass");
+        }
+        let detached = anchor
+            .remove_child(chars[anchor_depth])
+            .expect("path was found by the first pass");
+
+        let removed = Values::new(&detached).count();
+        self.word_count -= removed;
+        removed
+    }
+
+    /// Removes every entry for which `f(key, value)` returns false, pruning
+    /// nodes that no longer lead to any word. Returns the number of removed
+    /// entries.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// trie.insert("banana", 2);
+    /// trie.insert("cherry", 3);
+    /// assert_eq!(trie.retain(|_, value| value % 2 == 1), 1);
+    /// assert_eq!(trie.get("banana"), None);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&str, &TValue) -> bool,
+    {
+        let mut removed = 0;
+        let mut key = String::new();
+        Self::retain_recursively(&mut self.root, &mut key, &mut f, &mut removed);
+        self.word_count -= removed;
+        removed
+    }
 
-        if should_delete_child {
+    /// Recursive helper for `retain`. Visits the node and its subtree in
+    /// post-order and returns true if the node no longer leads to any word, so
+    /// the parent can remove it.
+    fn retain_recursively<F>(
+        current_node: &mut TrieNode<TValue, H>,
+        key: &mut String,
+        f: &mut F,
+        removed: &mut usize,
+    ) -> bool
+    where
+        F: FnMut(&str, &TValue) -> bool,
+    {
+        if let Some(value) = current_node.get_value() {
+            if !f(key, value) {
+                current_node.clear_value();
+                *removed += 1;
+            }
+        }
+
+        let mut emptied_children = Vec::new();
+        for (&c, child_node) in current_node.children_iter_mut() {
+            key.push(c);
+            if Self::retain_recursively(child_node, key, f, removed) {
+                emptied_children.push(c);
+            }
+            key.pop();
+        }
+        for c in emptied_children {
             current_node.remove_child(c);
-            // After removing the child, if this current node is not the end of another word
-            // and has no other children, it should also be deleted by its parent.
-            return !current_node.is_end_of_word() && !current_node.has_children();
         }
 
-        false
+        !current_node.is_end_of_word() && !current_node.has_children()
+    }
+
+    /// Calls `f` with the full key and a mutable reference to the value of every
+    /// entry whose key starts with `prefix`, and returns how many entries it
+    /// visited. The entries are updated in place in unspecified order, without
+    /// collecting or reinserting them.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("user.activity.clicks", 100.0);
+    /// trie.insert("user.activity.views", 50.0);
+    /// trie.insert("user.name_changes", 2.0);
+    /// let decayed = trie.update_with_prefix("user.activity.", |_, score| *score *= 0.9);
+    /// assert_eq!(decayed, 2);
+    /// assert_eq!(trie.get("user.activity.views"), Some(&45.0));
+    /// ```
+    pub fn update_with_prefix<F>(&mut self, prefix: &str, mut f: F) -> usize
+    where
+        F: FnMut(&str, &mut TValue),
+    {
+        let Some(start) = self.find_node_mut(prefix) else {
+            return 0;
+        };
+
+        let mut updated = 0;
+        let mut stack = vec![(prefix.to_string(), start)];
+        while let Some((key, node)) = stack.pop() {
+            let (value, children) = node.value_and_children_mut();
+            if let Some(value) = value {
+                f(&key, value);
+                updated += 1;
+            }
+            for (&c, child) in children {
+                let mut child_key = key.clone();
+                child_key.push(c);
+                stack.push((child_key, child));
+            }
+        }
+        updated
     }
 
     /// Checks if there is any word in the trie that starts with the given prefix.
-    /// Returns true if such a prefix exists, false otherwise.
+    /// Returns true if such a prefix exists, false otherwise. The empty prefix
+    /// is always found, even in an empty Trie, as it always has been; use
+    /// `prefix_match` to tell whether any key exists.
     /// Example:
     /// ```Rust
     /// let mut trie = Trie::new();
@@ -120,100 +607,1811 @@ impl<TValue: Clone> Trie<TValue> {
     /// assert_eq!(trie.prefix_search("apl"), false);
     /// ```
     pub fn prefix_search(&self, prefix: &str) -> bool {
-        let mut current_node = &self.root;
+        // Every node but the root leads to a word, so reaching one is enough.
+        self.find_node(prefix).is_some()
+    }
 
-        for c in prefix.chars() {
-            if let Some(child_node) = current_node.get_child(c) {
-                current_node = child_node;
-            } else {
-                return false;
-            }
+    /// Tells whether `prefix` is a stored key, the start of longer stored keys,
+    /// both, or neither, in a single lookup. Search boxes can use this to mark
+    /// an exact match differently from a partial one.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("app", 1);
+    /// trie.insert("apple", 2);
+    /// assert_eq!(trie.prefix_match("ap"), PrefixMatchResult::PrefixOfStoredKey);
+    /// assert_eq!(trie.prefix_match("app"), PrefixMatchResult::ExactAndPrefix);
+    /// assert_eq!(trie.prefix_match("apple"), PrefixMatchResult::ExactStoredKey);
+    /// assert_eq!(trie.prefix_match("apt"), PrefixMatchResult::NoMatch);
+    /// ```
+    pub fn prefix_match(&self, prefix: &str) -> PrefixMatchResult {
+        let Some(node) = self.find_node(prefix) else {
+            return PrefixMatchResult::NoMatch;
+        };
+        // Only the root can be a node with neither a value nor children.
+        match (node.is_end_of_word(), node.has_children()) {
+            (false, false) => PrefixMatchResult::NoMatch,
+            (false, true) => PrefixMatchResult::PrefixOfStoredKey,
+            (true, false) => PrefixMatchResult::ExactStoredKey,
+            (true, true) => PrefixMatchResult::ExactAndPrefix,
         }
-        true
     }
 
-    /// Returns up to `max_results` words in the trie that start with the given prefix.
-    /// If no words match the prefix, returns an empty vector.
+    /// Counts the words that start with the given prefix, including the prefix
+    /// itself if it is a word. No strings are built while counting.
+    ///
     /// Example:
     /// ```Rust
     /// let mut trie = Trie::new();
-    /// trie.insert("apple", 1);
-    /// trie.insert("app", 2);
-    /// trie.insert("apricot", 3);
-    /// let results = trie.auto_complete("ap", 2);
-    /// assert_eq!(results, vec!["app", "apple"]);
+    /// trie.insert("app", 1);
+    /// trie.insert("apple", 2);
+    /// trie.insert("banana", 3);
+    /// assert_eq!(trie.count_with_prefix("app"), 2);
+    /// assert_eq!(trie.count_with_prefix("x"), 0);
     /// ```
-    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
-        let mut results = Vec::new();
-        if max_results == 0 {
-            return results;
+    pub fn count_with_prefix(&self, prefix: &str) -> usize {
+        match self.find_node(prefix) {
+            Some(node) => Values::new(node).count(),
+            None => 0,
         }
-        let mut current_node = &self.root;
+    }
 
-        for c in prefix.chars() {
-            if let Some(child_node) = current_node.get_child(c) {
-                current_node = child_node;
-            } else {
-                return results;
-            }
+    /// Returns the number of nodes in the Trie, including the root and every
+    /// intermediate node that does not end a word. An empty Trie has one node.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("hello", 1);
+    /// assert_eq!(trie.node_count(), 6);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        let mut count = 0;
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            count += 1;
+            stack.extend(node.children_iter().map(|(_, child)| child));
         }
-        if current_node.is_end_of_word() {
-            results.push(prefix.to_string());
-            if results.len() >= max_results {
-                return results;
+        count
+    }
+
+    /// Returns how many stored keys have each length, counted in characters.
+    /// The counts add up to `len()`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("cat", 1);
+    /// trie.insert("car", 2);
+    /// trie.insert("cards", 3);
+    /// let histogram = trie.key_length_histogram();
+    /// assert_eq!(histogram.get(&3), Some(&2));
+    /// assert_eq!(histogram.get(&5), Some(&1));
+    /// ```
+    pub fn key_length_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        let mut stack = vec![(&self.root, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            if node.is_end_of_word() {
+                *histogram.entry(depth).or_insert(0) += 1;
             }
+            stack.extend(node.children_iter().map(|(_, child)| (child, depth + 1)));
         }
+        histogram
+    }
 
-        Self::collect_words_recursive(current_node, prefix.to_string(), &mut results, max_results);
-        results
+    /// Returns the length in characters of the longest stored key, or 0 if the
+    /// Trie is empty. Same as `height`.
+    pub fn max_key_length(&self) -> usize {
+        self.height()
     }
 
-    fn collect_words_recursive(
-        node: &TrieNode<TValue>,
-        curr_prefix: String,
-        results: &mut Vec<String>,
-        max_results: usize,
-    ) {
-        if results.len() >= max_results {
-            return;
-        }
-        for (char, child) in node.children_iter() {
-            if results.len() >= max_results {
-                return;
-            }
-            let new_prefix = format!("{curr_prefix}{char}");
-            if child.is_end_of_word() {
-                results.push(new_prefix.clone());
-            }
-            Self::collect_words_recursive(child, new_prefix, results, max_results);
-        }
+    /// Returns the length in characters of the shortest stored key, or 0 if the
+    /// Trie is empty.
+    pub fn min_key_length(&self) -> usize {
+        self.key_length_histogram()
+            .first_key_value()
+            .map_or(0, |(&length, _)| length)
     }
 
-    /// Adds multiple words to the trie from a list, using a value generator function
-    /// to determine the value associated with each word.
-    /// This is useful for bulk insertion where the value might depend on the word itself.
+    /// Returns the mean length in characters of the stored keys, or 0.0 if the
+    /// Trie is empty.
+    pub fn avg_key_length(&self) -> f64 {
+        self.average_key_length()
+    }
+
+    /// Returns the number of characters in `key` if it is stored, or `None`
+    /// if it is not. This is the depth of the key's node below the root.
+    ///
     /// Example:
     /// ```Rust
     /// let mut trie = Trie::new();
-    /// trie.add_word_list(&["cat", "car", "cart"], |word| word.len());
-    /// assert_eq!(trie.get("cat"), Some(&3));
-    /// assert_eq!(trie.get("car"), Some(&3));
-    /// assert_eq!(trie.get("cart"), Some(&4));
+    /// trie.insert("héllo", 1);
+    /// assert_eq!(trie.depth("héllo"), Some(5));
+    /// assert_eq!(trie.depth("hé"), None);
     /// ```
-    pub fn add_word_list<T, F>(&mut self, items: &[T], value_generator: F)
-    where
-        T: AsRef<str>,
-        F: Fn(&T) -> TValue,
-    {
-        for item in items {
-            self.insert(item.as_ref(), &value_generator(item));
+    pub fn depth(&self, key: &str) -> Option<usize> {
+        self.get(key).map(|_| key.chars().count())
+    }
+
+    /// Returns the length in characters of the longest stored key, which is
+    /// the depth of the deepest word node, or 0 if the Trie is empty. Useful
+    /// for sizing buffers or depth budgets before walking the Trie.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("a", 1);
+    /// trie.insert("abc", 2);
+    /// assert_eq!(trie.height(), 3);
+    /// ```
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack = vec![(&self.root, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            if node.is_end_of_word() {
+                height = height.max(depth);
+            }
+            stack.extend(node.children_iter().map(|(_, child)| (child, depth + 1)));
         }
+        height
     }
-}
 
-/// Allows creating a new Trie with `Trie::default()`.
-impl<TValue: Clone> Default for Trie<TValue> {
+    /// Returns the mean length in characters of the stored keys, or 0.0 if the
+    /// Trie is empty.
+    pub fn average_key_length(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let mut total = 0;
+        let mut stack = vec![(&self.root, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            if node.is_end_of_word() {
+                total += depth;
+            }
+            stack.extend(node.children_iter().map(|(_, child)| (child, depth + 1)));
+        }
+        total as f64 / self.len() as f64
+    }
+
+    /// Returns how many distinct characters follow `prefix`, i.e. the number of
+    /// children of its node, or `None` if no key starts with `prefix`. A Trie
+    /// with low branching factors along long paths compresses well into a
+    /// `RadixTrie`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("car", 1);
+    /// trie.insert("cat", 2);
+    /// assert_eq!(trie.branching_factor_at("ca"), Some(2));
+    /// assert_eq!(trie.branching_factor_at("car"), Some(0));
+    /// assert_eq!(trie.branching_factor_at("dog"), None);
+    /// ```
+    pub fn branching_factor_at(&self, prefix: &str) -> Option<usize> {
+        self.find_node(prefix).map(TrieNode::child_count)
+    }
+
+    /// Estimates the bytes used by the Trie's structure: the Trie itself plus
+    /// the allocated capacity of every node's children map. Child nodes live
+    /// inside their parent's map, so each is counted once through that capacity.
+    /// Heap memory owned by the values themselves, and the maps' per-slot
+    /// bookkeeping, are not included.
+    pub fn memory_estimate(&self) -> usize {
+        let slot_size = core::mem::size_of::<(char, TrieNode<TValue, H>)>();
+        let mut bytes = core::mem::size_of::<Self>();
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            bytes += node.children_capacity() * slot_size;
+            stack.extend(node.children_iter().map(|(_, child)| child));
+        }
+        bytes
+    }
+
+    /// Finds the longest stored key that is a prefix of `query`, returning the
+    /// key's length in bytes along with its value. The length can be used to
+    /// split off the unmatched remainder with `&query[len..]`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("/api", 1);
+    /// trie.insert("/api/users", 2);
+    /// let (len, value) = trie.longest_prefix_match("/api/users/42").unwrap();
+    /// assert_eq!((len, value), (10, &2));
+    /// assert_eq!(trie.longest_prefix_match("/static"), None);
+    /// ```
+    pub fn longest_prefix_match(&self, query: &str) -> Option<(usize, &TValue)> {
+        let mut current_node = &self.root;
+        let mut longest = current_node.get_value().map(|value| (0, value));
+
+        for (offset, c) in query.char_indices() {
+            match current_node.get_child(c) {
+                Some(child_node) => current_node = child_node,
+                None => break,
+            }
+            if let Some(value) = current_node.get_value() {
+                longest = Some((offset + c.len_utf8(), value));
+            }
+        }
+        longest
+    }
+
+    /// Returns the value of the longest stored key that is a prefix of `query`.
+    /// A value-only `longest_prefix_match` for hot lookup paths such as routing
+    /// tables; it allocates nothing.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("/api", 1);
+    /// trie.insert("/api/users", 2);
+    /// assert_eq!(trie.get_longest_match("/api/users/42"), Some(&2));
+    /// assert_eq!(trie.get_longest_match("/api/orders"), Some(&1));
+    /// assert_eq!(trie.get_longest_match("/static"), None);
+    /// ```
+    pub fn get_longest_match(&self, query: &str) -> Option<&TValue> {
+        let mut current_node = &self.root;
+        let mut last_value = current_node.get_value();
+
+        for c in query.chars() {
+            match current_node.get_child(c) {
+                Some(child_node) => current_node = child_node,
+                None => break,
+            }
+            if let Some(value) = current_node.get_value() {
+                last_value = Some(value);
+            }
+        }
+        last_value
+    }
+
+    /// Returns true if any stored key is a prefix of `query`, the inverse of
+    /// `prefix_search`. Stops at the first stored key found along the way, which
+    /// makes it a cheap check against a blocklist of prefixes.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut blocked = Trie::new();
+    /// blocked.insert("http://ads.", ());
+    /// assert!(blocked.contains_key_as_prefix_of("http://ads.example.com/banner"));
+    /// assert!(!blocked.contains_key_as_prefix_of("http://example.com"));
+    /// ```
+    pub fn contains_key_as_prefix_of(&self, query: &str) -> bool {
+        let mut current_node = &self.root;
+        if current_node.is_end_of_word() {
+            return true;
+        }
+        for c in query.chars() {
+            match current_node.get_child(c) {
+                Some(child_node) => current_node = child_node,
+                None => return false,
+            }
+            if current_node.is_end_of_word() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns every stored key that is a prefix of `query`, with its value, in
+    /// order of increasing length.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("app", 1);
+    /// trie.insert("application", 2);
+    /// trie.insert("apply", 3);
+    /// let matches = trie.all_prefixes_of("applications");
+    /// assert_eq!(matches, vec![("app".to_string(), &1), ("application".to_string(), &2)]);
+    /// ```
+    pub fn all_prefixes_of(&self, query: &str) -> Vec<(String, &TValue)> {
+        let mut current_node = &self.root;
+        let mut results = Vec::new();
+        if let Some(value) = current_node.get_value() {
+            results.push((String::new(), value));
+        }
+
+        for (offset, c) in query.char_indices() {
+            match current_node.get_child(c) {
+                Some(child_node) => current_node = child_node,
+                None => break,
+            }
+            if let Some(value) = current_node.get_value() {
+                let end = offset + c.len_utf8();
+                results.push((query[..end].to_string(), value));
+            }
+        }
+        results
+    }
+
+    /// Returns the longest string that is a prefix of every stored key, such as
+    /// the shared namespace of keys loaded from a configuration file. Empty if
+    /// the Trie is empty or its keys start differently.
+    ///
+    /// Only the chain of single-child nodes below the root is followed, so this
+    /// takes time proportional to the result, not to the number of keys.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("com.example.host", 1);
+    /// trie.insert("com.example.port", 2);
+    /// assert_eq!(trie.common_prefix_of_all_keys(), "com.example.");
+    /// ```
+    pub fn common_prefix_of_all_keys(&self) -> String {
+        let mut prefix = String::new();
+        let mut current_node = &self.root;
+        // A stored key ends the shared prefix, since nothing longer prefixes it.
+        while !current_node.is_end_of_word() && current_node.child_count() == 1 {
+            let Some((&c, child)) = current_node.children_iter().next() else {
+                break;
+            };
+            prefix.push(c);
+            current_node = child;
+        }
+        prefix
+    }
+
+    /// Returns the shortest prefix of a stored `key` that no other stored key
+    /// starts with, or `None` if `key` is not stored. When the key is itself a
+    /// prefix of another key, no shorter prefix can be unique and the whole key
+    /// is returned.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("cat", 1);
+    /// trie.insert("car", 2);
+    /// trie.insert("dog", 3);
+    /// assert_eq!(trie.shortest_unique_prefix("cat"), Some("cat".to_string()));
+    /// assert_eq!(trie.shortest_unique_prefix("dog"), Some("d".to_string()));
+    /// assert_eq!(trie.shortest_unique_prefix("cow"), None);
+    /// ```
+    pub fn shortest_unique_prefix(&self, key: &str) -> Option<String> {
+        // The prefix must reach one character past the deepest node where
+        // another key branches off, either by ending there or by taking a
+        // different child.
+        let mut current_node = &self.root;
+        let mut unique_len = 0;
+        for (depth, c) in key.chars().enumerate() {
+            if current_node.is_end_of_word() || current_node.child_count() > 1 {
+                unique_len = depth + 1;
+            }
+            current_node = current_node.get_child(c)?;
+        }
+
+        if !current_node.is_end_of_word() {
+            return None;
+        }
+        if current_node.has_children() {
+            return Some(key.to_string());
+        }
+        Some(key.chars().take(unique_len).collect())
+    }
+
+    /// Returns up to `max_results` words in the trie that start with the given prefix,
+    /// in lexicographic order. If no words match the prefix, returns an empty vector.
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// trie.insert("app", 2);
+    /// trie.insert("apricot", 3);
+    /// let results = trie.auto_complete("ap", 2);
+    /// assert_eq!(results, vec!["app", "apple"]);
+    /// ```
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        self.auto_complete_with_values(prefix, max_results)
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Returns up to `max_results` words that start with the given prefix along
+    /// with their values, in the same order as `auto_complete`. Saves a `get`
+    /// per result when the values are needed too, e.g. to show or rank them.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 10);
+    /// trie.insert("app", 20);
+    /// let results = trie.auto_complete_with_values("ap", 10);
+    /// assert_eq!(results, vec![("app".to_string(), &20), ("apple".to_string(), &10)]);
+    /// ```
+    pub fn auto_complete_with_values(
+        &self,
+        prefix: &str,
+        max_results: usize,
+    ) -> Vec<(String, &TValue)> {
+        let mut results = Vec::new();
+        if max_results == 0 {
+            return results;
+        }
+        let mut current_node = &self.root;
+
+        for c in prefix.chars() {
+            if let Some(child_node) = current_node.get_child(c) {
+                current_node = child_node;
+            } else {
+                return results;
+            }
+        }
+        if let Some(value) = current_node.get_value() {
+            results.push((prefix.to_string(), value));
+            if results.len() >= max_results {
+                return results;
+            }
+        }
+
+        Self::collect_words(current_node, prefix.to_string(), &mut results, max_results);
+        results
+    }
+
+    /// Returns the same completions as `auto_complete_with_values`, each with
+    /// the span of its key that matched, so a UI can highlight it.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// let results = trie.auto_complete_with_spans("ap", 10);
+    /// assert_eq!(results[0].matched_char_range, 0..2);
+    /// assert_eq!(results[0].matched(), "ap");
+    /// ```
+    pub fn auto_complete_with_spans(
+        &self,
+        prefix: &str,
+        max_results: usize,
+    ) -> Vec<CompletionResult<'_, TValue>> {
+        // Every completion starts with the prefix, so they all share one span.
+        let matched = 0..prefix.chars().count();
+        self.auto_complete_with_values(prefix, max_results)
+            .into_iter()
+            .map(|(key, value)| CompletionResult {
+                key,
+                value,
+                matched_char_range: matched.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns up to `max_results` words that start with the given prefix, in
+    /// lexicographic order. Produces the same result as `auto_complete`, but walks
+    /// the Trie with the lazy `keys` iterator instead of recursing.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apricot", 1);
+    /// trie.insert("apple", 2);
+    /// trie.insert("app", 3);
+    /// assert_eq!(trie.sorted_auto_complete("ap", 2), vec!["app", "apple"]);
+    /// ```
+    pub fn sorted_auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        match self.find_node(prefix) {
+            Some(node) => Keys::new(node, prefix.to_string())
+                .take(max_results)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the `max_results` best completions of `prefix` with their values,
+    /// highest `score` first. Completions with equal scores stay in
+    /// lexicographic order.
+    ///
+    /// Unlike `auto_complete`, this cannot stop early: good ranking needs every
+    /// completion scored, so the cost is O(n log n) in the number of words under
+    /// the prefix, however small `max_results` is.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 3);
+    /// trie.insert("app", 1);
+    /// trie.insert("apricot", 2);
+    /// // Shortest completions first
+    /// let results = trie.auto_complete_ranked_by("ap", 2, |key, _| std::cmp::Reverse(key.len()));
+    /// assert_eq!(results, vec![("app".to_string(), &1), ("apple".to_string(), &3)]);
+    /// ```
+    pub fn auto_complete_ranked_by<'a, F, K>(
+        &'a self,
+        prefix: &str,
+        max_results: usize,
+        score: F,
+    ) -> Vec<(String, &'a TValue)>
+    where
+        F: Fn(&str, &'a TValue) -> K,
+        K: Ord,
+    {
+        let mut scored: Vec<(K, String, &TValue)> = self
+            .prefix_iter(prefix)
+            .map(|(key, value)| (score(&key, value), key, value))
+            .collect();
+        // A stable sort keeps ties in the lexicographic order they arrived in.
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored
+            .into_iter()
+            .take(max_results)
+            .map(|(_, key, value)| (key, value))
+            .collect()
+    }
+
+    /// Returns the `max_results` completions of `prefix` with the largest
+    /// values, largest first, e.g. the most frequent words when the values are
+    /// counts. Like `auto_complete_ranked_by`, this looks at every completion.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("the", 500);
+    /// trie.insert("then", 40);
+    /// trie.insert("there", 90);
+    /// let results = trie.auto_complete_ranked("the", 2);
+    /// assert_eq!(results, vec![("the".to_string(), &500), ("there".to_string(), &90)]);
+    /// ```
+    pub fn auto_complete_ranked(&self, prefix: &str, max_results: usize) -> Vec<(String, &TValue)>
+    where
+        TValue: Ord,
+    {
+        self.auto_complete_ranked_by(prefix, max_results, |_, value| value)
+    }
+
+    /// Returns the `k` entries with the largest values, largest first. Entries
+    /// with equal values stay in lexicographic order.
+    ///
+    /// Only the best `k` entries seen so far are kept, in a min-heap, so this
+    /// takes O(n log k) time and O(k) extra space for n entries, against the
+    /// full sort behind `auto_complete_ranked`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("the", 500);
+    /// trie.insert("then", 40);
+    /// trie.insert("a", 300);
+    /// assert_eq!(trie.top_k_by_value(2), vec![("the".to_string(), &500), ("a".to_string(), &300)]);
+    /// ```
+    pub fn top_k_by_value(&self, k: usize) -> Vec<(String, &TValue)>
+    where
+        TValue: Ord,
+    {
+        self.top_k_by_value_with_prefix("", k)
+    }
+
+    /// Like `top_k_by_value`, but only considers keys that start with `prefix`.
+    pub fn top_k_by_value_with_prefix(&self, prefix: &str, k: usize) -> Vec<(String, &TValue)>
+    where
+        TValue: Ord,
+    {
+        if k == 0 {
+            return Vec::new();
+        }
+        // Ranked by value, then by arrival order so that among equal values the
+        // lexicographically smaller key wins. The order is unique, so keys are
+        // never compared.
+        // Each push beyond `k` follows a pop, so the heap never holds more
+        // than `k`, nor more than the Trie's keys however large `k` is.
+        let mut heap = BinaryHeap::with_capacity(k.min(self.len()));
+        for (order, (key, value)) in self.prefix_iter(prefix).enumerate() {
+            let candidate = Reverse((value, Reverse(order), key));
+            if heap.len() < k {
+                heap.push(candidate);
+            } else if heap.peek().is_some_and(|worst| candidate < *worst) {
+                heap.pop();
+                heap.push(candidate);
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((value, _, key))| (key, value))
+            .collect()
+    }
+
+    /// Returns a lazy iterator over the words that start with `prefix`, in
+    /// lexicographic order. Each call to `next` resumes the walk where the last
+    /// one stopped, so no `max_results` is needed.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// trie.insert("app", 2);
+    /// trie.insert("application", 3);
+    /// let first: Vec<String> = trie.auto_complete_iter("app").take(2).collect();
+    /// assert_eq!(first, vec!["app", "apple"]);
+    /// ```
+    pub fn auto_complete_iter(&self, prefix: &str) -> AutoCompleteIter<'_, TValue, H> {
+        AutoCompleteIter::new(self.prefix_iter(prefix))
+    }
+
+    /// Returns a lazy iterator over the (key, value) pairs whose keys start with
+    /// `prefix`, in lexicographic order. Unlike `auto_complete`, no limit is
+    /// needed up front: only as much of the Trie is walked as is consumed.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// trie.insert("app", 2);
+    /// trie.insert("banana", 3);
+    /// let first: Vec<_> = trie.prefix_iter("app").take(1).collect();
+    /// assert_eq!(first, vec![("app".to_string(), &2)]);
+    /// ```
+    pub fn prefix_iter(&self, prefix: &str) -> PrefixIter<'_, TValue, H> {
+        match self.find_node(prefix) {
+            Some(node) => PrefixIter::new(node, prefix.to_string()),
+            None => PrefixIter::empty(),
+        }
+    }
+
+    /// Appends the words below `node` to `results` in lexicographic order until
+    /// it holds `max_results`. The walk keeps its own stack of frames instead of
+    /// recursing, so deep keys cannot overflow the call stack.
+    fn collect_words<'a>(
+        node: &'a TrieNode<TValue, H>,
+        prefix: String,
+        results: &mut Vec<(String, &'a TValue)>,
+        max_results: usize,
+    ) {
+        let mut key = prefix;
+        let mut stack = vec![Frame {
+            key_len: key.len(),
+            // Visiting children in `char` order makes the result lexicographic
+            // rather than dependent on HashMap iteration order.
+            children: node.children_iter_sorted(),
+        }];
+        while results.len() < max_results {
+            let Some(frame) = stack.last_mut() else {
+                break;
+            };
+            let Some((&c, child)) = frame.children.next() else {
+                stack.pop();
+                continue;
+            };
+            key.truncate(frame.key_len);
+            key.push(c);
+            if let Some(value) = child.get_value() {
+                results.push((key.clone(), value));
+            }
+            stack.push(Frame {
+                key_len: key.len(),
+                children: child.children_iter_sorted(),
+            });
+        }
+    }
+
+    /// Wraps an already-built root node. `word_count` must equal the number of
+    /// values stored under it.
+    pub(crate) fn from_root(root: TrieNode<TValue, H>, word_count: usize) -> Self {
+        Trie {
+            root,
+            word_count,
+            sorted_children: false,
+        }
+    }
+
+    /// Makes `values`, `values_mut`, `iter_mut`, `into_iter` and `drain` visit
+    /// each node's children in `char` order, as `iter` always does.
+    pub(crate) fn set_sorted_children(&mut self, sorted: bool) {
+        self.sorted_children = sorted;
+    }
+
+    /// Returns true if the Trie was built with `TrieBuilder::sorted_children`,
+    /// so that `values`, `values_mut`, `iter_mut`, `into_iter` and `drain`
+    /// yield entries in lexicographic key order rather than in no particular
+    /// order.
+    pub fn sorted_children(&self) -> bool {
+        self.sorted_children
+    }
+
+    /// Removes the value stored under the empty key, which `delete` and `pop`
+    /// leave alone.
+    pub(crate) fn take_root_value(&mut self) -> Option<TValue> {
+        let removed = self.root.clear_value();
+        if removed.is_some() {
+            self.word_count -= 1;
+        }
+        removed
+    }
+
+    /// Consumes the Trie, handing its root node to a sibling module.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn into_root(self) -> TrieNode<TValue, H> {
+        self.root
+    }
+
+    /// Gives sibling modules read access to the root node.
+    pub(crate) fn root(&self) -> &TrieNode<TValue, H> {
+        &self.root
+    }
+
+    /// Walks down the path for `prefix`, returning the node it ends at.
+    pub(crate) fn find_node(&self, prefix: &str) -> Option<&TrieNode<TValue, H>> {
+        let mut current_node = &self.root;
+        for c in prefix.chars() {
+            current_node = current_node.get_child(c)?;
+        }
+        Some(current_node)
+    }
+
+    /// Mutable counterpart of `find_node`.
+    pub(crate) fn find_node_mut(&mut self, prefix: &str) -> Option<&mut TrieNode<TValue, H>> {
+        let mut current_node = &mut self.root;
+        for c in prefix.chars() {
+            current_node = current_node.get_child_mut(c)?;
+        }
+        Some(current_node)
+    }
+
+    /// Returns an independent copy of every entry whose key starts with `prefix`,
+    /// with the prefix stripped from the keys. If the prefix is itself a stored
+    /// key, its value ends up under the empty key. Returns `None` if no key starts
+    /// with `prefix`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("user.name", 1);
+    /// trie.insert("user.email", 2);
+    /// trie.insert("system.port", 3);
+    /// let users = trie.subtrie("user.").unwrap();
+    /// assert_eq!(users.get("name"), Some(&1));
+    /// assert_eq!(users.len(), 2);
+    /// ```
+    pub fn subtrie(&self, prefix: &str) -> Option<Trie<TValue, H>>
+    where
+        TValue: Clone,
+    {
+        let node = self.find_node(prefix)?;
+        let word_count = Values::new(node).count();
+        if word_count == 0 {
+            return None;
+        }
+        Some(Trie {
+            root: node.clone(),
+            word_count,
+            sorted_children: self.sorted_children,
+        })
+    }
+
+    /// Moves every entry of `other` into this Trie. When a key exists in both,
+    /// `on_conflict(self_value, other_value)` decides the value that is kept.
+    /// Returns the number of conflicting keys.
+    ///
+    /// Both tries are walked together, so whole branches that only exist in
+    /// `other` are moved over without visiting their entries one by one.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut a = Trie::new();
+    /// a.insert("apple", 1);
+    /// let mut b = Trie::new();
+    /// b.insert("apple", 10);
+    /// b.insert("banana", 2);
+    /// let conflicts = a.merge(b, |mine, theirs| mine + theirs);
+    /// assert_eq!(conflicts, 1);
+    /// assert_eq!(a.get("apple"), Some(&11));
+    /// ```
+    pub fn merge<F>(&mut self, other: Trie<TValue, H>, mut on_conflict: F) -> usize
+    where
+        F: FnMut(TValue, TValue) -> TValue,
+    {
+        let mut added = 0;
+        let mut conflicts = 0;
+        Self::merge_recursively(
+            &mut self.root,
+            other.root,
+            &mut on_conflict,
+            &mut added,
+            &mut conflicts,
+        );
+        self.word_count += added;
+        conflicts
+    }
+
+    /// Merges `other` into this Trie, taking `other`'s value on conflicts.
+    /// Returns the number of conflicting keys.
+    pub fn merge_overwrite(&mut self, other: Trie<TValue, H>) -> usize {
+        self.merge(other, |_, theirs| theirs)
+    }
+
+    /// Merges `other` into this Trie, keeping this Trie's value on conflicts.
+    /// Returns the number of conflicting keys.
+    pub fn merge_keep_self(&mut self, other: Trie<TValue, H>) -> usize {
+        self.merge(other, |mine, _| mine)
+    }
+
+    /// Merges `other` into this Trie, adding the values together on conflicts.
+    /// Returns the number of conflicting keys.
+    pub fn merge_sum(&mut self, other: Trie<TValue, H>) -> usize
+    where
+        TValue: Add<Output = TValue>,
+    {
+        self.merge(other, |mine, theirs| mine + theirs)
+    }
+
+    /// Merges `other` into this Trie, keeping the larger value on conflicts.
+    /// Returns the number of conflicting keys.
+    pub fn merge_max(&mut self, other: Trie<TValue, H>) -> usize
+    where
+        TValue: Ord,
+    {
+        self.merge(other, |mine, theirs| mine.max(theirs))
+    }
+
+    /// Returns a new Trie with every key from `self` and `other`. Where both
+    /// store a key, the value from `self` wins. Also available as `&a | &b`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut a = Trie::new();
+    /// a.insert("cat", 1);
+    /// let mut b = Trie::new();
+    /// b.insert("cat", 9);
+    /// b.insert("dog", 2);
+    /// let both = a.union(&b);
+    /// assert_eq!(both.get("cat"), Some(&1));
+    /// assert_eq!(both.get("dog"), Some(&2));
+    /// ```
+    pub fn union(&self, other: &Trie<TValue, H>) -> Trie<TValue, H>
+    where
+        TValue: Clone,
+    {
+        let mut result = self.clone();
+        result.merge_keep_self(other.clone());
+        result
+    }
+
+    /// Returns a new Trie with the entries of `self` whose keys are also in
+    /// `other`, whatever `other` stores under them. Also available as `&a & &b`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut words = Trie::new();
+    /// words.insert("cat", 1);
+    /// words.insert("dog", 2);
+    /// let mut pets = Trie::new();
+    /// pets.insert("cat", ());
+    /// assert_eq!(words.intersection(&pets).keys().collect::<Vec<_>>(), vec!["cat"]);
+    /// ```
+    pub fn intersection<W>(&self, other: &Trie<W, H>) -> Trie<TValue, H>
+    where
+        TValue: Clone,
+    {
+        Self::build_sorted(
+            self.root.empty_like(),
+            self.iter()
+                .filter(|(key, _)| other.get(key).is_some())
+                .map(|(key, value)| (key, value.clone())),
+        )
+    }
+
+    /// Returns a new Trie with the entries of `self` whose keys are not in
+    /// `other`, e.g. a vocabulary minus its stop words. Also available as
+    /// `&a - &b`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut words = Trie::new();
+    /// words.insert("the", 1);
+    /// words.insert("trie", 2);
+    /// let mut stop_words = Trie::new();
+    /// stop_words.insert("the", ());
+    /// assert_eq!(words.difference(&stop_words).keys().collect::<Vec<_>>(), vec!["trie"]);
+    /// ```
+    pub fn difference<W>(&self, other: &Trie<W, H>) -> Trie<TValue, H>
+    where
+        TValue: Clone,
+    {
+        Self::build_sorted(
+            self.root.empty_like(),
+            self.iter()
+                .filter(|(key, _)| other.get(key).is_none())
+                .map(|(key, value)| (key, value.clone())),
+        )
+    }
+
+    /// Splits the entries into two new tries: those for which `f(key, value)`
+    /// returns true, and the rest. The Trie itself is left as it is; this is
+    /// the copying counterpart of `retain`, and the Trie version of
+    /// `Iterator::partition`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let counts = trie! { "the" => 500, "cat" => 3, "then" => 120 };
+    /// let (common, rare) = counts.partition(|_, count| *count >= 100);
+    /// assert_eq!(common.len(), 2);
+    /// assert_eq!(rare.keys().collect::<Vec<_>>(), vec!["cat"]);
+    /// ```
+    pub fn partition<F>(&self, mut f: F) -> (Trie<TValue, H>, Trie<TValue, H>)
+    where
+        TValue: Clone,
+        F: FnMut(&str, &TValue) -> bool,
+    {
+        let (matching, rest): (Vec<_>, Vec<_>) = self
+            .iter()
+            .map(|(key, value)| (key, value.clone()))
+            .partition(|(key, value)| f(key, value));
+        (
+            Self::build_sorted(self.root.empty_like(), matching),
+            Self::build_sorted(self.root.empty_like(), rest),
+        )
+    }
+
+    /// Returns a new Trie with the keys stored in both `self` and `other`, each
+    /// holding the pair of their values, e.g. to join word frequencies with
+    /// word vectors.
+    ///
+    /// Both tries are walked together, following only the characters they
+    /// share, so branches that exist in just one of them are never visited.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let frequencies = trie! { "cat" => 0.4, "dog" => 0.6 };
+    /// let vectors = trie! { "cat" => vec![0.1, 0.9], "cow" => vec![0.5, 0.5] };
+    /// let joined = frequencies.zip(&vectors);
+    /// assert_eq!(joined.len(), 1);
+    /// assert_eq!(joined.get("cat"), Some(&(0.4, vec![0.1, 0.9])));
+    /// ```
+    pub fn zip<U: Clone>(&self, other: &Trie<U, H>) -> Trie<(TValue, U), H>
+    where
+        TValue: Clone,
+    {
+        let mut word_count = 0;
+        let mut zip_node = |node: &TrieNode<TValue, H>, other: &TrieNode<U, H>| {
+            let mut zipped = node.empty_mapped();
+            if let (Some(value), Some(other_value)) = (node.get_value(), other.get_value()) {
+                zipped.set_value((value.clone(), other_value.clone()));
+                word_count += 1;
+            }
+            zipped
+        };
+
+        // An explicit stack rather than recursion, so long keys cannot
+        // overflow the call stack. Each frame is a node on the current path:
+        // the character leading into it, its zipped copy so far, its children
+        // still to visit, and the matching node of `other`.
+        let mut stack = vec![(
+            '\0',
+            zip_node(&self.root, &other.root),
+            self.root.children_iter(),
+            &other.root,
+        )];
+        let root = loop {
+            let (_, _, children, other_node) = stack.last_mut().expect("the root is popped last");
+            if let Some((&c, child)) = children.next() {
+                if let Some(other_child) = other_node.get_child(c) {
+                    let zipped = zip_node(child, other_child);
+                    stack.push((c, zipped, child.children_iter(), other_child));
+                }
+                continue;
+            }
+
+            let (c, zipped, _, _) = stack.pop().expect("the stack is not empty");
+            let Some((_, parent, _, _)) = stack.last_mut() else {
+                break zipped;
+            };
+            // Branches with no key stored in both tries are left out.
+            if zipped.is_end_of_word() || zipped.has_children() {
+                parent.set_child(c, zipped);
+            }
+        };
+        Trie::from_root(root, word_count)
+    }
+
+    /// Returns a new Trie with the same keys, where each value is
+    /// `f(key, value)`. The values may change type, e.g. to normalize counts
+    /// into frequencies.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let counts = trie! { "the" => 3, "cat" => 1 };
+    /// let frequencies = counts.map_values(|_, count| *count as f64 / 4.0);
+    /// assert_eq!(frequencies.get("the"), Some(&0.75));
+    /// ```
+    pub fn map_values<U, F>(&self, f: F) -> Trie<U, H>
+    where
+        F: Fn(&str, &TValue) -> U,
+    {
+        Trie::build_sorted(
+            self.root.empty_mapped(),
+            self.iter().map(|(key, value)| {
+                let mapped = f(&key, value);
+                (key, mapped)
+            }),
+        )
+    }
+
+    /// Consumes the Trie and returns one with the same keys, where each value
+    /// is `f(key, value)`. Values are moved into `f`, so they need not be
+    /// `Clone`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "a" => vec![1, 2], "b" => vec![3] };
+    /// let lengths = trie.map_values_into(|_, list| list.len());
+    /// assert_eq!(lengths.get("a"), Some(&2));
+    /// ```
+    pub fn map_values_into<U>(self, f: impl Fn(String, TValue) -> U) -> Trie<U, H> {
+        let root = self.root.empty_mapped();
+        // `into_iter` is not in key order, but it is depth-first: the keys under
+        // each prefix arrive together, so `build_sorted` never has to reopen a
+        // closed branch. Any other order would still be correct, only slower.
+        Trie::build_sorted(
+            root,
+            self.into_iter()
+                .map(|(key, value)| (key.clone(), f(key, value))),
+        )
+    }
+
+    /// Returns a new Trie with the keys for which `f(key, value)` returns
+    /// `Some`, each holding the value inside it.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "one" => "1", "two" => "2", "many" => "lots" };
+    /// let numbers = trie.filter_map_values(|_, text| text.parse::<u32>().ok());
+    /// assert_eq!(numbers.len(), 2);
+    /// assert_eq!(numbers.get("two"), Some(&2));
+    /// ```
+    pub fn filter_map_values<U, F>(&self, f: F) -> Trie<U, H>
+    where
+        F: Fn(&str, &TValue) -> Option<U>,
+    {
+        Trie::build_sorted(
+            self.root.empty_mapped(),
+            self.iter()
+                .filter_map(|(key, value)| f(&key, value).map(|mapped| (key, mapped))),
+        )
+    }
+
+    /// Recursive helper for `merge`. Folds `source` into `target`, counting keys
+    /// that were newly added and keys that collided.
+    fn merge_recursively<F>(
+        target: &mut TrieNode<TValue, H>,
+        source: TrieNode<TValue, H>,
+        on_conflict: &mut F,
+        added: &mut usize,
+        conflicts: &mut usize,
+    ) where
+        F: FnMut(TValue, TValue) -> TValue,
+    {
+        let (source_value, source_children) = source.into_parts();
+        if let Some(source_value) = source_value {
+            match target.clear_value() {
+                Some(target_value) => {
+                    target.set_value(on_conflict(target_value, source_value));
+                    *conflicts += 1;
+                }
+                None => {
+                    target.set_value(source_value);
+                    *added += 1;
+                }
+            }
+        }
+
+        for (c, source_child) in source_children {
+            match target.get_child_mut(c) {
+                Some(target_child) => Self::merge_recursively(
+                    target_child,
+                    source_child,
+                    on_conflict,
+                    added,
+                    conflicts,
+                ),
+                None => {
+                    // Nothing to reconcile: move the whole branch across.
+                    *added += Values::new(&source_child).count();
+                    target.set_child(c, source_child);
+                }
+            }
+        }
+    }
+
+    /// Returns an iterator over all (key, value) pairs in the Trie, in
+    /// lexicographic order. It is double-ended, so `iter().rev()` walks the
+    /// entries from the largest key down.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("cat", 1);
+    /// trie.insert("car", 2);
+    /// let total: i32 = trie.iter().map(|(_, value)| value).sum();
+    /// assert_eq!(total, 3);
+    /// assert_eq!(trie.iter().next_back(), Some(("cat".to_string(), &1)));
+    /// ```
+    pub fn iter(&self) -> TrieIter<'_, TValue, H> {
+        TrieIter::new(&self.root)
+    }
+
+    /// Returns an iterator over all (key, value) pairs in the Trie with mutable
+    /// references to the values. The order of iteration is unspecified.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("cat", 1);
+    /// for (_, value) in trie.iter_mut() {
+    ///     *value *= 10;
+    /// }
+    /// assert_eq!(trie.get("cat"), Some(&10));
+    /// ```
+    pub fn iter_mut(&mut self) -> TrieIterMut<'_, TValue, H> {
+        TrieIterMut::new(&mut self.root, self.sorted_children)
+    }
+
+    /// Returns an iterator over the (key, value) pairs with `start <= key < end`,
+    /// in lexicographic order. Yields nothing if `start >= end`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// trie.insert("banana", 2);
+    /// trie.insert("cherry", 3);
+    /// let keys: Vec<String> = trie.range_iter("b", "c").map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec!["banana"]);
+    /// ```
+    pub fn range_iter(&self, start: &str, end: &str) -> RangeIter<'_, TValue, H> {
+        RangeIter::new(&self.root, start, end)
+    }
+
+    /// Returns an iterator over all keys in the Trie in lexicographic order.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("car", 1);
+    /// trie.insert("apple", 2);
+    /// trie.insert("cat", 3);
+    /// let keys: Vec<String> = trie.keys().collect();
+    /// assert_eq!(keys, vec!["apple", "car", "cat"]);
+    /// ```
+    pub fn keys(&self) -> Keys<'_, TValue, H> {
+        Keys::new(&self.root, String::new())
+    }
+
+    /// Returns the lexicographically smallest key, or `None` if the Trie is
+    /// empty.
+    ///
+    /// This follows the smallest child down from the root until it reaches a
+    /// stored key, so it takes one step per character of the result, each
+    /// choosing among a single node's children.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "cat" => 1, "car" => 2, "dog" => 3 };
+    /// assert_eq!(trie.first_key(), Some("car".to_string()));
+    /// ```
+    pub fn first_key(&self) -> Option<String> {
+        let mut key = String::new();
+        let mut node = &self.root;
+        while !node.is_end_of_word() {
+            let (&c, child) = node.children_iter().min_by_key(|&(&c, _)| c)?;
+            key.push(c);
+            node = child;
+        }
+        Some(key)
+    }
+
+    /// Returns the lexicographically largest key, or `None` if the Trie is
+    /// empty.
+    ///
+    /// This follows the largest child down from the root to a leaf, which
+    /// always holds a key, taking one step per character of the result.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "cat" => 1, "car" => 2, "cats" => 3 };
+    /// assert_eq!(trie.last_key(), Some("cats".to_string()));
+    /// ```
+    pub fn last_key(&self) -> Option<String> {
+        let mut key = String::new();
+        let mut node = &self.root;
+        while let Some((&c, child)) = node.children_iter().max_by_key(|&(&c, _)| c) {
+            key.push(c);
+            node = child;
+        }
+        node.is_end_of_word().then_some(key)
+    }
+
+    /// Returns the number of stored keys that sort strictly before `key`,
+    /// which need not be stored itself. A stored key's rank is its position in
+    /// `keys()`.
+    ///
+    /// Nodes do not keep counts of the keys below them, so every branch that
+    /// sorts before `key` is walked in full to count its keys. This takes time
+    /// proportional to the number of nodes in those branches, not to the rank,
+    /// and in the worst case visits every node in the Trie.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "apple" => 1, "banana" => 2, "cherry" => 3 };
+    /// assert_eq!(trie.rank("banana"), 1);
+    /// assert_eq!(trie.rank("blueberry"), 2);
+    /// ```
+    pub fn rank(&self, key: &str) -> usize {
+        let mut rank = 0;
+        let mut node = &self.root;
+        for c in key.chars() {
+            // The node's own key is a proper prefix of `key`, so it sorts first.
+            if node.is_end_of_word() {
+                rank += 1;
+            }
+            rank += node
+                .children_iter()
+                .filter(|&(&other, _)| other < c)
+                .map(|(_, child)| Values::new(child).count())
+                .sum::<usize>();
+            match node.get_child(c) {
+                Some(child) => node = child,
+                None => return rank,
+            }
+        }
+        rank
+    }
+
+    /// Returns the `n`th key in lexicographic order, counting from zero, or
+    /// `None` if fewer than `n + 1` keys are stored. This is the inverse of
+    /// `rank`: `select(rank(key))` is `key` for every stored key.
+    ///
+    /// As with `rank`, the keys below each branch are counted by walking it.
+    /// At every level of the result the branches up to and including the one
+    /// it lies in are walked, and that one is walked again a level down, so
+    /// this takes time proportional to the size of those branches rather than
+    /// to `n`: up to the number of nodes times the length of the result.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "apple" => 1, "banana" => 2, "cherry" => 3 };
+    /// assert_eq!(trie.select(1), Some("banana".to_string()));
+    /// assert_eq!(trie.select(3), None);
+    /// ```
+    pub fn select(&self, n: usize) -> Option<String> {
+        if n >= self.word_count {
+            return None;
+        }
+        let mut remaining = n;
+        let mut key = String::new();
+        let mut node = &self.root;
+        loop {
+            if node.is_end_of_word() {
+                if remaining == 0 {
+                    return Some(key);
+                }
+                remaining -= 1;
+            }
+            let mut next = None;
+            for (&c, child) in node.children_iter_sorted() {
+                let count = Values::new(child).count();
+                if remaining < count {
+                    next = Some((c, child));
+                    break;
+                }
+                remaining -= count;
+            }
+            let (c, child) = next?;
+            key.push(c);
+            node = child;
+        }
+    }
+
+    /// Returns an iterator over the stored keys that are greater than or equal
+    /// to `key`, in lexicographic order: a seek into the sorted key space.
+    /// Branches whose keys all sort before `key` are skipped without being
+    /// walked.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let trie = trie! { "apple" => 1, "banana" => 2, "band" => 3, "cherry" => 4 };
+    /// let keys: Vec<String> = trie.keys_starting_at_or_after("ban").take(2).collect();
+    /// assert_eq!(keys, vec!["banana", "band"]);
+    /// ```
+    pub fn keys_starting_at_or_after(&self, key: &str) -> impl Iterator<Item = String> + '_ {
+        RangeIter::starting_at(&self.root, key).map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over all values in the Trie, without building the
+    /// keys. The order of iteration is unspecified.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("cat", 1);
+    /// trie.insert("car", 2);
+    /// assert_eq!(trie.values().sum::<i32>(), 3);
+    /// ```
+    pub fn values(&self) -> Values<'_, TValue, H> {
+        Values::ordered(&self.root, self.sorted_children)
+    }
+
+    /// Returns an iterator over mutable references to all values in the Trie.
+    /// The order of iteration is unspecified.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("cat", 1);
+    /// trie.values_mut().for_each(|v| *v *= 2);
+    /// assert_eq!(trie.get("cat"), Some(&2));
+    /// ```
+    pub fn values_mut(&mut self) -> ValuesMut<'_, TValue, H> {
+        ValuesMut::new(&mut self.root, self.sorted_children)
+    }
+
+    /// Removes every entry from the Trie, returning them as an iterator of owned
+    /// (key, value) pairs in no particular order. Each entry leaves the Trie as
+    /// it is yielded, so `len` counts down during the drain, and entries the
+    /// iterator does not yield are removed when it is dropped.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("cat", 1);
+    /// let drained: Vec<(String, i32)> = trie.drain().collect();
+    /// assert_eq!(drained, vec![("cat".to_string(), 1)]);
+    /// assert!(trie.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> TrieDrain<'_, TValue, H> {
+        let empty = self.root.empty_like();
+        TrieDrain::new(
+            &mut self.root,
+            &mut self.word_count,
+            self.sorted_children,
+            empty,
+        )
+    }
+
+    /// Renders the structure of the Trie as an indented tree, one node per line,
+    /// with children in sorted order and values shown on terminal nodes.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("car", 1);
+    /// trie.insert("cat", 2);
+    /// print!("{}", trie.display_tree());
+    /// // (root)
+    /// // └── c
+    /// //     └── a
+    /// //         ├── r = 1
+    /// //         └── t = 2
+    /// ```
+    pub fn display_tree(&self) -> String
+    where
+        TValue: fmt::Debug,
+    {
+        let mut out = String::from("(root)");
+        if let Some(value) = self.root.get_value() {
+            out.push_str(&format!(" = {value:?}"));
+        }
+        out.push('\n');
+
+        // Each frame is a node's sorted children plus the indentation its
+        // children's lines start with.
+        let mut stack = vec![(self.root.children_iter_sorted().peekable(), String::new())];
+        while let Some((children, indent)) = stack.last_mut() {
+            let Some((c, child)) = children.next() else {
+                stack.pop();
+                continue;
+            };
+
+            let is_last = children.peek().is_none();
+            let (branch, continuation) = if is_last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            out.push_str(&format!("{indent}{branch}{c}"));
+            if let Some(value) = child.get_value() {
+                out.push_str(&format!(" = {value:?}"));
+            }
+            out.push('\n');
+
+            let child_indent = format!("{indent}{continuation}");
+            stack.push((child.children_iter_sorted().peekable(), child_indent));
+        }
+        out
+    }
+
+    /// Consumes the Trie, moving every entry into a `HashMap`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("apple", 1);
+    /// let map = trie.into_hashmap();
+    /// assert_eq!(map.get("apple"), Some(&1));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn into_hashmap(self) -> HashMap<String, TValue> {
+        self.into_iter().collect()
+    }
+
+    /// Consumes the Trie, moving every entry into a `BTreeMap`, which keeps the
+    /// keys in sorted order.
+    pub fn into_btreemap(self) -> BTreeMap<String, TValue> {
+        self.into_iter().collect()
+    }
+
+    /// Inserts one key per line read from `reader`, with the value `value_fn`
+    /// computes from it. Lines are trimmed and blank lines skipped, and the
+    /// input is read a line at a time, so large files are never held in memory
+    /// whole. Returns how many new keys were added; a line repeating a stored
+    /// key replaces its value without being counted.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let file = File::open("words.txt")?;
+    /// let mut trie = Trie::new();
+    /// let added = trie.insert_from_reader(BufReader::new(file), |_| 1)?;
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn insert_from_reader<R, F>(&mut self, reader: R, value_fn: F) -> io::Result<usize>
+    where
+        R: BufRead,
+        F: Fn(&str) -> TValue,
+    {
+        let before = self.len();
+        for line in reader.lines() {
+            let line = line?;
+            let key = line.trim();
+            if !key.is_empty() {
+                self.insert(key, value_fn(key));
+            }
+        }
+        Ok(self.len() - before)
+    }
+
+    /// Writes every key to `writer` in lexicographic order, one per line, in
+    /// the format `insert_from_reader` reads. Keys that contain line breaks or
+    /// surrounding whitespace will not read back unchanged.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut out = Vec::new();
+    /// trie.export_to_writer(&mut out)?;
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn export_to_writer<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for key in self.keys() {
+            writeln!(writer, "{key}")?;
+        }
+        Ok(())
+    }
+
+    /// The body of `from_sorted_input`, adding the entries below `root`, which
+    /// must be empty. New nodes share its hasher.
+    fn build_sorted<I, S>(mut root: TrieNode<TValue, H>, iter: I) -> Self
+    where
+        I: IntoIterator<Item = (S, TValue)>,
+        S: AsRef<str>,
+    {
+        let blank = root.empty_like();
+        // The nodes below the root on the previous key's path, detached from
+        // their parents until they are closed.
+        let mut open: Vec<(char, TrieNode<TValue, H>)> = Vec::new();
+        let mut word_count = 0;
+
+        for (key, value) in iter {
+            let key = key.as_ref();
+            let shared = key
+                .chars()
+                .zip(&open)
+                .take_while(|(c, (open_c, _))| c == open_c)
+                .count();
+            while open.len() > shared {
+                Self::close_node(&mut root, &mut open);
+            }
+            // Out of order input can come back to a branch that was already
+            // closed; reopen it, and every level of it the key passes through,
+            // rather than replacing it. Below the first missing child all nodes
+            // are new, so sorted input only pays for one failed lookup per key.
+            let mut reopening = true;
+            for c in key.chars().skip(shared) {
+                let parent = open.last_mut().map_or(&mut root, |(_, node)| node);
+                let existing = if reopening {
+                    parent.remove_child(c)
+                } else {
+                    None
+                };
+                reopening = existing.is_some();
+                open.push((c, existing.unwrap_or_else(|| blank.empty_like())));
+            }
+            let node = open.last_mut().map_or(&mut root, |(_, node)| node);
+            if node.set_value(value).is_none() {
+                word_count += 1;
+            }
+        }
+        while !open.is_empty() {
+            Self::close_node(&mut root, &mut open);
+        }
+        Self::from_root(root, word_count)
+    }
+
+    /// Pops the deepest open node and attaches it to its parent.
+    fn close_node(root: &mut TrieNode<TValue, H>, open: &mut Vec<(char, TrieNode<TValue, H>)>) {
+        if let Some((c, node)) = open.pop() {
+            let parent = open.last_mut().map_or(root, |(_, node)| node);
+            parent.set_child(c, node);
+        }
+    }
+
+    /// Adds multiple words to the trie from a list, using a value generator function
+    /// to determine the value associated with each word.
+    /// This is useful for bulk insertion where the value might depend on the word itself.
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.add_word_list(&["cat", "car", "cart"], |word| word.len());
+    /// assert_eq!(trie.get("cat"), Some(&3));
+    /// assert_eq!(trie.get("car"), Some(&3));
+    /// assert_eq!(trie.get("cart"), Some(&4));
+    /// ```
+    pub fn add_word_list<T, F>(&mut self, items: &[T], value_generator: F)
+    where
+        T: AsRef<str>,
+        F: Fn(&T) -> TValue,
+    {
+        for item in items {
+            self.insert(item.as_ref(), value_generator(item));
+        }
+    }
+}
+
+/// One level of the depth-first walk in `collect_words`: how long the key is
+/// at this node, and the node's children that are still to be visited.
+struct Frame<'a, TValue, H> {
+    key_len: usize,
+    children: SortedChildIter<'a, TValue, H>,
+}
+
+/// Allows creating a new Trie with `Trie::default()`.
+impl<TValue, H: Default> Default for Trie<TValue, H> {
     fn default() -> Self {
-        Self::new()
+        Trie {
+            root: TrieNode::new(),
+            word_count: 0,
+            sorted_children: false,
+        }
+    }
+}
+
+/// Formats the Trie as its entries in sorted key order, e.g.
+/// `Trie { "apple" => 1, "car" => 2 }`.
+impl<TValue: fmt::Debug, H: BuildHasher + Clone> fmt::Debug for Trie<TValue, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<(String, &TValue)> = self.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        write!(f, "Trie {{")?;
+        for (i, (key, value)) in entries.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{separator}{key:?} => {value:?}")?;
+        }
+        if entries.is_empty() {
+            write!(f, "}}")
+        } else {
+            write!(f, " }}")
+        }
+    }
+}
+
+/// Allows `trie["key"]` lookups, panicking if the key is not stored.
+///
+/// Example:
+/// ```Rust
+/// let mut trie = Trie::new();
+/// trie.insert("apple", 1);
+/// assert_eq!(trie["apple"], 1);
+/// ```
+impl<TValue, H: BuildHasher + Clone> Index<&str> for Trie<TValue, H> {
+    type Output = TValue;
+
+    fn index(&self, key: &str) -> &Self::Output {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("key '{key}' not found in Trie"),
+        }
     }
 }
+
+/// `&a | &b` is `a.union(&b)`.
+impl<TValue: Clone, H: BuildHasher + Clone> BitOr<&Trie<TValue, H>> for &Trie<TValue, H> {
+    type Output = Trie<TValue, H>;
+
+    fn bitor(self, other: &Trie<TValue, H>) -> Trie<TValue, H> {
+        self.union(other)
+    }
+}
+
+/// `&a & &b` is `a.intersection(&b)`.
+impl<TValue: Clone, W, H: BuildHasher + Clone> BitAnd<&Trie<W, H>> for &Trie<TValue, H> {
+    type Output = Trie<TValue, H>;
+
+    fn bitand(self, other: &Trie<W, H>) -> Trie<TValue, H> {
+        self.intersection(other)
+    }
+}
+
+/// `&a - &b` is `a.difference(&b)`.
+impl<TValue: Clone, W, H: BuildHasher + Clone> Sub<&Trie<W, H>> for &Trie<TValue, H> {
+    type Output = Trie<TValue, H>;
+
+    fn sub(self, other: &Trie<W, H>) -> Trie<TValue, H> {
+        self.difference(other)
+    }
+}
+
+/// Two tries are equal when they store the same (key, value) pairs, regardless
+/// of the order the keys were inserted in.
+impl<TValue: PartialEq, H: BuildHasher + Clone> PartialEq for Trie<TValue, H> {
+    fn eq(&self, other: &Self) -> bool {
+        // The O(1) count check rules out most unequal tries before any traversal.
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(&key) == Some(value))
+    }
+}
+
+impl<TValue: Eq, H: BuildHasher + Clone> Eq for Trie<TValue, H> {}
+
+/// Orders tries lexicographically by their (key, value) pairs in key order, so
+/// a trie with a smaller first key is smaller, tries with the same keys are
+/// ordered by their values, and the empty trie is smaller than any other.
+impl<TValue: PartialOrd, H: BuildHasher + Clone> PartialOrd for Trie<TValue, H> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+/// Lets tries be sorted and used as `BTreeMap` keys or `BTreeSet` members.
+impl<TValue: Ord, H: BuildHasher + Clone> Ord for Trie<TValue, H> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+/// Hashes the (key, value) pairs in lexicographic key order, so tries that
+/// compare equal hash equally whatever order their keys were inserted in.
+/// This lets a Trie be a `HashMap` key or a `HashSet` member.
+impl<TValue: Hash, H: BuildHasher + Clone> Hash for Trie<TValue, H> {
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        state.write_usize(self.len());
+        for (key, value) in self.iter() {
+            key.hash(state);
+            value.hash(state);
+        }
+    }
+}
+
+/// Consumes the Trie, yielding owned (key, value) pairs in unspecified order.
+impl<TValue, H> IntoIterator for Trie<TValue, H> {
+    type Item = (String, TValue);
+    type IntoIter = IntoIter<TValue, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.root, self.word_count, self.sorted_children)
+    }
+}
+
+impl<'a, TValue, H: BuildHasher + Clone> IntoIterator for &'a Trie<TValue, H> {
+    type Item = (String, &'a TValue);
+    type IntoIter = TrieIter<'a, TValue, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, TValue, H: BuildHasher + Clone> IntoIterator for &'a mut Trie<TValue, H> {
+    type Item = (String, &'a mut TValue);
+    type IntoIter = TrieIterMut<'a, TValue, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Inserts every (key, value) pair from the iterator. Keys that are already
+/// present are overwritten, just like repeated calls to `insert`.
+///
+/// Example:
+/// ```Rust
+/// let mut trie = Trie::new();
+/// trie.insert("hello", 1);
+/// trie.extend(vec![("hello", 10), ("world", 2)]);
+/// assert_eq!(trie.get("hello"), Some(&10));
+/// ```
+impl<K: AsRef<str>, TValue, H: BuildHasher + Clone> Extend<(K, TValue)> for Trie<TValue, H> {
+    fn extend<I: IntoIterator<Item = (K, TValue)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key.as_ref(), value);
+        }
+    }
+}
+
+/// Builds a Trie from (key, value) pairs. Later duplicates overwrite earlier
+/// ones, just like repeated calls to `insert`.
+///
+/// Example:
+/// ```Rust
+/// let trie: Trie<i32> = vec![("hello", 1), ("world", 2)].into_iter().collect();
+/// assert_eq!(trie.get("world"), Some(&2));
+/// ```
+impl<K: AsRef<str>, TValue, H: BuildHasher + Clone + Default> FromIterator<(K, TValue)>
+    for Trie<TValue, H>
+{
+    fn from_iter<I: IntoIterator<Item = (K, TValue)>>(iter: I) -> Self {
+        let mut trie = Trie::default();
+        trie.extend(iter);
+        trie
+    }
+}
+
+/// Builds a Trie holding every entry of the map.
+#[cfg(feature = "std")]
+impl<TValue> From<HashMap<String, TValue>> for Trie<TValue> {
+    fn from(map: HashMap<String, TValue>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+/// Builds a Trie holding every entry of the map.
+impl<TValue> From<BTreeMap<String, TValue>> for Trie<TValue> {
+    fn from(map: BTreeMap<String, TValue>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+// Word lists map each word to its position. A word listed twice keeps the
+// index of its last occurrence, as if the list were inserted in order.
+macro_rules! impl_indexed_word_list_conversions {
+    ($($list:ty),*) => {$(
+        /// Builds a Trie mapping each word to its 0-based index in the list.
+        impl<'a, 'b> From<$list> for Trie<usize> {
+            fn from(words: $list) -> Self {
+                words
+                    .iter()
+                    .enumerate()
+                    .map(|(index, word)| (word, index))
+                    .collect()
+            }
+        }
+    )*};
+}
+
+impl_indexed_word_list_conversions!(Vec<&'a str>, Vec<String>, &'a [&'b str]);
+
+// A blanket `K: AsRef<str>` impl for word sets would overlap with the pair impls
+// above for `Trie<()>`, so the common string types are listed individually.
+macro_rules! impl_word_set_conversions {
+    ($($key:ty),*) => {$(
+        /// Adds every key from the iterator to the word set.
+        impl<'a, 'b> Extend<$key> for Trie<()> {
+            fn extend<I: IntoIterator<Item = $key>>(&mut self, iter: I) {
+                for key in iter {
+                    self.insert(AsRef::<str>::as_ref(&key), ());
+                }
+            }
+        }
+
+        /// Builds a word set from an iterator of keys.
+        impl<'a, 'b> FromIterator<$key> for Trie<()> {
+            fn from_iter<I: IntoIterator<Item = $key>>(iter: I) -> Self {
+                let mut trie = Trie::new();
+                trie.extend(iter);
+                trie
+            }
+        }
+    )*};
+}
+
+impl_word_set_conversions!(&'a str, &'a &'b str, String, &'a String);
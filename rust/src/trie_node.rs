@@ -1,52 +1,204 @@
-use std::collections::HashMap;
+use crate::map::{ChildMap, DefaultHashBuilder, SORTED_CHILDREN, child_map};
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::BuildHasher;
+#[cfg(not(all(feature = "std", not(feature = "smallvec-children"))))]
+use core::marker::PhantomData;
 
-/// Represents a node within a Trie. It is generic over the value it stores.
-pub struct TrieNode<TValue> {
+/// Iterator over a node's children, as returned by `TrieNode::children_iter`.
+pub type ChildIter<'a, TValue, H = DefaultHashBuilder> =
+    child_map::Iter<'a, char, TrieNode<TValue, H>>;
+
+/// Mutable iterator over a node's children.
+pub type ChildIterMut<'a, TValue, H = DefaultHashBuilder> =
+    child_map::IterMut<'a, char, TrieNode<TValue, H>>;
+
+/// Owning iterator over a node's children.
+pub type ChildIntoIter<TValue, H = DefaultHashBuilder> =
+    child_map::IntoIter<char, TrieNode<TValue, H>>;
+
+/// Iterator over a node's children in ascending `char` order.
+pub type SortedChildIter<'a, TValue, H = DefaultHashBuilder> =
+    alloc::vec::IntoIter<(&'a char, &'a TrieNode<TValue, H>)>;
+
+/// A node's children in map order, or in ascending `char` order for a Trie
+/// built with sorted children when the map does not keep them that way.
+pub(crate) enum OrderedChildren<I, S> {
+    Map(I),
+    Sorted(S),
+}
+
+impl<I: Iterator, S: Iterator<Item = I::Item>> Iterator for OrderedChildren<I, S> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            OrderedChildren::Map(children) => children.next(),
+            OrderedChildren::Sorted(children) => children.next(),
+        }
+    }
+}
+
+/// Iterator over a node's children, as returned by `children_iter_ordered`.
+pub(crate) type OrderedChildIter<'a, TValue, H> =
+    OrderedChildren<ChildIter<'a, TValue, H>, SortedChildIter<'a, TValue, H>>;
+
+/// Mutable iterator over a node's children, as returned by
+/// `value_and_children_mut_ordered`.
+pub(crate) type OrderedChildIterMut<'a, TValue, H> = OrderedChildren<
+    ChildIterMut<'a, TValue, H>,
+    alloc::vec::IntoIter<(&'a char, &'a mut TrieNode<TValue, H>)>,
+>;
+
+/// Owning iterator over a node's children, as returned by `into_parts_ordered`.
+pub(crate) type OrderedChildIntoIter<TValue, H> =
+    OrderedChildren<ChildIntoIter<TValue, H>, alloc::vec::IntoIter<(char, TrieNode<TValue, H>)>>;
+
+// Only a `HashMap` takes the hasher; the other children maps ignore it.
+#[cfg(all(feature = "std", not(feature = "smallvec-children")))]
+type Children<TValue, H> = ChildMap<char, TrieNode<TValue, H>, H>;
+#[cfg(not(all(feature = "std", not(feature = "smallvec-children"))))]
+type Children<TValue, H> = ChildMap<char, TrieNode<TValue, H>>;
+
+/// Represents a node within a Trie. It is generic over the value it stores and
+/// the hasher of its children map.
+#[derive(Clone)]
+pub struct TrieNode<TValue, H = DefaultHashBuilder> {
     // Note: The `character` for this node is the *key* in the parent's HashMap.
     // We don't need to store it inside the node itself.
-    children: HashMap<char, TrieNode<TValue>>,
+    children: Children<TValue, H>,
 
     /// The value associated with the full word ending at this node.
     /// Using Option is key, as intermediate nodes won't have a value.
     value: Option<TValue>,
+
+    // Keeps the hasher parameter in use when the children map does not hash.
+    #[cfg(not(all(feature = "std", not(feature = "smallvec-children"))))]
+    hasher: PhantomData<H>,
 }
 
-impl<TValue> TrieNode<TValue> {
+impl<TValue, H: Default> TrieNode<TValue, H> {
     /// Creates a new, "empty" TrieNode without an initial value.
     /// This is the correct constructor for a node that isn't the end of a word yet.
     pub fn new() -> Self {
+        Self::from_children(Children::default())
+    }
+
+    /// Creates an empty TrieNode whose children map has room for `capacity`
+    /// children before it reallocates. Without the `std` feature the children
+    /// live in a `BTreeMap`, which cannot reserve space, and this is `new`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        #[cfg(all(feature = "std", not(feature = "smallvec-children")))]
+        let children = ChildMap::with_capacity_and_hasher(capacity, H::default());
+        #[cfg(feature = "smallvec-children")]
+        let children = ChildMap::with_capacity(capacity);
+        #[cfg(not(any(feature = "std", feature = "smallvec-children")))]
+        let children = {
+            let _ = capacity;
+            ChildMap::new()
+        };
+        Self::from_children(children)
+    }
+}
+
+impl<TValue, H> TrieNode<TValue, H> {
+    /// Creates an empty TrieNode whose children map hashes with `hasher`.
+    pub fn with_hasher(hasher: H) -> Self {
+        #[cfg(all(feature = "std", not(feature = "smallvec-children")))]
+        return Self::from_children(ChildMap::with_hasher(hasher));
+        #[cfg(not(all(feature = "std", not(feature = "smallvec-children"))))]
+        {
+            let _ = hasher;
+            Self::from_children(Children::default())
+        }
+    }
+
+    fn from_children(children: Children<TValue, H>) -> Self {
         Self {
-            children: HashMap::new(),
+            children,
             value: None,
+            #[cfg(not(all(feature = "std", not(feature = "smallvec-children"))))]
+            hasher: PhantomData,
         }
     }
+}
 
-    /// Checks if the node has any children. A node with no children is a "leaf".
-    pub fn has_children(&self) -> bool {
-        !self.children.is_empty()
+impl<TValue, H: BuildHasher + Clone> TrieNode<TValue, H> {
+    /// Creates an empty TrieNode that hashes its children the way this one does.
+    pub fn empty_like(&self) -> Self {
+        self.empty_mapped()
+    }
+
+    /// Creates an empty TrieNode for values of another type that hashes its
+    /// children the way this one does.
+    pub fn empty_mapped<U>(&self) -> TrieNode<U, H> {
+        #[cfg(all(feature = "std", not(feature = "smallvec-children")))]
+        return TrieNode::with_hasher(self.children.hasher().clone());
+        #[cfg(not(all(feature = "std", not(feature = "smallvec-children"))))]
+        return TrieNode::from_children(Children::default());
     }
 
     /// Gets an immutable reference to a child node corresponding to the character.
-    pub fn get_child(&self, character: char) -> Option<&TrieNode<TValue>> {
+    pub fn get_child(&self, character: char) -> Option<&TrieNode<TValue, H>> {
         self.children.get(&character)
     }
 
     /// Gets a mutable reference to a child node corresponding to the character.
-    pub fn get_child_mut(&mut self, character: char) -> Option<&mut TrieNode<TValue>> {
+    pub fn get_child_mut(&mut self, character: char) -> Option<&mut TrieNode<TValue, H>> {
         self.children.get_mut(&character)
     }
 
     /// Adds a child node for the given character if it doesn't exist,
     /// and returns a mutable reference to it.
-    pub fn add_child(&mut self, character: char) -> &mut TrieNode<TValue> {
-        // .or_insert_with() is perfect here. It calls TrieNode::new() only if
-        // the `character` key is not already in the HashMap.
-        self.children.entry(character).or_default()
+    pub fn add_child(&mut self, character: char) -> &mut TrieNode<TValue, H> {
+        // .or_insert_with() only builds the child if the `character` key is not
+        // already in the map. New children share this node's hasher.
+        #[cfg(all(feature = "std", not(feature = "smallvec-children")))]
+        let hasher = self.children.hasher().clone();
+        #[cfg(all(feature = "std", not(feature = "smallvec-children")))]
+        return self
+            .children
+            .entry(character)
+            .or_insert_with(|| TrieNode::with_hasher(hasher));
+        #[cfg(not(all(feature = "std", not(feature = "smallvec-children"))))]
+        return self
+            .children
+            .entry(character)
+            .or_insert_with(|| TrieNode::from_children(Children::default()));
+    }
+
+    /// Attaches an existing node as the child for the given character, replacing
+    /// any previous child.
+    pub fn set_child(&mut self, character: char, child: TrieNode<TValue, H>) {
+        self.children.insert(character, child);
+    }
+
+    /// Removes a child node, returning it if it existed.
+    pub fn remove_child(&mut self, character: char) -> Option<TrieNode<TValue, H>> {
+        self.children.remove(&character)
+    }
+}
+
+impl<TValue, H> TrieNode<TValue, H> {
+    /// Checks if the node has any children. A node with no children is a "leaf".
+    pub fn has_children(&self) -> bool {
+        !self.children.is_empty()
+    }
+
+    /// Returns the number of children of this node.
+    pub fn child_count(&self) -> usize {
+        self.children.len()
     }
 
-    /// Removes a child node.
-    pub fn remove_child(&mut self, character: char) {
-        self.children.remove(&character);
+    /// Returns how many children this node's map can hold without reallocating.
+    /// A `BTreeMap` (without the `std` feature) has no spare capacity to report,
+    /// and a `SmallMap` (with `smallvec-children`) allocates each child on its
+    /// own, so for those this is the length.
+    pub fn children_capacity(&self) -> usize {
+        #[cfg(all(feature = "std", not(feature = "smallvec-children")))]
+        return self.children.capacity();
+        #[cfg(not(all(feature = "std", not(feature = "smallvec-children"))))]
+        return self.children.len();
     }
 
     /// Checks if this node represents the end of a complete word.
@@ -59,16 +211,89 @@ impl<TValue> TrieNode<TValue> {
         self.value.as_ref()
     }
 
-    pub fn children_iter(&self) -> impl Iterator<Item = (&char, &TrieNode<TValue>)> {
+    /// Gets a mutable reference to the value stored in this node.
+    pub fn get_value_mut(&mut self) -> Option<&mut TValue> {
+        self.value.as_mut()
+    }
+
+    pub fn children_iter(&self) -> ChildIter<'_, TValue, H> {
         self.children.iter()
     }
 
+    /// Iterates over the children with mutable access to each child node.
+    pub fn children_iter_mut(&mut self) -> ChildIterMut<'_, TValue, H> {
+        self.children.iter_mut()
+    }
+
+    /// Iterates over the children in ascending `char` order. Unlike `children_iter`,
+    /// this has to collect and sort the children first.
+    pub fn children_iter_sorted(&self) -> SortedChildIter<'_, TValue, H> {
+        let mut children: Vec<_> = self.children.iter().collect();
+        children.sort_unstable_by_key(|(c, _)| **c);
+        children.into_iter()
+    }
+
+    /// Splits the node into its value and its children so both can be borrowed
+    /// mutably at the same time.
+    pub fn value_and_children_mut(&mut self) -> (Option<&mut TValue>, ChildIterMut<'_, TValue, H>) {
+        (self.value.as_mut(), self.children.iter_mut())
+    }
+
+    /// Consumes the node, returning its value and an owning iterator over its children.
+    pub fn into_parts(self) -> (Option<TValue>, ChildIntoIter<TValue, H>) {
+        (self.value, self.children.into_iter())
+    }
+
+    /// Like `children_iter`, but in ascending `char` order if `sorted` is set.
+    /// The children are only collected and sorted when the map does not
+    /// already keep them in order.
+    pub(crate) fn children_iter_ordered(&self, sorted: bool) -> OrderedChildIter<'_, TValue, H> {
+        if sorted && !SORTED_CHILDREN {
+            OrderedChildren::Sorted(self.children_iter_sorted())
+        } else {
+            OrderedChildren::Map(self.children.iter())
+        }
+    }
+
+    /// Like `value_and_children_mut`, but with the children in ascending
+    /// `char` order if `sorted` is set.
+    pub(crate) fn value_and_children_mut_ordered(
+        &mut self,
+        sorted: bool,
+    ) -> (Option<&mut TValue>, OrderedChildIterMut<'_, TValue, H>) {
+        let children = if sorted && !SORTED_CHILDREN {
+            let mut children: Vec<_> = self.children.iter_mut().collect();
+            children.sort_unstable_by_key(|(c, _)| **c);
+            OrderedChildren::Sorted(children.into_iter())
+        } else {
+            OrderedChildren::Map(self.children.iter_mut())
+        };
+        (self.value.as_mut(), children)
+    }
+
+    /// Like `into_parts`, but with the children in ascending `char` order if
+    /// `sorted` is set.
+    pub(crate) fn into_parts_ordered(
+        self,
+        sorted: bool,
+    ) -> (Option<TValue>, OrderedChildIntoIter<TValue, H>) {
+        let children = if sorted && !SORTED_CHILDREN {
+            let mut children: Vec<_> = self.children.into_iter().collect();
+            children.sort_unstable_by_key(|&(c, _)| c);
+            OrderedChildren::Sorted(children.into_iter())
+        } else {
+            OrderedChildren::Map(self.children.into_iter())
+        };
+        (self.value, children)
+    }
+
     // It's useful for the Trie to be able to set and clear the value.
     // These methods should be part of the node's public API.
 
     /// Sets the value for this node, marking it as the end of a word.
-    pub fn set_value(&mut self, value: TValue) {
-        self.value = Some(value);
+    /// Returns the value it replaced, if there was one.
+    pub fn set_value(&mut self, value: TValue) -> Option<TValue> {
+        self.value.replace(value)
     }
 
     /// Clears the value from this node, un-marking it as the end of a word.
@@ -79,8 +304,18 @@ impl<TValue> TrieNode<TValue> {
 }
 
 // It's also idiomatic to implement the Default trait.
-impl<TValue> Default for TrieNode<TValue> {
+impl<TValue, H: Default> Default for TrieNode<TValue, H> {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Shows the node's own value and how many children it has, without descending.
+impl<TValue: fmt::Debug, H> fmt::Debug for TrieNode<TValue, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrieNode")
+            .field("value", &self.value)
+            .field("children", &self.children.len())
+            .finish()
+    }
+}
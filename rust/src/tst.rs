@@ -0,0 +1,370 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Index of a node in the arena.
+type NodeId = u32;
+
+/// Marks a missing link, so links need no `Option` tag and stay 4 bytes.
+const NIL: NodeId = NodeId::MAX;
+
+/// A ternary search tree: a Trie where each node holds one character and
+/// three links, to the subtrees of smaller (`lo`) and larger (`hi`) characters
+/// at the same position and to the next position (`eq`).
+///
+/// Siblings form a binary search tree instead of a per-node map, so a node is
+/// a fixed 20 bytes plus its value, lookups compare characters rather than
+/// hash them, and walks come out in sorted order. Like any unbalanced binary
+/// tree, sibling chains stay shallow for keys inserted in random order but
+/// degrade for sorted input. Nodes live in an arena, as in `ArenaTrie`.
+///
+/// The API mirrors `Trie`.
+#[derive(Clone)]
+pub struct TstTrie<TValue> {
+    nodes: Vec<TstNode<TValue>>,
+    // Slots of deleted nodes, handed out again before the arena grows.
+    free: Vec<NodeId>,
+    root: NodeId,
+    // Nodes stand for characters, so the empty key's value is kept aside.
+    empty_key_value: Option<TValue>,
+    word_count: usize,
+}
+
+#[derive(Clone)]
+struct TstNode<TValue> {
+    c: char,
+    lo: NodeId,
+    eq: NodeId,
+    hi: NodeId,
+    value: Option<TValue>,
+}
+
+/// Where a link to a node is stored.
+#[derive(Clone, Copy)]
+enum Link {
+    Root,
+    Lo(NodeId),
+    Eq(NodeId),
+    Hi(NodeId),
+}
+
+impl<TValue> TstTrie<TValue> {
+    /// Initializes a new, empty TstTrie.
+    pub fn new() -> Self {
+        TstTrie {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NIL,
+            empty_key_value: None,
+            word_count: 0,
+        }
+    }
+
+    /// Returns the number of words stored in the TstTrie. This is O(1).
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    /// Returns true if the TstTrie contains no words.
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
+    /// Inserts a key-value pair, returning the previous value if the key was
+    /// already stored.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = TstTrie::new();
+    /// assert_eq!(trie.insert("apple", 1), None);
+    /// assert_eq!(trie.insert("apple", 2), Some(1));
+    /// ```
+    pub fn insert(&mut self, key: &str, value: TValue) -> Option<TValue> {
+        let slot = match self.find_or_add(key) {
+            Some(id) => &mut self.node_mut(id).value,
+            None => &mut self.empty_key_value,
+        };
+        let previous = slot.replace(value);
+        if previous.is_none() {
+            self.word_count += 1;
+        }
+        previous
+    }
+
+    /// Gets the value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&TValue> {
+        if key.is_empty() {
+            return self.empty_key_value.as_ref();
+        }
+        self.node(self.find_node(key)?).value.as_ref()
+    }
+
+    /// Gets a mutable reference to the value stored under `key`.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut TValue> {
+        if key.is_empty() {
+            return self.empty_key_value.as_mut();
+        }
+        let id = self.find_node(key)?;
+        self.node_mut(id).value.as_mut()
+    }
+
+    /// Deletes a key and its value, freeing any nodes that only existed for it.
+    /// Returns true if the key was found. As with `Trie`, the empty key cannot
+    /// be removed this way.
+    pub fn delete(&mut self, key: &str) -> bool {
+        if key.is_empty() {
+            return false;
+        }
+
+        // Every link followed on the way down, so nodes can be unlinked from
+        // their parents on the way back up.
+        let mut path = Vec::new();
+        let mut link = Link::Root;
+        let mut chars = key.chars();
+        let Some(mut c) = chars.next() else {
+            return false;
+        };
+        let found = loop {
+            let id = self.target(link);
+            if id == NIL {
+                return false;
+            }
+            path.push(link);
+            let node = self.node(id);
+            link = if c < node.c {
+                Link::Lo(id)
+            } else if c > node.c {
+                Link::Hi(id)
+            } else {
+                match chars.next() {
+                    Some(next) => {
+                        c = next;
+                        Link::Eq(id)
+                    }
+                    None => break id,
+                }
+            };
+        };
+        if self.node_mut(found).value.take().is_none() {
+            return false;
+        }
+        self.word_count -= 1;
+
+        // A node with no value and nothing after it is only there to route to
+        // its siblings: drop it if it has none, or let its only sibling subtree
+        // take its place. Emptying a node can leave its parent unneeded too.
+        while let Some(link) = path.pop() {
+            let id = self.target(link);
+            let node = self.node(id);
+            if node.value.is_some() || node.eq != NIL {
+                break;
+            }
+            let replacement = match (node.lo, node.hi) {
+                (NIL, only) | (only, NIL) => only,
+                _ => break,
+            };
+            self.set_target(link, replacement);
+            self.free.push(id);
+            if replacement != NIL {
+                break;
+            }
+        }
+        true
+    }
+
+    /// Returns true if any stored word starts with `prefix`.
+    pub fn prefix_search(&self, prefix: &str) -> bool {
+        if prefix.is_empty() {
+            return !self.is_empty();
+        }
+        // Every subtree holds at least one word, so the prefix has words
+        // under it unless it is only a routing node for its siblings.
+        self.find_node(prefix).is_some_and(|id| {
+            let node = self.node(id);
+            node.value.is_some() || node.eq != NIL
+        })
+    }
+
+    /// Returns up to `max_results` words that start with `prefix`, in
+    /// lexicographic order.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = TstTrie::new();
+    /// trie.insert("apricot", 1);
+    /// trie.insert("apple", 2);
+    /// trie.insert("app", 3);
+    /// assert_eq!(trie.auto_complete("ap", 2), vec!["app", "apple"]);
+    /// ```
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        let mut results = Vec::new();
+        if max_results == 0 {
+            return results;
+        }
+        let start = if prefix.is_empty() {
+            if self.empty_key_value.is_some() {
+                results.push(String::new());
+            }
+            self.root
+        } else {
+            let Some(id) = self.find_node(prefix) else {
+                return results;
+            };
+            if self.node(id).value.is_some() {
+                results.push(prefix.to_string());
+            }
+            self.node(id).eq
+        };
+
+        // In-order walk with an explicit stack: smaller siblings, then the
+        // node's own word, then the words continuing through it, then larger
+        // siblings. Each entry is a subtree and the key leading into it.
+        enum Step {
+            Visit(NodeId, String),
+            Emit(String),
+        }
+        let mut stack = vec![Step::Visit(start, prefix.to_string())];
+        while let Some(step) = stack.pop() {
+            if results.len() >= max_results {
+                break;
+            }
+            match step {
+                Step::Emit(key) => results.push(key),
+                Step::Visit(NIL, _) => {}
+                Step::Visit(id, key) => {
+                    let node = self.node(id);
+                    let mut own_key = key.clone();
+                    own_key.push(node.c);
+                    stack.push(Step::Visit(node.hi, key.clone()));
+                    stack.push(Step::Visit(node.eq, own_key.clone()));
+                    if node.value.is_some() {
+                        stack.push(Step::Emit(own_key));
+                    }
+                    stack.push(Step::Visit(node.lo, key));
+                }
+            }
+        }
+        results
+    }
+
+    /// Adds multiple words, using `value_generator` to compute each word's value.
+    pub fn add_word_list<T, F>(&mut self, items: &[T], value_generator: F)
+    where
+        T: AsRef<str>,
+        F: Fn(&T) -> TValue,
+    {
+        for item in items {
+            self.insert(item.as_ref(), value_generator(item));
+        }
+    }
+
+    /// Returns the number of nodes in use. Freed slots that are waiting to be
+    /// reused are not counted.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    fn node(&self, id: NodeId) -> &TstNode<TValue> {
+        &self.nodes[id as usize]
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> &mut TstNode<TValue> {
+        &mut self.nodes[id as usize]
+    }
+
+    fn target(&self, link: Link) -> NodeId {
+        match link {
+            Link::Root => self.root,
+            Link::Lo(id) => self.node(id).lo,
+            Link::Eq(id) => self.node(id).eq,
+            Link::Hi(id) => self.node(id).hi,
+        }
+    }
+
+    fn set_target(&mut self, link: Link, target: NodeId) {
+        match link {
+            Link::Root => self.root = target,
+            Link::Lo(id) => self.node_mut(id).lo = target,
+            Link::Eq(id) => self.node_mut(id).eq = target,
+            Link::Hi(id) => self.node_mut(id).hi = target,
+        }
+    }
+
+    /// Returns the node for the last character of a non-empty `key`.
+    fn find_node(&self, key: &str) -> Option<NodeId> {
+        let mut id = self.root;
+        let mut chars = key.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if id == NIL {
+                return None;
+            }
+            let node = self.node(id);
+            if c < node.c {
+                id = node.lo;
+            } else if c > node.c {
+                id = node.hi;
+            } else {
+                chars.next();
+                if chars.peek().is_none() {
+                    return Some(id);
+                }
+                id = node.eq;
+            }
+        }
+        None
+    }
+
+    /// Returns the node for the last character of `key`, adding the missing
+    /// nodes on the way, or `None` for the empty key.
+    fn find_or_add(&mut self, key: &str) -> Option<NodeId> {
+        let mut link = Link::Root;
+        let mut chars = key.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            let mut id = self.target(link);
+            if id == NIL {
+                id = self.allocate(c);
+                self.set_target(link, id);
+            }
+            let node = self.node(id);
+            link = if c < node.c {
+                Link::Lo(id)
+            } else if c > node.c {
+                Link::Hi(id)
+            } else {
+                chars.next();
+                if chars.peek().is_none() {
+                    return Some(id);
+                }
+                Link::Eq(id)
+            };
+        }
+        None
+    }
+
+    fn allocate(&mut self, c: char) -> NodeId {
+        let node = TstNode {
+            c,
+            lo: NIL,
+            eq: NIL,
+            hi: NIL,
+            value: None,
+        };
+        if let Some(id) = self.free.pop() {
+            self.nodes[id as usize] = node;
+            return id;
+        }
+        let id = NodeId::try_from(self.nodes.len())
+            .ok()
+            .filter(|&id| id != NIL)
+            .expect("TstTrie cannot hold more than u32::MAX - 1 nodes");
+        self.nodes.push(node);
+        id
+    }
+}
+
+/// Allows creating a new TstTrie with `TstTrie::default()`.
+impl<TValue> Default for TstTrie<TValue> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,210 @@
+//! Read-only access to a Trie's structure: `TrieRef`, a view of the subtree
+//! under some prefix, and `Cursor`, which walks the nodes one character at a
+//! time.
+//!
+//! These are the extension points for algorithms the crate does not provide
+//! itself, such as Aho-Corasick automata, DFA construction or longest common
+//! substring searches. Both borrow the Trie immutably, so nothing they do can
+//! change it.
+
+use crate::iter::{PrefixIter, TrieIter};
+use crate::map::DefaultHashBuilder;
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// A read-only view of the subtree under one node of a Trie, created by
+/// `Trie::as_ref` for the whole Trie or `Trie::node_at` for a prefix.
+///
+/// Keys passed to and returned by a TrieRef are relative to that node: in the
+/// view at `"ca"`, `get("t")` looks up `"cat"` and `iter` yields `"t"` for it.
+pub struct TrieRef<'a, TValue, H = DefaultHashBuilder> {
+    node: &'a TrieNode<TValue, H>,
+}
+
+// Derived impls would needlessly require `TValue: Clone`.
+impl<TValue, H> Clone for TrieRef<'_, TValue, H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<TValue, H> Copy for TrieRef<'_, TValue, H> {}
+
+impl<TValue, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Returns a read-only view of the whole Trie.
+    pub fn as_ref(&self) -> TrieRef<'_, TValue, H> {
+        TrieRef { node: self.root() }
+    }
+
+    /// Returns a cursor at the root, for walking the Trie one character at a
+    /// time. Same as `trie.as_ref().cursor()`.
+    pub fn cursor(&self) -> Cursor<'_, TValue, H> {
+        self.as_ref().cursor()
+    }
+
+    /// Returns a read-only view of the subtree under `prefix`, or `None` if no
+    /// key starts with `prefix`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("car", 1);
+    /// trie.insert("cat", 2);
+    /// let view = trie.node_at("ca").unwrap();
+    /// assert_eq!(view.get("t"), Some(&2));
+    /// assert_eq!(view.auto_complete("", 10), vec!["r", "t"]);
+    /// ```
+    pub fn node_at(&self, prefix: &str) -> Option<TrieRef<'_, TValue, H>> {
+        self.find_node(prefix).map(|node| TrieRef { node })
+    }
+}
+
+impl<'a, TValue, H: BuildHasher + Clone> TrieRef<'a, TValue, H> {
+    /// Gets the value stored under `key`, relative to this view.
+    pub fn get(&self, key: &str) -> Option<&'a TValue> {
+        self.find(key)?.get_value()
+    }
+
+    /// Returns true if any key in this view starts with `prefix`.
+    pub fn prefix_search(&self, prefix: &str) -> bool {
+        self.find(prefix).is_some()
+    }
+
+    /// Returns up to `max_results` keys in this view that start with `prefix`,
+    /// in lexicographic order.
+    pub fn auto_complete(&self, prefix: &str, max_results: usize) -> Vec<String> {
+        match self.find(prefix) {
+            Some(node) => PrefixIter::new(node, prefix.to_string())
+                .take(max_results)
+                .map(|(key, _)| key)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns an iterator over the (key, value) pairs in this view, in
+    /// lexicographic order.
+    pub fn iter(&self) -> TrieIter<'a, TValue, H> {
+        TrieIter::new(self.node)
+    }
+
+    /// Returns a cursor positioned at this view's node.
+    pub fn cursor(&self) -> Cursor<'a, TValue, H> {
+        Cursor {
+            path: vec![self.node],
+            key: String::new(),
+        }
+    }
+
+    fn find(&self, key: &str) -> Option<&'a TrieNode<TValue, H>> {
+        let mut node = self.node;
+        for c in key.chars() {
+            node = node.get_child(c)?;
+        }
+        Some(node)
+    }
+}
+
+impl<'a, TValue, H: BuildHasher + Clone> IntoIterator for TrieRef<'a, TValue, H> {
+    type Item = (String, &'a TValue);
+    type IntoIter = TrieIter<'a, TValue, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Walks a Trie one edge at a time, remembering the path back to where it
+/// started. Created by `Trie::cursor` at the root or by `TrieRef::cursor`.
+///
+/// Example:
+/// ```Rust
+/// let mut trie = Trie::new();
+/// trie.insert("cat", 1);
+/// let mut cursor = trie.cursor();
+/// assert!(cursor.advance('c') && cursor.advance('a'));
+/// assert!(!cursor.advance('x')); // no "cax"; the cursor stays at "ca"
+/// assert_eq!(cursor.child_chars().collect::<Vec<_>>(), vec!['t']);
+/// assert!(cursor.advance('t'));
+/// assert_eq!((cursor.current_key(), cursor.value()), ("cat", Some(&1)));
+/// assert!(cursor.backtrack());
+/// assert_eq!(cursor.current_key(), "ca");
+/// ```
+pub struct Cursor<'a, TValue, H = DefaultHashBuilder> {
+    // The nodes from the starting node down to the current one.
+    path: Vec<&'a TrieNode<TValue, H>>,
+    key: String,
+}
+
+impl<TValue, H> Clone for Cursor<'_, TValue, H> {
+    fn clone(&self) -> Self {
+        Cursor {
+            path: self.path.clone(),
+            key: self.key.clone(),
+        }
+    }
+}
+
+impl<'a, TValue, H: BuildHasher + Clone> Cursor<'a, TValue, H> {
+    /// Moves to the child reached by `c` and returns true, or returns false
+    /// and stays put if there is no such child.
+    pub fn advance(&mut self, c: char) -> bool {
+        match self.current().get_child(c) {
+            Some(child) => {
+                self.path.push(child);
+                self.key.push(c);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves back to the parent node and returns true, or returns false if the
+    /// cursor is at the node it started from.
+    pub fn backtrack(&mut self) -> bool {
+        if self.path.len() == 1 {
+            return false;
+        }
+        self.path.pop();
+        self.key.pop();
+        true
+    }
+
+    /// Returns the value stored at the current node, if its key is stored.
+    pub fn value(&self) -> Option<&'a TValue> {
+        self.current().get_value()
+    }
+
+    /// Returns the characters leading to the current node's children, in
+    /// ascending order.
+    pub fn child_chars(&self) -> impl Iterator<Item = char> + 'a {
+        self.current().children_iter_sorted().map(|(&c, _)| c)
+    }
+
+    /// Returns the characters followed from the starting node to here.
+    pub fn current_key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the number of characters followed from the starting node.
+    pub fn depth(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    /// Returns a view of the subtree under the current node.
+    pub fn view(&self) -> TrieRef<'a, TValue, H> {
+        TrieRef {
+            node: self.current(),
+        }
+    }
+
+    fn current(&self) -> &'a TrieNode<TValue, H> {
+        self.path
+            .last()
+            .expect("the path always holds the starting node")
+    }
+}
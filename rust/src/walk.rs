@@ -0,0 +1,107 @@
+use crate::trie::Trie;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+/// Tells `Trie::walk` and `Trie::walk_mut` how to continue after visiting a
+/// node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkControl {
+    /// Carry on into this node's children.
+    Continue,
+    /// Leave this node's children out, but carry on with the rest of the walk.
+    SkipSubtree,
+    /// End the walk immediately.
+    Stop,
+}
+
+impl<TValue, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Visits every node under `prefix`, starting with the node for `prefix`
+    /// itself, in lexicographic pre-order. The visitor gets each node's key and
+    /// its value, if the key is stored, and decides through the returned
+    /// `WalkControl` whether to descend, skip the node's subtree, or stop.
+    /// Nothing is visited if no key starts with `prefix`.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("car", 1);
+    /// trie.insert("cart", 2);
+    /// trie.insert("cat", 3);
+    /// let mut words = Vec::new();
+    /// trie.walk("ca", |key, value| {
+    ///     if value.is_some() {
+    ///         words.push(key.to_string());
+    ///         // Don't look for longer words under a match
+    ///         return WalkControl::SkipSubtree;
+    ///     }
+    ///     WalkControl::Continue
+    /// });
+    /// assert_eq!(words, vec!["car", "cat"]);
+    /// ```
+    pub fn walk<F>(&self, prefix: &str, mut visitor: F)
+    where
+        F: FnMut(&str, Option<&TValue>) -> WalkControl,
+    {
+        let Some(start) = self.find_node(prefix) else {
+            return;
+        };
+
+        let mut stack = vec![(prefix.to_string(), start)];
+        while let Some((key, node)) = stack.pop() {
+            match visitor(&key, node.get_value()) {
+                WalkControl::Continue => {}
+                WalkControl::SkipSubtree => continue,
+                WalkControl::Stop => return,
+            }
+            // Pushed in reverse so the smallest character is visited next.
+            for (&c, child) in node.children_iter_sorted().rev() {
+                let mut child_key = key.clone();
+                child_key.push(c);
+                stack.push((child_key, child));
+            }
+        }
+    }
+
+    /// Like `walk`, but hands the visitor mutable access to each value.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("car", 1);
+    /// trie.insert("cat", 2);
+    /// trie.walk_mut("", |_, value| {
+    ///     if let Some(value) = value {
+    ///         *value *= 10;
+    ///     }
+    ///     WalkControl::Continue
+    /// });
+    /// assert_eq!(trie.get("cat"), Some(&20));
+    /// ```
+    pub fn walk_mut<F>(&mut self, prefix: &str, mut visitor: F)
+    where
+        F: FnMut(&str, Option<&mut TValue>) -> WalkControl,
+    {
+        let Some(start) = self.find_node_mut(prefix) else {
+            return;
+        };
+
+        let mut stack = vec![(prefix.to_string(), start)];
+        while let Some((key, node)) = stack.pop() {
+            let (value, children) = node.value_and_children_mut();
+            match visitor(&key, value) {
+                WalkControl::Continue => {}
+                WalkControl::SkipSubtree => continue,
+                WalkControl::Stop => return,
+            }
+            let mut children: Vec<_> = children.collect();
+            children.sort_unstable_by_key(|(c, _)| **c);
+            for (&c, child) in children.into_iter().rev() {
+                let mut child_key = key.clone();
+                child_key.push(c);
+                stack.push((child_key, child));
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::trie::Trie;
+use crate::trie_node::TrieNode;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeSet as VisitedSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+#[cfg(feature = "std")]
+use std::collections::HashSet as VisitedSet;
+
+impl<TValue, H: BuildHasher + Clone> Trie<TValue, H> {
+    /// Returns every stored key matching a glob-style `pattern`, sorted by key.
+    /// `?` matches any single character and `*` matches zero or more characters;
+    /// every other character matches itself.
+    ///
+    /// Example:
+    /// ```Rust
+    /// let mut trie = Trie::new();
+    /// trie.insert("hello", 1);
+    /// trie.insert("hippo", 2);
+    /// trie.insert("ho", 3);
+    /// assert_eq!(trie.wildcard_match("h?llo"), vec![("hello".to_string(), &1)]);
+    /// assert_eq!(trie.wildcard_match("h*o").len(), 3);
+    /// ```
+    pub fn wildcard_match(&self, pattern: &str) -> Vec<(String, &TValue)> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let mut search = WildcardSearch {
+            pattern: &pattern,
+            visited: VisitedSet::new(),
+            key: String::new(),
+            results: Vec::new(),
+        };
+        search.visit(self.root(), 0);
+
+        let mut results = search.results;
+        results.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        results
+    }
+}
+
+/// State for the backtracking walk behind `wildcard_match`.
+struct WildcardSearch<'p, 'a, TValue, H> {
+    pattern: &'p [char],
+    // `*` can reach the same node at the same pattern position along several
+    // paths; each pair only needs exploring once, which also keeps a key from
+    // being reported twice.
+    visited: VisitedSet<(*const TrieNode<TValue, H>, usize)>,
+    key: String,
+    results: Vec<(String, &'a TValue)>,
+}
+
+impl<'a, TValue, H: BuildHasher + Clone> WildcardSearch<'_, 'a, TValue, H> {
+    fn visit(&mut self, node: &'a TrieNode<TValue, H>, position: usize) {
+        if !self.visited.insert((node as *const _, position)) {
+            return;
+        }
+
+        let Some(&token) = self.pattern.get(position) else {
+            if let Some(value) = node.get_value() {
+                self.results.push((self.key.clone(), value));
+            }
+            return;
+        };
+
+        match token {
+            '*' => {
+                // Consume nothing, or consume one more character and keep the star.
+                self.visit(node, position + 1);
+                for (&c, child) in node.children_iter() {
+                    self.descend(c, child, position);
+                }
+            }
+            '?' => {
+                for (&c, child) in node.children_iter() {
+                    self.descend(c, child, position + 1);
+                }
+            }
+            literal => {
+                if let Some(child) = node.get_child(literal) {
+                    self.descend(literal, child, position + 1);
+                }
+            }
+        }
+    }
+
+    fn descend(&mut self, c: char, child: &'a TrieNode<TValue, H>, position: usize) {
+        self.key.push(c);
+        self.visit(child, position);
+        self.key.pop();
+    }
+}
@@ -0,0 +1,79 @@
+/* Exercises the C API through include/trie_hard.h. Run with tests/ffi/run.sh. */
+
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "trie_hard.h"
+
+static void test_insert_get_delete(void) {
+    TrieHandle *trie = trie_new();
+    int64_t value = 0;
+
+    trie_insert(trie, "apple", 1);
+    trie_insert(trie, "app", 2);
+    trie_insert(trie, "apple", 3);
+
+    assert(trie_get(trie, "apple", &value) && value == 3);
+    assert(trie_get(trie, "app", &value) && value == 2);
+    assert(!trie_get(trie, "ap", &value) && value == 2);
+    assert(trie_get(trie, "app", NULL));
+
+    assert(trie_delete(trie, "app"));
+    assert(!trie_delete(trie, "app"));
+    assert(!trie_get(trie, "app", NULL));
+    assert(trie_get(trie, "apple", NULL));
+
+    trie_free(trie);
+}
+
+static void test_prefix_search_and_auto_complete(void) {
+    TrieHandle *trie = trie_new();
+    const char *words[] = {"car", "card", "care", "cat", "dog"};
+    for (size_t i = 0; i < sizeof words / sizeof *words; i++) {
+        trie_insert(trie, words[i], (int64_t)i);
+    }
+
+    assert(trie_prefix_search(trie, "ca"));
+    assert(!trie_prefix_search(trie, "cow"));
+
+    char *out[3];
+    size_t len = 0;
+    trie_auto_complete(trie, "car", 3, out, &len);
+    assert(len == 3);
+    assert(strcmp(out[0], "car") == 0);
+    assert(strcmp(out[1], "card") == 0);
+    assert(strcmp(out[2], "care") == 0);
+    for (size_t i = 0; i < len; i++) {
+        trie_string_free(out[i]);
+    }
+
+    trie_auto_complete(trie, "x", 3, out, &len);
+    assert(len == 0);
+
+    trie_free(trie);
+}
+
+static void test_null_arguments(void) {
+    TrieHandle *trie = trie_new();
+    size_t len = 1;
+
+    trie_insert(trie, NULL, 1);
+    assert(!trie_get(trie, NULL, NULL));
+    assert(!trie_get(NULL, "a", NULL));
+    assert(!trie_delete(NULL, "a"));
+    trie_auto_complete(NULL, "", 5, NULL, &len);
+    assert(len == 0);
+    trie_string_free(NULL);
+    trie_free(NULL);
+
+    trie_free(trie);
+}
+
+int main(void) {
+    test_insert_get_delete();
+    test_prefix_search_and_auto_complete();
+    test_null_arguments();
+    puts("ffi_test: ok");
+    return 0;
+}
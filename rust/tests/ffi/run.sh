@@ -0,0 +1,11 @@
+#!/bin/sh
+# Builds the library as a static archive with the `ffi` feature, then compiles
+# and runs the C test against include/trie_hard.h.
+set -eu
+cd "$(dirname "$0")/../.."
+
+cargo rustc --lib --features ffi --crate-type staticlib
+out="${CARGO_TARGET_DIR:-target}/debug"
+${CC:-cc} -std=c99 -Wall -Wextra -Werror -DTRIE_HARD_FFI -Iinclude tests/ffi/ffi_test.c \
+    "$out/libtrie_hard_rs.a" -lpthread -ldl -lm -o "$out/ffi_test"
+"$out/ffi_test"